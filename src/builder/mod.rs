@@ -1,20 +1,28 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use crate::config::{FontsConfig, ThemesConfig, TokensConfig};
+use crate::css::fonts;
+use crate::js;
 use crate::optimizer;
+use crate::tokens::{self, DesignTokens};
+use crate::tokens::import::ImportResult;
+use crate::typography::{typeset, LanguageRegistry, TypesetOptions};
 
 // Version constant - single source of truth
 pub const VERSION: &str = "0.1.0";
 pub const LICENSE: &str = "Apache-2.0";
 pub const WEBSITE: &str = "https://reedstyle.dev";
 
-pub fn write_output(css: &str, js: &str) -> Result<()> {
+/// Runtime output files, removed by builds without runtime engines
+const RUNTIME_FILES: [&str; 4] = ["dist/reedstyle.js", "dist/reedstyle.min.js", "dist/reedstyle.esm.js", "dist/reedstyle.d.ts"];
+
+pub fn write_output(css: &str, runtime: Option<&js::Bundle>) -> Result<()> {
     // Create dist directory
     fs::create_dir_all("dist")?;
     
     // Add headers to files
     let css_with_header = add_css_header(css, false);
-    let js_with_header = add_js_header(js, false);
     
     // Write development CSS file
     let css_path = Path::new("dist/reedstyle.css");
@@ -23,26 +31,26 @@ pub fn write_output(css: &str, js: &str) -> Result<()> {
     println!("✓ Written: dist/reedstyle.css ({}KB)", css_size);
     
     // Write minified CSS using Lightning CSS
-    let minified_css = optimizer::minify_css(&css)?;
+    let minified_css = optimizer::minify_css(css)?;
     let minified_css_with_header = add_css_header(&minified_css, true);
     let min_css_path = Path::new("dist/reedstyle.min.css");
     fs::write(min_css_path, &minified_css_with_header)?;
     let min_css_size = minified_css_with_header.len() / 1024;
     println!("✓ Written: dist/reedstyle.min.css ({}KB)", min_css_size);
     
-    // Write development JS file
-    let js_path = Path::new("dist/reedstyle.js");
-    fs::write(js_path, &js_with_header)?;
-    let js_size = js_with_header.len() / 1024;
-    println!("✓ Written: dist/reedstyle.js ({}KB)", js_size);
-    
-    // Write minified JS
-    let minified_js = minify_js(&js)?;
-    let minified_js_with_header = add_js_header(&minified_js, true);
-    let min_js_path = Path::new("dist/reedstyle.min.js");
-    fs::write(min_js_path, &minified_js_with_header)?;
-    let min_js_size = minified_js_with_header.len() / 1024;
-    println!("✓ Written: dist/reedstyle.min.js ({}KB)", min_js_size);
+    let js_sizes = match runtime {
+        Some(runtime) => Some(write_runtime(runtime)?),
+        None => {
+            // A stale runtime from an earlier build would still be served
+            for file in RUNTIME_FILES {
+                if Path::new(file).exists() {
+                    fs::remove_file(file)?;
+                    println!("✓ Removed: {}", file);
+                }
+            }
+            None
+        }
+    };
     
     // Copy LICENSE file to dist
     if Path::new("LICENSE").exists() {
@@ -60,13 +68,176 @@ pub fn write_output(css: &str, js: &str) -> Result<()> {
     println!("  CSS: {}KB → {}KB ({}% reduction)", 
         css_size, min_css_size, 
         ((css_size - min_css_size) * 100 / css_size));
-    println!("  JS:  {}KB → {}KB ({}% reduction)", 
-        js_size, min_js_size,
-        ((js_size - min_js_size) * 100 / js_size));
+    match (runtime, js_sizes) {
+        (Some(runtime), Some((js_size, min_js_size))) => {
+            println!("  JS:  {}KB → {}KB ({}% reduction)", 
+                js_size / 1024, min_js_size / 1024,
+                ((js_size - min_js_size) * 100 / js_size));
+            let modules: Vec<String> = runtime.sizes.iter()
+                .filter(|(_, size)| *size > 0)
+                .map(|(name, size)| format!("{} {:.1}KB", name, *size as f64 / 1024.0))
+                .collect();
+            println!("       minified by module: {}", modules.join(", "));
+        }
+        _ => println!("  JS:  none (no runtime engines used)"),
+    }
     
     Ok(())
 }
 
+/// Write the runtime builds; returns the bytes of reedstyle.js and reedstyle.min.js
+fn write_runtime(runtime: &js::Bundle) -> Result<(usize, usize)> {
+    // Write development JS file
+    let js_with_header = add_js_header(&runtime.script, false);
+    let js_path = Path::new("dist/reedstyle.js");
+    fs::write(js_path, &js_with_header)?;
+    println!("✓ Written: dist/reedstyle.js ({}KB)", js_with_header.len() / 1024);
+    
+    // Write minified JS
+    let minified_js = js::minify(&runtime.script)?;
+    let minified_js_with_header = add_js_header(&minified_js, true);
+    let min_js_path = Path::new("dist/reedstyle.min.js");
+    fs::write(min_js_path, &minified_js_with_header)?;
+    println!("✓ Written: dist/reedstyle.min.js ({}KB)", minified_js_with_header.len() / 1024);
+    
+    // Write the ES module build and the type declarations for both
+    let esm_with_header = add_js_header(&runtime.module, false);
+    fs::write("dist/reedstyle.esm.js", &esm_with_header)?;
+    println!("✓ Written: dist/reedstyle.esm.js ({}KB)", esm_with_header.len() / 1024);
+    
+    let declarations_with_header = add_js_header(&runtime.declarations, false);
+    fs::write("dist/reedstyle.d.ts", &declarations_with_header)?;
+    println!("✓ Written: dist/reedstyle.d.ts ({}KB)", declarations_with_header.len() / 1024);
+    
+    Ok((js_with_header.len(), minified_js_with_header.len()))
+}
+
+pub fn write_tokens(design_tokens: &DesignTokens, config: &TokensConfig) -> Result<()> {
+    fs::create_dir_all(&config.output)?;
+    
+    for format in &config.formats {
+        let content = design_tokens.render(*format)?;
+        let path = Path::new(&config.output).join(tokens::file_name(*format));
+        fs::write(&path, &content)?;
+        println!("✓ Written: {} ({}KB)", path.display(), content.len() / 1024);
+    }
+    
+    Ok(())
+}
+
+/// Copy declared font files into `dist/fonts` and write preload hints
+pub fn write_fonts(fonts_config: &FontsConfig) -> Result<()> {
+    if fonts_config.loading.copy {
+        for path in fonts::copy_font_files(fonts_config, &Path::new("dist").join(fonts::FONTS_DIR))? {
+            println!("✓ Copied: {}", path.display());
+        }
+    }
+
+    let hints = fonts::generate_preload_hints(fonts_config);
+    if !hints.is_empty() {
+        let path = Path::new("dist").join("reedstyle.preload.html");
+        fs::write(&path, &hints)?;
+        println!("✓ Written: {} ({}KB)", path.display(), hints.len() / 1024);
+    }
+
+    Ok(())
+}
+
+/// Write the inline `<head>` script that applies the chosen theme before
+/// first paint, or remove a stale one when there are no themes
+pub fn write_themes(themes: &ThemesConfig) -> Result<()> {
+    let path = Path::new("dist").join("reedstyle.theme.html");
+
+    match js::theme_bootstrap(themes)? {
+        Some(script) => {
+            // A `</script>` in a stylesheet URL would end the element early
+            let html = format!("<script>{}</script>\n", script.replace("</", "<\\/"));
+            fs::write(&path, &html)?;
+            println!("✓ Written: {} ({}KB)", path.display(), html.len() / 1024);
+        }
+        None if path.exists() => {
+            fs::remove_file(&path)?;
+            println!("✓ Removed: {}", path.display());
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// Write imported colors and fonts as YAML next to (not over) the project config
+pub fn write_import(result: &ImportResult, output: &str) -> Result<()> {
+    fs::create_dir_all(output)?;
+
+    let files = [
+        ("reedstyle.colors.yaml", result.colors_yaml()?),
+        ("reedstyle.fonts.yaml", result.fonts_yaml()?),
+    ];
+    for (name, content) in files {
+        let path = Path::new(output).join(name);
+        fs::write(&path, &content)?;
+        println!("✓ Written: {} ({}KB)", path.display(), content.len() / 1024);
+    }
+
+    Ok(())
+}
+
+/// Typeset every HTML file matching `pattern`, in place or into `output`
+/// (keeping paths relative to the pattern's directory)
+pub fn write_typeset(
+    pattern: &str,
+    output: Option<&str>,
+    languages: &LanguageRegistry,
+    options: &TypesetOptions,
+) -> Result<Vec<PathBuf>> {
+    let base = glob_base(pattern);
+    let mut written = Vec::new();
+
+    for entry in glob::glob(pattern)? {
+        let source = entry?;
+        if !source.is_file() {
+            continue;
+        }
+
+        let html = fs::read_to_string(&source)?;
+        let typeset = typeset::typeset_html(&html, languages, options)?;
+
+        let target = match output {
+            Some(output) => Path::new(output).join(source.strip_prefix(&base).unwrap_or(&source)),
+            None => source.clone(),
+        };
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, &typeset)?;
+        println!("✓ Typeset: {}", target.display());
+        written.push(target);
+    }
+
+    if written.is_empty() {
+        bail!("No files match '{}'", pattern);
+    }
+
+    Ok(written)
+}
+
+/// The leading directories of a glob pattern that contain no wildcards
+fn glob_base(pattern: &str) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in Path::new(pattern).components() {
+        let part = component.as_os_str().to_string_lossy();
+        if part.contains(['*', '?', '[']) {
+            break;
+        }
+        base.push(component);
+    }
+    // A pattern without wildcards names a file, whose directory is the base
+    if base == Path::new(pattern) {
+        base.pop();
+    }
+    base
+}
+
 fn add_css_header(css: &str, minified: bool) -> String {
     let header = if minified {
         format!("/* ReedSTYLE v{} | {} | {} */\n", VERSION, LICENSE, WEBSITE)
@@ -116,41 +287,3 @@ fn add_js_header(js: &str, minified: bool) -> String {
 }
 
 // Old minify_css function replaced by Lightning CSS in optimizer module
-
-fn minify_js(js: &str) -> Result<String> {
-    // For now, just use basic minification since minify-js has issues
-    // TODO: Fix minify-js or use a different library
-    Ok(basic_minify_js(js))
-}
-
-fn basic_minify_js(js: &str) -> String {
-    js.lines()
-        .map(|line| {
-            // Remove line comments
-            if let Some(pos) = line.find("//") {
-                // Check if it's not in a string
-                let before = &line[..pos];
-                if !before.contains('"') && !before.contains('\'') {
-                    before.trim().to_string()
-                } else {
-                    line.trim().to_string()
-                }
-            } else {
-                line.trim().to_string()
-            }
-        })
-        .filter(|line| !line.is_empty())
-        .collect::<Vec<_>>()
-        .join(" ")
-        .replace("  ", " ")
-        .replace(" = ", "=")
-        .replace(" + ", "+")
-        .replace(" - ", "-")
-        .replace(" * ", "*")
-        .replace(" / ", "/")
-        .replace(" { ", "{")
-        .replace(" } ", "}")
-        .replace("; ", ";")
-        .replace(": ", ":")
-        .replace(", ", ",")
-}
\ No newline at end of file
@@ -1,3 +1,7 @@
+mod resolve;
+
+pub use resolve::resolve_colors;
+
 use anyhow::Result;
 use palette::{Srgb, IntoColor, Oklch};
 
@@ -102,11 +106,11 @@ pub fn generate_color_scale(oklch_color: &str) -> Result<ColorScale> {
         return Ok(ColorScale::grayscale());
     }
     
-    let base_lightness = parts[0].trim_end_matches('%').parse::<f32>().unwrap_or(50.0) / 100.0;
     let base_chroma = parts[1].parse::<f32>().unwrap_or(0.1);
     let hue = parts[2].parse::<f32>().unwrap_or(0.0);
     
-    // Generate perceptually uniform scale
+    // Generate perceptually uniform scale - lightness is fixed per step,
+    // only chroma and hue come from the base color
     // Target lightness values for each step (1-9)
     let target_lightness = [
         0.95,  // 1 - near white
@@ -187,6 +191,15 @@ pub struct ColorScale {
 }
 
 impl ColorScale {
+    /// All nine steps in order, 1 (lightest) to 9 (darkest)
+    pub fn steps(&self) -> [&str; 9] {
+        [
+            &self.scale_1, &self.scale_2, &self.scale_3,
+            &self.scale_4, &self.scale_5, &self.scale_6,
+            &self.scale_7, &self.scale_8, &self.scale_9,
+        ]
+    }
+    
     /// Create a grayscale fallback
     fn grayscale() -> Self {
         generate_neutral_scale()
@@ -0,0 +1,173 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use crate::config::{Color, ColorsConfig};
+use super::to_oklch;
+
+/// Resolve every color to a concrete OKLCH value, following references
+///
+/// Fails on references to undefined colors and on reference cycles, so a
+/// typo never reaches the CSS as an undefined custom property. Semantic
+/// entries must name a defined color or be a color value themselves.
+pub fn resolve_colors(colors: &ColorsConfig) -> Result<HashMap<String, String>> {
+    let mut resolved = HashMap::new();
+
+    let mut names: Vec<&String> = colors.colors.keys().collect();
+    names.sort();
+
+    for name in names {
+        let mut chain = Vec::new();
+        resolve(name, colors, &mut resolved, &mut chain)?;
+    }
+
+    let mut semantic: Vec<(&String, &String)> = colors.semantic.iter().collect();
+    semantic.sort();
+
+    for (name, target) in semantic {
+        if let Color::Reference(target) = Color::from_string(target.clone())
+            && !colors.colors.contains_key(&target)
+        {
+            bail!(
+                "Semantic color '{}' references '{}', which is not defined{}",
+                name, target, suggestion(&target, colors)
+            );
+        }
+    }
+
+    Ok(resolved)
+}
+
+fn resolve(
+    name: &str,
+    colors: &ColorsConfig,
+    resolved: &mut HashMap<String, String>,
+    chain: &mut Vec<String>,
+) -> Result<String> {
+    if let Some(value) = resolved.get(name) {
+        return Ok(value.clone());
+    }
+
+    if chain.iter().any(|n| n == name) {
+        chain.push(name.to_string());
+        bail!("Color reference cycle: {}", chain.join(" → "));
+    }
+
+    let Some(color) = colors.colors.get(name) else {
+        let from = chain.last().map(String::as_str).unwrap_or(name);
+        bail!(
+            "Color '{}' references '{}', which is not defined{}",
+            from, name, suggestion(name, colors)
+        );
+    };
+
+    let value = match color {
+        Color::Hex(value) => to_oklch(value)?,
+        Color::Reference(target) => follow(name, target, colors, resolved, chain)?,
+        Color::Object { oklch: Some(oklch), .. } => oklch.clone(),
+        Color::Object { hex: Some(hex), .. } => to_oklch(hex)?,
+        Color::Object { reference: Some(target), .. } => follow(name, target, colors, resolved, chain)?,
+        Color::Object { .. } => "transparent".to_string(),
+    };
+
+    resolved.insert(name.to_string(), value.clone());
+    Ok(value)
+}
+
+fn follow(
+    name: &str,
+    target: &str,
+    colors: &ColorsConfig,
+    resolved: &mut HashMap<String, String>,
+    chain: &mut Vec<String>,
+) -> Result<String> {
+    chain.push(name.to_string());
+    let value = resolve(target, colors, resolved, chain)?;
+    chain.pop();
+    Ok(value)
+}
+
+/// " (did you mean 'brand-a'?)" for names one or two edits away from a defined color
+fn suggestion(name: &str, colors: &ColorsConfig) -> String {
+    colors.colors.keys()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, candidate)| format!(" (did you mean '{}'?)", candidate))
+        .unwrap_or_default()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(entries: &[(&str, &str)]) -> ColorsConfig {
+        ColorsConfig {
+            colors: entries.iter().map(|(n, v)| (n.to_string(), Color::from_string(v.to_string()))).collect(),
+            semantic: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_reference_chain_resolves() {
+        let colors = config(&[("brand-a", "#3B82F6"), ("brand-b", "brand-a"), ("state-info", "brand-b")]);
+        let resolved = resolve_colors(&colors).unwrap();
+
+        assert!(resolved["brand-a"].starts_with("oklch("));
+        assert_eq!(resolved["state-info"], resolved["brand-a"]);
+    }
+
+    #[test]
+    fn test_dangling_reference() {
+        let colors = config(&[("brand-a", "#3B82F6"), ("brand-b", "brnad-a")]);
+        let error = resolve_colors(&colors).unwrap_err().to_string();
+
+        assert!(error.contains("'brand-b' references 'brnad-a'"));
+        assert!(error.contains("did you mean 'brand-a'"));
+    }
+
+    #[test]
+    fn test_reference_cycle() {
+        let colors = config(&[("brand-a", "brand-c"), ("brand-b", "brand-a"), ("brand-c", "brand-b")]);
+        let error = resolve_colors(&colors).unwrap_err().to_string();
+
+        assert_eq!(error, "Color reference cycle: brand-a → brand-c → brand-b → brand-a");
+    }
+
+    #[test]
+    fn test_yaml_strings_are_classified() {
+        let colors: ColorsConfig = serde_yaml::from_str(
+            "colors:\n  brand-a: \"#3B82F6\"\n  brand-b: brand-a\n  brand-c:\n    reference: brand-a\nsemantic:\n  primary: brand-a\n"
+        ).unwrap();
+
+        assert!(matches!(colors.colors["brand-a"], Color::Hex(_)));
+        assert!(matches!(colors.colors["brand-b"], Color::Reference(_)));
+        assert!(resolve_colors(&colors).is_ok());
+    }
+
+    #[test]
+    fn test_named_colors_are_values() {
+        let colors = config(&[("white", "white"), ("clear", "transparent"), ("ink", "currentColor"), ("paper", "white")]);
+        let resolved = resolve_colors(&colors).unwrap();
+
+        assert!(matches!(colors.colors["clear"], Color::Hex(_)));
+        assert_eq!(resolved["paper"], "white");
+        assert_eq!(resolved["clear"], "transparent");
+        assert_eq!(resolved["ink"], "currentColor");
+    }
+}
@@ -16,6 +16,8 @@ impl Default for Config {
                 components: "./reedstyle.components.yaml".to_string(),
                 bridge: "./reedstyle.bridge.yaml".to_string(),
                 env: "./reedstyle.env.yaml".to_string(),
+                typography: default_typography_path(),
+                api: default_api_path(),
             },
             output: OutputPaths {
                 css: "./dist/reedstyle.css".to_string(),
@@ -26,6 +28,38 @@ impl Default for Config {
                 generate_variations: true,
                 custom_properties: true,
             },
+            tokens: TokensConfig::default(),
+            runtime: RuntimeConfig::default(),
+            themes: ThemesConfig::default(),
+        }
+    }
+}
+
+impl Default for ThemesConfig {
+    fn default() -> Self {
+        ThemesConfig {
+            default: "auto".to_string(),
+            storage: "reedstyle-theme".to_string(),
+            available: Default::default(),
+        }
+    }
+}
+
+impl Default for ApiDefaults {
+    fn default() -> Self {
+        ApiDefaults {
+            method: "POST".to_string(),
+            headers: Default::default(),
+        }
+    }
+}
+
+impl Default for TokensConfig {
+    fn default() -> Self {
+        // No token files unless formats are listed in reedstyle.config.yaml
+        TokensConfig {
+            formats: Vec::new(),
+            output: "./dist/tokens".to_string(),
         }
     }
 }
@@ -101,38 +135,34 @@ impl Default for FontsConfig {
                 weights.insert("black".to_string(), 900);
                 Some(weights)
             },
+            ..Default::default()
         });
         
         fonts.insert("font-b".to_string(), FontStack {
             family: "'Georgia', 'Cambria', 'Times New Roman', Times, serif".to_string(),
             fallback: "serif".to_string(),
             weights: None,
+            ..Default::default()
         });
         
         fonts.insert("font-c".to_string(), FontStack {
             family: "'SF Mono', Monaco, 'Cascadia Code', 'Roboto Mono', Consolas, 'Courier New', monospace".to_string(),
             fallback: "monospace".to_string(),
             weights: None,
+            ..Default::default()
         });
         
-        FontsConfig { fonts }
+        FontsConfig { fonts, loading: FontLoading::default(), languages: HashMap::new() }
     }
 }
 
-impl Default for ComponentsConfig {
+impl Default for FontLoading {
     fn default() -> Self {
-        // Empty by default - components are optional
-        ComponentsConfig {
-            components: HashMap::new(),
+        FontLoading {
+            strategy: "swap".to_string(),
+            preload: Vec::new(),
+            subset: None,
+            copy: false,
         }
     }
 }
-
-impl Default for BridgeConfig {
-    fn default() -> Self {
-        // Empty by default - no bridge frameworks enabled
-        BridgeConfig {
-            bridge: HashMap::new(),
-        }
-    }
-}
\ No newline at end of file
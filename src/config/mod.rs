@@ -12,6 +12,12 @@ pub struct Config {
     pub config: ConfigPaths,
     pub output: OutputPaths,
     pub features: Features,
+    #[serde(default)]
+    pub tokens: TokensConfig,
+    #[serde(default)]
+    pub runtime: RuntimeConfig,
+    #[serde(default)]
+    pub themes: ThemesConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -28,6 +34,18 @@ pub struct ConfigPaths {
     pub components: String,
     pub bridge: String,
     pub env: String,
+    #[serde(default = "default_typography_path")]
+    pub typography: String,
+    #[serde(default = "default_api_path")]
+    pub api: String,
+}
+
+fn default_typography_path() -> String {
+    "./reedstyle.typography.yaml".to_string()
+}
+
+fn default_api_path() -> String {
+    "./reedstyle.api.yaml".to_string()
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -46,6 +64,118 @@ pub struct Features {
     pub custom_properties: bool,
 }
 
+/// Design token export settings - each listed format is written to `output`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TokensConfig {
+    pub formats: Vec<TokenFormat>,
+    pub output: String,
+}
+
+/// Engines compiled into reedstyle.js; `auto` includes an engine when the
+/// `content` files use it (or always, without `content`)
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RuntimeConfig {
+    /// HTML and template files (globs) scanned for `auto`
+    pub content: Vec<String>,
+    /// `<r-s as="…">` components
+    pub components: Switch,
+    /// Runtime loading of reedstyle.bridge.yaml (`data-fetch-config`)
+    pub bridge: Switch,
+    /// `data-root` color overrides and runtime loading of reedstyle.colors.yaml
+    pub colors: Switch,
+    /// `text="filter:…"` on elements not typeset at build time
+    pub typography: Switch,
+    /// `fx="click:…"`, `scroll:` and `stagger:`
+    pub effects: Switch,
+    /// `ReedStyle.forms` validation and ajax helpers for `<form>`
+    pub forms: Switch,
+    /// `ReedStyle.theme` switching between `themes` (`auto`: when there are any)
+    pub theme: Switch,
+    /// `ReedStyle.api` client for reedstyle.api.yaml (`auto`: when it has endpoints)
+    pub api: Switch,
+}
+
+/// `true`, `false` or `auto`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Switch {
+    On,
+    Off,
+    #[default]
+    Auto,
+}
+
+impl Serialize for Switch {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Switch::On => serializer.serialize_bool(true),
+            Switch::Off => serializer.serialize_bool(false),
+            Switch::Auto => serializer.serialize_str("auto"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Switch {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawSwitch {
+            Bool(bool),
+            Word(String),
+        }
+
+        match RawSwitch::deserialize(deserializer)? {
+            RawSwitch::Bool(true) => Ok(Switch::On),
+            RawSwitch::Bool(false) => Ok(Switch::Off),
+            RawSwitch::Word(word) if word == "auto" => Ok(Switch::Auto),
+            RawSwitch::Word(word) => Err(serde::de::Error::custom(format!("expected true, false or auto, found '{}'", word))),
+        }
+    }
+}
+
+/// Themes switched at runtime, by the `data-theme` attribute of the document
+/// or a stylesheet of their own
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThemesConfig {
+    /// Theme without a stored choice; `auto` picks by `prefers-color-scheme`
+    pub default: String,
+    /// localStorage key of the chosen theme (empty: not remembered)
+    pub storage: String,
+    /// Themes by name
+    pub available: std::collections::BTreeMap<String, Theme>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Theme {
+    /// Matched against `prefers-color-scheme`, and set as `color-scheme`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<ColorScheme>,
+    /// Stylesheet URL loaded while the theme is active
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stylesheet: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenFormat {
+    /// W3C Design Tokens Community Group JSON
+    Dtcg,
+    /// SCSS variables and maps
+    Scss,
+    /// TypeScript `const` module
+    Typescript,
+    /// Tokens Studio for Figma JSON
+    TokensStudio,
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let path = Path::new("reedstyle.config.yaml");
@@ -88,6 +218,28 @@ impl Config {
         }
     }
 
+    pub fn load_typography(&self) -> Result<crate::typography::TypographyConfig> {
+        use crate::typography::TypographyConfig;
+        
+        if Path::new(&self.config.typography).exists() {
+            let content = fs::read_to_string(&self.config.typography)?;
+            let typography: TypographyConfig = serde_yaml::from_str(&content)?;
+            Ok(typography)
+        } else {
+            Ok(TypographyConfig::default())
+        }
+    }
+
+    pub fn load_api(&self) -> Result<ApiConfig> {
+        if Path::new(&self.config.api).exists() {
+            let content = fs::read_to_string(&self.config.api)?;
+            let api: ApiConfig = serde_yaml::from_str(&content)?;
+            Ok(api)
+        } else {
+            Ok(ApiConfig::default())
+        }
+    }
+
     pub fn load_bridge(&self) -> Result<BridgeConfig> {
         if Path::new(&self.config.bridge).exists() {
             let content = fs::read_to_string(&self.config.bridge)?;
@@ -105,7 +257,8 @@ pub struct ColorsConfig {
     pub semantic: std::collections::HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// A color value (`#hex`, `rgb()`, `hsl()`, `oklch()`, named colors) or a reference to another color name
+#[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum Color {
     Hex(String),
@@ -117,37 +270,234 @@ pub enum Color {
     },
 }
 
+impl Color {
+    /// Plain strings are color values when they look like one or CSS reads
+    /// them as a color (`white`, `transparent`, `currentColor`), otherwise references
+    pub fn from_string(value: String) -> Self {
+        use lightningcss::traits::Parse;
+
+        let lower = value.trim().to_lowercase();
+        let is_value = lower.starts_with('#')
+            || ["rgb(", "rgba(", "hsl(", "hsla(", "oklch("].iter().any(|p| lower.starts_with(p))
+            || lightningcss::values::color::CssColor::parse_string(value.trim()).is_ok();
+
+        if is_value {
+            Color::Hex(value)
+        } else {
+            Color::Reference(value)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Untagged string variants cannot be told apart by serde, so strings are classified by content
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawColor {
+            String(String),
+            Object {
+                hex: Option<String>,
+                oklch: Option<String>,
+                reference: Option<String>,
+            },
+        }
+
+        Ok(match RawColor::deserialize(deserializer)? {
+            RawColor::String(value) => Color::from_string(value),
+            RawColor::Object { hex, oklch, reference } => Color::Object { hex, oklch, reference },
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct FontsConfig {
     pub fonts: std::collections::HashMap<String, FontStack>,
+    #[serde(default)]
+    pub loading: FontLoading,
+    /// Per-language stacks (`ja: { font-a: ... }`), emitted as `:lang()` overrides
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub languages: std::collections::HashMap<String, std::collections::HashMap<String, String>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct FontStack {
     pub family: String,
     pub fallback: String,
     pub weights: Option<std::collections::HashMap<String, u16>>,
+    /// Variable font axes (`wght`, `wdth`, `opsz`, `slnt`) with named values;
+    /// a `default` entry replaces the axis default for unset elements
+    #[serde(default)]
+    pub axes: std::collections::HashMap<String, std::collections::HashMap<String, f32>>,
+    /// Local font files - each becomes one `@font-face` rule
+    #[serde(default)]
+    pub files: Vec<FontFile>,
+    /// Overrides `loading.strategy` for this stack
+    pub display: Option<String>,
+    /// Overrides the `loading.subset` range for this stack
+    #[serde(rename = "unicodeRange")]
+    pub unicode_range: Option<String>,
+}
+
+/// One font file for a weight (or variable weight range) and style
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FontFile {
+    pub src: String,
+    pub weight: FontWeight,
+    #[serde(default = "default_font_style")]
+    pub style: String,
+}
+
+/// `400`, or `"100 900"` for variable fonts
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FontWeight {
+    Single(u16),
+    Range(String),
+}
+
+fn default_font_style() -> String {
+    "normal".to_string()
 }
 
+/// How font files are loaded
 #[derive(Debug, Deserialize, Serialize)]
+pub struct FontLoading {
+    /// `font-display` value: swap | block | fallback | optional
+    #[serde(default = "default_font_display")]
+    pub strategy: String,
+    /// Stacks whose regular file gets a `<link rel="preload">` hint
+    #[serde(default)]
+    pub preload: Vec<String>,
+    /// Named `unicode-range` subset: latin | latin-ext | cyrillic | greek
+    pub subset: Option<String>,
+    /// Copy font files to `<output>/fonts` and point `@font-face` at the copies
+    #[serde(default)]
+    pub copy: bool,
+}
+
+fn default_font_display() -> String {
+    "swap".to_string()
+}
+
+/// Endpoints compiled into the runtime's API client - empty by default
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// Endpoints by group and name
+    pub endpoints: std::collections::BTreeMap<String, std::collections::BTreeMap<String, Endpoint>>,
+    pub config: ApiSettings,
+}
+
+/// A path (`{name}` segments are parameters), with the default method or its own
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Endpoint {
+    Path(String),
+    Request {
+        path: String,
+        method: String,
+        /// Retry even though the method is not idempotent (POST, PATCH)
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        retry: bool,
+    },
+}
+
+impl Endpoint {
+    pub fn path(&self) -> &str {
+        match self {
+            Endpoint::Path(path) | Endpoint::Request { path, .. } => path,
+        }
+    }
+
+    pub fn method(&self) -> Option<&str> {
+        match self {
+            Endpoint::Path(_) => None,
+            Endpoint::Request { method, .. } => Some(method),
+        }
+    }
+
+    pub fn retry(&self) -> bool {
+        matches!(self, Endpoint::Request { retry: true, .. })
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ApiSettings {
+    /// Prefixed to every path (empty: same origin)
+    pub base_url: String,
+    pub defaults: ApiDefaults,
+    pub auth: ApiAuth,
+    pub errors: ApiErrors,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ApiDefaults {
+    pub method: String,
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ApiAuth {
+    #[serde(rename = "type")]
+    pub kind: AuthType,
+    /// localStorage key of the token sent with bearer and basic auth
+    pub token_key: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthType {
+    #[default]
+    None,
+    Bearer,
+    Basic,
+    /// The browser's cookies, also sent to another origin
+    Cookie,
+}
+
+/// Retries of idempotent requests after network errors, timeouts and 408/429/502/503/504
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ApiErrors {
+    pub retry: u32,
+    /// Milliseconds per attempt (0: none)
+    pub timeout: u64,
+}
+
+/// Empty by default - components are optional
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct ComponentsConfig {
     pub components: std::collections::HashMap<String, Component>,
 }
 
+/// Unset fields are left out when serialized, so they don't override a base
+/// component when merged
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Component {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub element: Option<String>,  // Optional for extends
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub extends: Option<String>,
-    #[serde(rename = "box")]
+    #[serde(rename = "box", skip_serializing_if = "Option::is_none")]
     pub box_: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub face: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub layout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub device: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fx: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Empty by default - no bridge frameworks enabled
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct BridgeConfig {
     pub bridge: std::collections::HashMap<String, BridgeFramework>,
 }
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use std::collections::HashMap;
+use crate::config::FontsConfig;
+use crate::css::fonts;
 
 /// Generate CSS for user-defined components from YAML config
 /// This creates the actual styles from namespace attributes
-pub fn generate_component_styles(components: &crate::config::ComponentsConfig) -> Result<String> {
+pub fn generate_component_styles(components: &crate::config::ComponentsConfig, fonts: &FontsConfig) -> Result<String> {
     let mut css = String::new();
     
     css.push_str("\n  /* ========== Component Styles ========== */\n");
@@ -26,7 +28,7 @@ pub fn generate_component_styles(components: &crate::config::ComponentsConfig) -
         }
         
         if let Some(text_attr) = &component.text {
-            css.push_str(&parse_text_properties(text_attr)?);
+            css.push_str(&parse_text_properties(text_attr, fonts)?);
         }
         
         if let Some(layout_attr) = &component.layout {
@@ -59,30 +61,30 @@ fn resolve_inheritance(components: &HashMap<String, crate::config::Component>) -
         let mut final_component = component.clone();
         
         // If component extends another, merge properties
-        if let Some(base_name) = &component.extends {
-            if let Some(base_component) = components.get(base_name) {
-                // Merge base component properties (child overrides parent)
-                if final_component.element.is_none() {
-                    final_component.element = base_component.element.clone();
-                }
-                if final_component.box_.is_none() {
-                    final_component.box_ = base_component.box_.clone();
-                }
-                if final_component.face.is_none() {
-                    final_component.face = base_component.face.clone();
-                }
-                if final_component.text.is_none() {
-                    final_component.text = base_component.text.clone();
-                }
-                if final_component.layout.is_none() {
-                    final_component.layout = base_component.layout.clone();
-                }
-                if final_component.device.is_none() {
-                    final_component.device = base_component.device.clone();
-                }
-                if final_component.fx.is_none() {
-                    final_component.fx = base_component.fx.clone();
-                }
+        if let Some(base_name) = &component.extends
+            && let Some(base_component) = components.get(base_name)
+        {
+            // Merge base component properties (child overrides parent)
+            if final_component.element.is_none() {
+                final_component.element = base_component.element.clone();
+            }
+            if final_component.box_.is_none() {
+                final_component.box_ = base_component.box_.clone();
+            }
+            if final_component.face.is_none() {
+                final_component.face = base_component.face.clone();
+            }
+            if final_component.text.is_none() {
+                final_component.text = base_component.text.clone();
+            }
+            if final_component.layout.is_none() {
+                final_component.layout = base_component.layout.clone();
+            }
+            if final_component.device.is_none() {
+                final_component.device = base_component.device.clone();
+            }
+            if final_component.fx.is_none() {
+                final_component.fx = base_component.fx.clone();
             }
         }
         
@@ -246,10 +248,21 @@ fn parse_face_properties(attr: &str) -> Result<String> {
 }
 
 /// Parse text namespace properties
-fn parse_text_properties(attr: &str) -> Result<String> {
+fn parse_text_properties(attr: &str, fonts_config: &FontsConfig) -> Result<String> {
     let mut css = String::new();
     let properties = parse_attribute_string(attr)?;
     
+    // Weights and axis values come from the named font, else from any font declaring them
+    let font = properties.get("font").filter(|font| fonts_config.fonts.contains_key(*font)).cloned();
+    let font = font.as_deref();
+    let font_weights = font.map(|font| fonts::font_weights(&fonts_config.fonts[font])).unwrap_or_default();
+    let font_axes = match font {
+        Some(font) => fonts::font_axes(font, &fonts_config.fonts[font])?,
+        None => Vec::new(),
+    };
+    let axis_tokens = fonts::axis_tokens(fonts_config)?;
+    let mut uses_axes = false;
+    
     for (key, value) in properties {
         match key.as_str() {
             "color" => {
@@ -274,20 +287,28 @@ fn parse_text_properties(attr: &str) -> Result<String> {
                 };
                 css.push_str(&format!("    font-size: {};\n", size_value));
             },
+            "font" => css.push_str(&format!("    font-family: var(--rs-{});\n", value)),
             "weight" => {
-                let weight_value = match value.as_str() {
-                    "thin" => "100",
-                    "light" => "300",
-                    "normal" => "400",
-                    "medium" => "500",
-                    "semibold" => "600",
-                    "bold" => "700",
-                    "extrabold" => "800",
-                    "black" => "900",
-                    _ => &value,
+                let weight_value = match (font, font_weights.iter().any(|(name, _)| *name == value)) {
+                    (Some(font), true) => format!("var(--rs-{}-weight-{})", font, value),
+                    _ => fonts::weight_tokens(fonts_config).into_iter()
+                        .find(|(name, _)| *name == value)
+                        .map(|(_, weight)| weight)
+                        .unwrap_or(value.clone()),
                 };
                 css.push_str(&format!("    font-weight: {};\n", weight_value));
             },
+            axis if fonts::AXES.iter().any(|(tag, _)| *tag == axis) => {
+                let axis_value = match (font, font_axes.iter().any(|(a, name, _)| a == axis && *name == value)) {
+                    (Some(font), true) => format!("var(--rs-{}-{}-{})", font, axis, value),
+                    _ => axis_tokens.iter()
+                        .find(|(a, name, _)| a == axis && *name == value)
+                        .map(|(_, _, token)| token.clone())
+                        .unwrap_or(value.clone()),
+                };
+                css.push_str(&format!("    --rs-{}: {};\n", axis, axis_value));
+                uses_axes = true;
+            },
             "align" => css.push_str(&format!("    text-align: {};\n", value)),
             "transform" => css.push_str(&format!("    text-transform: {};\n", value)),
             "decoration" => css.push_str(&format!("    text-decoration: {};\n", value)),
@@ -297,6 +318,12 @@ fn parse_text_properties(attr: &str) -> Result<String> {
         }
     }
     
+    if uses_axes
+        && let Some(settings) = fonts::variation_settings(fonts_config)
+    {
+        css.push_str(&format!("    font-variation-settings: {};\n", settings));
+    }
+    
     Ok(css)
 }
 
@@ -464,12 +491,9 @@ fn parse_fx_value(value: &str) -> Result<Vec<(String, String)>> {
             }
         } else {
             // Handle simple effect names
-            match effect {
-                "lift" => {
-                    props.push(("transform".to_string(), "translateY(-2px)".to_string()));
-                    props.push(("box-shadow".to_string(), "0 4px 6px rgba(0,0,0,0.1)".to_string()));
-                },
-                _ => {}
+            if effect == "lift" {
+                props.push(("transform".to_string(), "translateY(-2px)".to_string()));
+                props.push(("box-shadow".to_string(), "0 4px 6px rgba(0,0,0,0.1)".to_string()));
             }
         }
     }
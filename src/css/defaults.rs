@@ -99,7 +99,20 @@ pub fn generate_html_defaults() -> String {
     css.push_str("  r-s[as=\"output\"] { display: inline; }\n");
     css.push_str("  r-s[as=\"progress\"] { display: inline-block; vertical-align: baseline; }\n");
     css.push_str("  r-s[as=\"meter\"] { display: inline-block; vertical-align: baseline; }\n\n");
-    
+
+    // Form states, set by the forms engine on forms with `api`; a
+    // `form-error` component restyles the messages
+    css.push_str("  /* Form states */\n");
+    css.push_str("  r-s[as=\"form-error\"] { display: block; font-size: var(--rs-size-small); color: var(--rs-state-error); }\n");
+    css.push_str("  r-s[as=\"form-error\"]:empty { display: none; }\n");
+    css.push_str("  :is(input, select, textarea)[state=\"invalid\"] { border-color: var(--rs-state-error); }\n");
+    css.push_str("  [state=\"loading\"] :is(button, input[type=\"submit\"]) { cursor: progress; opacity: 0.6; }\n");
+    css.push_str("  [api] r-s[when] { display: none; }\n");
+    css.push_str("  [api][state=\"loading\"] r-s[when=\"loading\"], [api][state=\"success\"] r-s[when=\"success\"],\n");
+    css.push_str("  [api][state=\"error\"] r-s[when=\"error\"] { display: block; }\n");
+    css.push_str("  [api] r-s[when=\"success\"] { color: var(--rs-state-success); }\n");
+    css.push_str("  [api] r-s[when=\"error\"] { color: var(--rs-state-error); }\n\n");
+
     // Interactive elements
     css.push_str("  /* Interactive elements */\n");
     css.push_str("  r-s[as=\"details\"] { display: block; }\n");
@@ -163,7 +176,7 @@ pub fn generate_component_defaults(components: &crate::config::ComponentsConfig)
         css.push_str("  }\n");
     }
     
-    css.push_str("\n");
+    css.push('\n');
     Ok(css)
 }
 
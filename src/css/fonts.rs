@@ -0,0 +1,471 @@
+use anyhow::{bail, Result};
+use crate::config::{FontFile, FontStack, FontWeight, FontsConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Weight tokens used when no font in `reedstyle.fonts.yaml` declares `weights`
+pub const DEFAULT_WEIGHTS: &[(&str, u16)] = &[
+    ("thin", 100),
+    ("light", 300),
+    ("normal", 400),
+    ("regular", 400),
+    ("medium", 500),
+    ("semibold", 600),
+    ("bold", 700),
+    ("extrabold", 800),
+    ("black", 900),
+];
+
+/// Supported variable font axes and their defaults in `font-variation-settings`
+pub const AXES: &[(&str, f32)] = &[
+    ("wght", 400.0),
+    ("wdth", 100.0),
+    ("opsz", 14.0),
+    ("slnt", 0.0),
+];
+
+/// `unicode-range` values for the named `loading.subset` options (as served by Google Fonts)
+pub const SUBSET_RANGES: &[(&str, &str)] = &[
+    ("latin", "U+0000-00FF, U+0131, U+0152-0153, U+02BB-02BC, U+02C6, U+02DA, U+02DC, U+0304, U+0308, U+0329, U+2000-206F, U+20AC, U+2122, U+2191, U+2193, U+2212, U+2215, U+FEFF, U+FFFD"),
+    ("latin-ext", "U+0100-02BA, U+02BD-02C5, U+02C7-02CC, U+02CE-02D7, U+02DD-02FF, U+0304, U+0308, U+0329, U+1D00-1DBF, U+1E00-1E9F, U+1EF2-1EFF, U+2020, U+20A0-20AB, U+20AD-20C0, U+2113, U+2C60-2C7F, U+A720-A7FF"),
+    ("cyrillic", "U+0301, U+0400-045F, U+0490-0491, U+04B0-04B1, U+2116"),
+    ("greek", "U+0370-0377, U+037A-037F, U+0384-038A, U+038C, U+038E-03A1, U+03A3-03FF"),
+];
+
+/// Directory (relative to the CSS output) that copied font files are placed in
+pub const FONTS_DIR: &str = "fonts";
+
+/// Generate `@font-face` rules for every stack that declares font files
+pub fn generate_font_faces(fonts: &FontsConfig) -> String {
+    let mut css = String::new();
+
+    for (_, stack) in sorted_stacks(fonts) {
+        let family = face_family(stack);
+        let display = stack.display.as_deref().unwrap_or(&fonts.loading.strategy);
+        let range = stack.unicode_range.as_deref().or_else(|| {
+            let subset = fonts.loading.subset.as_deref()?;
+            SUBSET_RANGES.iter().find(|(name, _)| *name == subset).map(|(_, range)| *range)
+        });
+
+        for file in &stack.files {
+            css.push_str("  @font-face {\n");
+            css.push_str(&format!("    font-family: '{}';\n", family));
+            css.push_str(&format!(
+                "    src: url('{}') format('{}');\n",
+                font_url(file, fonts.loading.copy),
+                font_format(&file.src)
+            ));
+            css.push_str(&format!("    font-weight: {};\n", weight_value(&file.weight)));
+            css.push_str(&format!("    font-style: {};\n", file.style));
+            css.push_str(&format!("    font-display: {};\n", display));
+            if let Some(range) = range {
+                css.push_str(&format!("    unicode-range: {};\n", range));
+            }
+            css.push_str("  }\n\n");
+        }
+    }
+
+    css
+}
+
+/// `<link rel="preload">` tags for the regular file of each stack in `loading.preload`
+pub fn generate_preload_hints(fonts: &FontsConfig) -> String {
+    let mut html = String::new();
+
+    for name in &fonts.loading.preload {
+        let Some(stack) = fonts.fonts.get(name) else { continue };
+
+        // The upright file closest to weight 400 is what first paint needs
+        let regular = stack.files.iter()
+            .filter(|file| file.style == "normal")
+            .min_by_key(|file| match &file.weight {
+                FontWeight::Single(weight) => weight.abs_diff(400),
+                FontWeight::Range(_) => 0,
+            });
+
+        if let Some(file) = regular {
+            html.push_str(&format!(
+                "<link rel=\"preload\" href=\"{}\" as=\"font\" type=\"{}\" crossorigin>\n",
+                font_url(file, fonts.loading.copy),
+                font_mime(&file.src)
+            ));
+        }
+    }
+
+    html
+}
+
+/// Copy every stack's font files into `target`; all files are checked
+/// before anything is written
+pub fn copy_font_files(fonts: &FontsConfig, target: &Path) -> Result<Vec<PathBuf>> {
+    let mut copies = Vec::new();
+    for (_, stack) in sorted_stacks(fonts) {
+        for file in &stack.files {
+            let source = Path::new(&file.src);
+            let Some(name) = source.file_name() else {
+                bail!("Font file '{}' has no file name", file.src);
+            };
+            if !source.exists() {
+                bail!("Font file '{}' does not exist", file.src);
+            }
+            copies.push((source, target.join(name)));
+        }
+    }
+
+    fs::create_dir_all(target)?;
+    for (source, copy) in &copies {
+        fs::copy(source, copy)?;
+    }
+    Ok(copies.into_iter().map(|(_, copy)| copy).collect())
+}
+
+/// The `font-family` name for `@font-face` - the first family in the stack
+pub fn face_family(stack: &FontStack) -> String {
+    stack.family
+        .split(',')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches('\'')
+        .trim_matches('"')
+        .to_string()
+}
+
+/// Font stack value for `--rs-font-*`, ending in the configured generic fallback
+pub fn family_with_fallback(stack: &FontStack) -> String {
+    let last = stack.family.rsplit(',').next().unwrap_or_default().trim();
+    if stack.fallback.is_empty() || last == stack.fallback {
+        stack.family.clone()
+    } else {
+        format!("{}, {}", stack.family, stack.fallback)
+    }
+}
+
+/// A font's declared weights, lightest first
+pub fn font_weights(stack: &FontStack) -> Vec<(String, u16)> {
+    let mut weights: Vec<(String, u16)> = stack.weights.iter()
+        .flatten()
+        .map(|(name, weight)| (name.clone(), *weight))
+        .collect();
+    weights.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    weights
+}
+
+/// A font's named axis values as (axis, name, value), validated against `AXES`
+pub fn font_axes(font: &str, stack: &FontStack) -> Result<Vec<(String, String, f32)>> {
+    let mut values = Vec::new();
+
+    for (axis, named) in &stack.axes {
+        if !AXES.iter().any(|(tag, _)| tag == axis) {
+            bail!("Unknown variable font axis '{}' in {} (expected wght, wdth, opsz or slnt)", axis, font);
+        }
+        for (name, value) in named.iter().filter(|(name, _)| *name != "default") {
+            values.push((axis.clone(), name.clone(), *value));
+        }
+    }
+
+    values.sort_by(|a, b| a.0.cmp(&b.0).then(a.2.total_cmp(&b.2)).then_with(|| a.1.cmp(&b.1)));
+    Ok(values)
+}
+
+/// Weight tokens across all fonts, for elements that do not name their font
+///
+/// Standard names keep their numeric value (`bold` is 700 whatever a font
+/// declares); other names take the declared value when every font agrees,
+/// and need `font:` otherwise.
+pub fn weight_tokens(fonts: &FontsConfig) -> Vec<(String, String)> {
+    let mut declared: Vec<(String, Vec<u16>)> = Vec::new();
+
+    for (_, stack) in sorted_stacks(fonts) {
+        for (name, weight) in font_weights(stack) {
+            match declared.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, weights)) => weights.push(weight),
+                None => declared.push((name, vec![weight])),
+            }
+        }
+    }
+
+    if declared.is_empty() {
+        return DEFAULT_WEIGHTS.iter().map(|(name, weight)| (name.to_string(), weight.to_string())).collect();
+    }
+
+    declared.into_iter()
+        .filter_map(|(name, weights)| {
+            let weight = match DEFAULT_WEIGHTS.iter().find(|(n, _)| *n == name) {
+                Some((_, weight)) => *weight,
+                None if weights.iter().all(|w| *w == weights[0]) => weights[0],
+                None => return None,
+            };
+            Some((name, weight.to_string()))
+        })
+        .collect()
+}
+
+/// Axis tokens across all fonts as (axis, name, value), first font by name wins
+pub fn axis_tokens(fonts: &FontsConfig) -> Result<Vec<(String, String, String)>> {
+    let mut tokens: Vec<(String, String, String)> = Vec::new();
+
+    for (font, stack) in sorted_stacks(fonts) {
+        for (axis, name, _) in font_axes(font, stack)? {
+            if !tokens.iter().any(|(a, n, _)| *a == axis && *n == name) {
+                let value = format!("var(--rs-{}-{}-{})", font, axis, name);
+                tokens.push((axis, name, value));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// `font-variation-settings` composed from the per-axis `--rs-<axis>` properties,
+/// covering every axis some font declares
+pub fn variation_settings(fonts: &FontsConfig) -> Option<String> {
+    let settings: Vec<String> = AXES.iter()
+        .filter(|(tag, _)| fonts.fonts.values().any(|stack| stack.axes.contains_key(*tag)))
+        .map(|(tag, default)| {
+            let default = fonts.fonts.values()
+                .find_map(|stack| stack.axes.get(*tag)?.get("default").copied())
+                .unwrap_or(*default);
+            format!("\"{}\" var(--rs-{}, {})", tag, tag, default)
+        })
+        .collect();
+
+    (!settings.is_empty()).then(|| settings.join(", "))
+}
+
+/// Per-font weight and axis custom properties for `:root`
+pub fn generate_font_properties(fonts: &FontsConfig) -> Result<String> {
+    let mut css = String::new();
+
+    for (font, stack) in sorted_stacks(fonts) {
+        for (name, weight) in font_weights(stack) {
+            css.push_str(&format!("    --rs-{}-weight-{}: {};\n", font, name, weight));
+        }
+        for (axis, name, value) in font_axes(font, stack)? {
+            css.push_str(&format!("    --rs-{}-{}-{}: {};\n", font, axis, name, value));
+        }
+    }
+
+    Ok(css)
+}
+
+/// `:lang()` rules replacing `--rs-font-*` with the stacks in `languages`
+pub fn generate_language_stacks(fonts: &FontsConfig) -> Result<String> {
+    let mut css = String::new();
+    let mut languages: Vec<_> = fonts.languages.iter().collect();
+    languages.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (lang, stacks) in languages {
+        let mut stacks: Vec<_> = stacks.iter().collect();
+        stacks.sort_by(|a, b| a.0.cmp(b.0));
+
+        css.push_str(&format!("  :lang({}) {{\n", lang));
+        for (font, family) in stacks {
+            if !fonts.fonts.contains_key(font) {
+                bail!("Unknown font '{}' in languages.{} (not declared under fonts)", font, lang);
+            }
+            css.push_str(&format!("    --rs-{}: {};\n", font, family));
+        }
+        css.push_str("  }\n");
+    }
+
+    Ok(css)
+}
+
+fn sorted_stacks(fonts: &FontsConfig) -> Vec<(&String, &FontStack)> {
+    let mut stacks: Vec<_> = fonts.fonts.iter().collect();
+    stacks.sort_by(|a, b| a.0.cmp(b.0));
+    stacks
+}
+
+/// Copied files are referenced relative to the CSS file, others as written
+fn font_url(file: &FontFile, copied: bool) -> String {
+    if copied {
+        let name = Path::new(&file.src).file_name().unwrap_or_default().to_string_lossy();
+        format!("{}/{}", FONTS_DIR, name)
+    } else {
+        file.src.clone()
+    }
+}
+
+fn font_format(src: &str) -> &'static str {
+    match Path::new(src).extension().and_then(|e| e.to_str()) {
+        Some("woff") => "woff",
+        Some("ttf") => "truetype",
+        Some("otf") => "opentype",
+        _ => "woff2",
+    }
+}
+
+fn font_mime(src: &str) -> &'static str {
+    match Path::new(src).extension().and_then(|e| e.to_str()) {
+        Some("woff") => "font/woff",
+        Some("ttf") => "font/ttf",
+        Some("otf") => "font/otf",
+        _ => "font/woff2",
+    }
+}
+
+fn weight_value(weight: &FontWeight) -> String {
+    match weight {
+        FontWeight::Single(weight) => weight.to_string(),
+        FontWeight::Range(range) => range.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> FontsConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    const INTER: &str = r#"
+fonts:
+  font-a:
+    family: "'Inter Var', sans-serif"
+    fallback: sans-serif
+    files:
+      - { src: assets/inter-var.woff2, weight: "100 900" }
+      - { src: fonts/inter-italic.ttf, weight: 400, style: italic }
+  font-b:
+    family: Lora, serif
+    fallback: serif
+    display: optional
+    unicodeRange: "U+0000-00FF"
+    files:
+      - { src: fonts/lora-bold.otf, weight: 700 }
+      - { src: fonts/lora.woff, weight: 400 }
+loading:
+  subset: latin-ext
+  preload: [font-b, font-c]
+"#;
+
+    #[test]
+    fn test_font_faces() {
+        let css = generate_font_faces(&config(INTER));
+        let latin_ext = SUBSET_RANGES.iter().find(|(name, _)| *name == "latin-ext").unwrap().1;
+
+        assert!(css.starts_with(&format!(concat!(
+            "  @font-face {{\n",
+            "    font-family: 'Inter Var';\n",
+            "    src: url('assets/inter-var.woff2') format('woff2');\n",
+            "    font-weight: 100 900;\n",
+            "    font-style: normal;\n",
+            "    font-display: swap;\n",
+            "    unicode-range: {};\n",
+            "  }}\n\n",
+        ), latin_ext)));
+        assert!(css.contains("src: url('fonts/inter-italic.ttf') format('truetype');\n    font-weight: 400;\n    font-style: italic;"));
+        // Stack settings override `loading`
+        assert!(css.contains("src: url('fonts/lora-bold.otf') format('opentype');"));
+        assert!(css.contains("    font-family: 'Lora';\n    src: url('fonts/lora.woff') format('woff');"));
+        assert!(css.contains("    font-display: optional;\n    unicode-range: U+0000-00FF;\n"));
+        assert_eq!(css.matches("@font-face").count(), 4);
+
+        // Copied files are referenced next to the CSS; no subset means no range
+        let mut fonts = config(INTER);
+        fonts.loading.copy = true;
+        fonts.loading.subset = None;
+        let css = generate_font_faces(&fonts);
+        assert!(css.contains("src: url('fonts/inter-var.woff2') format('woff2');"));
+        assert_eq!(css.matches("unicode-range").count(), 2);
+
+        assert_eq!(generate_font_faces(&FontsConfig::default()), "");
+    }
+
+    #[test]
+    fn test_preload_hints() {
+        let mut fonts = config(INTER);
+        // Unknown stacks are skipped; the upright file nearest 400 is preloaded
+        assert_eq!(generate_preload_hints(&fonts),
+            "<link rel=\"preload\" href=\"fonts/lora.woff\" as=\"font\" type=\"font/woff\" crossorigin>\n");
+
+        // A variable file covers 400; italic files are never preloaded
+        fonts.loading.preload = vec!["font-a".to_string()];
+        assert_eq!(generate_preload_hints(&fonts),
+            "<link rel=\"preload\" href=\"assets/inter-var.woff2\" as=\"font\" type=\"font/woff2\" crossorigin>\n");
+        fonts.fonts.get_mut("font-a").unwrap().files.remove(0);
+        assert_eq!(generate_preload_hints(&fonts), "");
+    }
+
+    #[test]
+    fn test_weight_tokens() {
+        let fonts = config(r#"
+fonts:
+  font-b: { family: Lora, fallback: serif, weights: { regular: 400, book: 380, bold: 700 } }
+  font-a: { family: Inter, fallback: sans-serif, weights: { book: 350, bold: 600, heavy: 850 } }
+"#);
+        // Only declared names, lightest first per font; standard names keep their
+        // numeric value, custom ones need all fonts to agree
+        assert_eq!(weight_tokens(&fonts), vec![
+            ("bold".to_string(), "700".to_string()),
+            ("heavy".to_string(), "850".to_string()),
+            ("regular".to_string(), "400".to_string()),
+        ]);
+        assert!(!weight_tokens(&fonts).iter().any(|(name, _)| name == "medium"));
+
+        let table = weight_tokens(&config("fonts:\n  font-a: { family: Inter, fallback: sans-serif }\n"));
+        assert_eq!(table.len(), DEFAULT_WEIGHTS.len());
+        assert!(table.contains(&("medium".to_string(), "500".to_string())));
+    }
+
+    #[test]
+    fn test_font_axes() {
+        let fonts = config(r#"
+fonts:
+  font-a:
+    family: Inter
+    fallback: sans-serif
+    axes:
+      wdth: { condensed: 75, default: 90, expanded: 125 }
+      slnt: { italic: -10 }
+  font-b:
+    family: Lora
+    fallback: serif
+    axes:
+      wdth: { condensed: 80, narrow: 87.5 }
+"#);
+        assert_eq!(font_axes("font-a", &fonts.fonts["font-a"]).unwrap(), vec![
+            ("slnt".to_string(), "italic".to_string(), -10.0),
+            ("wdth".to_string(), "condensed".to_string(), 75.0),
+            ("wdth".to_string(), "expanded".to_string(), 125.0),
+        ]);
+        assert_eq!(axis_tokens(&fonts).unwrap(), vec![
+            ("slnt".to_string(), "italic".to_string(), "var(--rs-font-a-slnt-italic)".to_string()),
+            ("wdth".to_string(), "condensed".to_string(), "var(--rs-font-a-wdth-condensed)".to_string()),
+            ("wdth".to_string(), "expanded".to_string(), "var(--rs-font-a-wdth-expanded)".to_string()),
+            ("wdth".to_string(), "narrow".to_string(), "var(--rs-font-b-wdth-narrow)".to_string()),
+        ]);
+        // Declared axes only, in AXES order, with the configured default
+        assert_eq!(variation_settings(&fonts).unwrap(), "\"wdth\" var(--rs-wdth, 90), \"slnt\" var(--rs-slnt, 0)");
+        assert_eq!(variation_settings(&FontsConfig::default()), None);
+
+        let unknown = config("fonts:\n  font-a: { family: Inter, fallback: sans-serif, axes: { GRAD: { soft: 50 } } }\n");
+        assert!(font_axes("font-a", &unknown.fonts["font-a"]).unwrap_err().to_string().starts_with("Unknown variable font axis 'GRAD' in font-a"));
+    }
+
+    #[test]
+    fn test_copy_font_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("inter.woff2");
+        fs::write(&source, "woff2").unwrap();
+        let target = dir.path().join("dist").join(FONTS_DIR);
+
+        let mut fonts = config("fonts:\n  font-a:\n    family: Inter\n    fallback: sans-serif\n");
+        let stack = fonts.fonts.get_mut("font-a").unwrap();
+        stack.files.push(FontFile { src: source.display().to_string(), weight: FontWeight::Single(400), style: "normal".to_string() });
+        assert_eq!(copy_font_files(&fonts, &target).unwrap(), vec![target.join("inter.woff2")]);
+        assert_eq!(fs::read_to_string(target.join("inter.woff2")).unwrap(), "woff2");
+
+        // A missing file fails the build before anything is copied
+        let missing = dir.path().join("missing.woff2");
+        let stack = fonts.fonts.get_mut("font-a").unwrap();
+        stack.files.insert(0, FontFile { src: missing.display().to_string(), weight: FontWeight::Single(700), style: "normal".to_string() });
+        fs::remove_dir_all(&target).unwrap();
+        let error = copy_font_files(&fonts, &target).unwrap_err();
+        assert_eq!(error.to_string(), format!("Font file '{}' does not exist", missing.display()));
+        assert!(!target.exists());
+    }
+}
@@ -2,16 +2,19 @@ pub mod namespaces;
 pub mod defaults;
 pub mod components;
 pub mod breakpoints;
+pub mod fonts;
 
 use anyhow::Result;
 use crate::config::{Config, ColorsConfig, FontsConfig, ComponentsConfig, BridgeConfig};
+use crate::typography::TypographyConfig;
 
 pub fn generate(
     config: &Config,
     colors: &ColorsConfig,
-    fonts: &FontsConfig,
+    fonts_config: &FontsConfig,
     components: &ComponentsConfig,
     bridge: &BridgeConfig,
+    typography: &TypographyConfig,
 ) -> Result<String> {
     let mut css = String::new();
     
@@ -20,15 +23,20 @@ pub fn generate(
     
     // Generate settings layer
     css.push_str("@layer settings {\n");
+    
+    // Font files declared in reedstyle.fonts.yaml
+    css.push_str(&fonts::generate_font_faces(fonts_config));
+    
     css.push_str("  :root {\n");
     
-    // Generate color variables
-    for (name, color) in &colors.colors {
-        let base_color = format_color(color)?;
+    // Generate color variables (references resolved to concrete values)
+    let resolved = crate::color::resolve_colors(colors)?;
+    for name in colors.colors.keys() {
+        let base_color = &resolved[name];
         
         // Generate 1-9 scale for brand and state colors
         if name.starts_with("brand-") || name.starts_with("state-") {
-            let scale = crate::color::generate_color_scale(&base_color)?;
+            let scale = crate::color::generate_color_scale(base_color)?;
             css.push_str(&format!("    --rs-color-{}-1: {};\n", name, scale.scale_1));
             css.push_str(&format!("    --rs-color-{}-2: {};\n", name, scale.scale_2));
             css.push_str(&format!("    --rs-color-{}-3: {};\n", name, scale.scale_3));
@@ -40,7 +48,7 @@ pub fn generate(
             css.push_str(&format!("    --rs-color-{}-9: {};\n", name, scale.scale_9));
             
             // Keep old format temporarily for backwards compatibility
-            let variations = crate::color::generate_variations(&base_color)?;
+            let variations = crate::color::generate_variations(base_color)?;
             css.push_str(&format!("    --rs-{}-weak: {};\n", name, variations.weak));
             css.push_str(&format!("    --rs-{}-light: {};\n", name, variations.light));
             css.push_str(&format!("    --rs-{}: {};\n", name, base_color));
@@ -66,15 +74,27 @@ pub fn generate(
     css.push_str(&format!("    --rs-color-neutral-9: {};\n", neutral_scale.scale_9));
     
     // Generate font variables
-    for (name, font) in &fonts.fonts {
-        css.push_str(&format!("    --rs-{}: {};\n", name, font.family));
+    for (name, font) in &fonts_config.fonts {
+        css.push_str(&format!("    --rs-{}: {};\n", name, fonts::family_with_fallback(font)));
+    }
+    css.push_str(&fonts::generate_font_properties(fonts_config)?);
+    
+    // Text sizes, shared by size: tokens and the form messages
+    for (name, size) in namespaces::text::FONT_SIZES {
+        css.push_str(&format!("    --rs-size-{}: {};\n", name, size));
     }
     
     css.push_str("  }\n");
     
+    // Font stacks for :lang(ja), :lang(zh-Hans) ... from reedstyle.fonts.yaml
+    css.push_str(&fonts::generate_language_stacks(fonts_config)?);
+    
     // Generate HTML element defaults in settings layer
     css.push_str(&defaults::generate_html_defaults());
     
+    // Site-wide typography defaults and :lang() rules
+    css.push_str(&crate::typography::generate_defaults(typography)?);
+    
     // Generate custom component defaults from YAML
     css.push_str(&defaults::generate_component_defaults(components)?);
     
@@ -87,39 +107,35 @@ pub fn generate(
     css.push_str("@layer theme {\n");
     
     // Generate namespace styles
-    generate_namespaces(&mut css, config, colors, fonts)?;
+    generate_namespaces(&mut css, config, colors, fonts_config)?;
     
     // Generate component styles from YAML
-    css.push_str(&components::generate_component_styles(components)?);
+    css.push_str(&components::generate_component_styles(components, fonts_config)?);
     
     css.push_str("}\n\n");
     
     Ok(css)
 }
 
-fn format_color(color: &crate::config::Color) -> Result<String> {
-    use crate::config::Color;
-    use crate::color::to_oklch;
-    
-    match color {
-        Color::Hex(hex) => {
-            // Convert to OKLCH internally
-            to_oklch(hex)
-        },
-        Color::Reference(reference) => Ok(format!("var(--rs-{})", reference)),
-        Color::Object { hex, oklch, reference } => {
-            if let Some(oklch) = oklch {
-                Ok(oklch.clone())
-            } else if let Some(hex) = hex {
-                // Convert hex to OKLCH
-                to_oklch(hex)
-            } else if let Some(reference) = reference {
-                Ok(format!("var(--rs-{})", reference))
-            } else {
-                Ok("transparent".to_string())
-            }
-        }
-    }
+/// Selector for `r-s` elements whose `attr` contains `token` as a whole entry
+///
+/// Attribute values are written as `k:v`, `[k:v, other:x]` or space-separated,
+/// so a plain `*=` would also match `k:v` inside `k:value`.
+pub(crate) fn token_selector(attr: &str, token: &str) -> String {
+    let variants = [
+        format!("[{}~=\"{}\"]", attr, token),
+        format!("[{}~=\"{},\"]", attr, token),
+        format!("[{}~=\"[{}\"]", attr, token),
+        format!("[{}~=\"[{},\"]", attr, token),
+        format!("[{}~=\"{}]\"]", attr, token),
+        format!("[{}~=\"[{}]\"]", attr, token),
+        format!("[{}^=\"{},\"]", attr, token),
+        format!("[{}^=\"[{},\"]", attr, token),
+        format!("[{}*=\",{},\"]", attr, token),
+        format!("[{}*=\",{}]\"]", attr, token),
+        format!("[{}$=\",{}\"]", attr, token),
+    ];
+    format!("r-s:is({})", variants.join(", "))
 }
 
 fn generate_namespaces(css: &mut String, config: &Config, colors: &ColorsConfig, fonts: &FontsConfig) -> Result<()> {
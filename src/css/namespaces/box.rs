@@ -2,6 +2,22 @@ use anyhow::Result;
 use crate::config::Config;
 use crate::css::breakpoints::BREAKPOINTS;
 
+/// Spacing scale shared by padding, margin and the design token export
+pub const SPACING_SCALE: &[(&str, &str)] = &[
+    ("0", "0"),
+    ("1", "0.25rem"),
+    ("2", "0.5rem"),
+    ("3", "0.75rem"),
+    ("4", "1rem"),
+    ("6", "1.5rem"),
+    ("8", "2rem"),
+    ("10", "2.5rem"),
+    ("12", "3rem"),
+    ("16", "4rem"),
+    ("20", "5rem"),
+    ("24", "6rem"),
+];
+
 pub struct BoxNamespace;
 
 impl BoxNamespace {
@@ -55,22 +71,7 @@ impl BoxNamespace {
     fn generate_padding(namespace: &str) -> String {
         let mut css = String::new();
         
-        let scale = [
-            ("0", "0"),
-            ("1", "0.25rem"),
-            ("2", "0.5rem"),
-            ("3", "0.75rem"),
-            ("4", "1rem"),
-            ("6", "1.5rem"),
-            ("8", "2rem"),
-            ("10", "2.5rem"),
-            ("12", "3rem"),
-            ("16", "4rem"),
-            ("20", "5rem"),
-            ("24", "6rem"),
-        ];
-        
-        for (key, value) in &scale {
+        for (key, value) in SPACING_SCALE {
             // All sides
             css.push_str(&format!("    r-s[{}*=\"padding:{}\"] {{ padding: {}; }}\n", namespace, key, value));
             
@@ -91,21 +92,6 @@ impl BoxNamespace {
     fn generate_margin(namespace: &str) -> String {
         let mut css = String::new();
         
-        let scale = [
-            ("0", "0"),
-            ("1", "0.25rem"),
-            ("2", "0.5rem"),
-            ("3", "0.75rem"),
-            ("4", "1rem"),
-            ("6", "1.5rem"),
-            ("8", "2rem"),
-            ("10", "2.5rem"),
-            ("12", "3rem"),
-            ("16", "4rem"),
-            ("20", "5rem"),
-            ("24", "6rem"),
-        ];
-        
         // Auto margin
         css.push_str(&format!("    r-s[{}*=\"margin:auto\"] {{ margin: auto; }}\n", namespace));
         css.push_str(&format!("    r-s[{}*=\"margin-x:auto\"] {{ margin-left: auto; margin-right: auto; }}\n", namespace));
@@ -113,7 +99,7 @@ impl BoxNamespace {
         css.push_str(&format!("    r-s[{}*=\"margin-left:auto\"] {{ margin-left: auto; }}\n", namespace));
         css.push_str(&format!("    r-s[{}*=\"margin-right:auto\"] {{ margin-right: auto; }}\n", namespace));
         
-        for (key, value) in &scale {
+        for (key, value) in SPACING_SCALE {
             // All sides
             css.push_str(&format!("    r-s[{}*=\"margin:{}\"] {{ margin: {}; }}\n", namespace, key, value));
             
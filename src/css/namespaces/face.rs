@@ -2,6 +2,29 @@ use anyhow::Result;
 use crate::config::{Config, ColorsConfig};
 use crate::css::breakpoints::BREAKPOINTS;
 
+/// Border radius scale shared by the face namespace and the design token export
+pub const RADIUS_SCALE: &[(&str, &str)] = &[
+    ("none", "0"),
+    ("sm", "0.125rem"),
+    ("md", "0.25rem"),
+    ("lg", "0.5rem"),
+    ("xl", "0.75rem"),
+    ("2xl", "1rem"),
+    ("3xl", "1.5rem"),
+    ("full", "9999px"),
+];
+
+/// Box shadow scale shared by the face namespace and the design token export
+pub const SHADOW_SCALE: &[(&str, &str)] = &[
+    ("none", "none"),
+    ("sm", "0 1px 2px 0 rgb(0 0 0 / 0.05)"),
+    ("md", "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)"),
+    ("lg", "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)"),
+    ("xl", "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)"),
+    ("2xl", "0 25px 50px -12px rgb(0 0 0 / 0.25)"),
+    ("inner", "inset 0 2px 4px 0 rgb(0 0 0 / 0.05)"),
+];
+
 pub struct FaceNamespace;
 
 impl FaceNamespace {
@@ -134,14 +157,9 @@ impl FaceNamespace {
     fn generate_radius(namespace: &str) -> String {
         let mut css = String::new();
         
-        css.push_str(&format!("    r-s[{}*=\"radius:none\"] {{ border-radius: 0; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"radius:sm\"] {{ border-radius: 0.125rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"radius:md\"] {{ border-radius: 0.25rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"radius:lg\"] {{ border-radius: 0.5rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"radius:xl\"] {{ border-radius: 0.75rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"radius:2xl\"] {{ border-radius: 1rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"radius:3xl\"] {{ border-radius: 1.5rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"radius:full\"] {{ border-radius: 9999px; }}\n", namespace));
+        for (key, value) in RADIUS_SCALE {
+            css.push_str(&format!("    r-s[{}*=\"radius:{}\"] {{ border-radius: {}; }}\n", namespace, key, value));
+        }
         
         // Individual corners
         css.push_str(&format!("    r-s[{}*=\"radius-tl:lg\"] {{ border-top-left-radius: 0.5rem; }}\n", namespace));
@@ -155,13 +173,9 @@ impl FaceNamespace {
     fn generate_shadows(namespace: &str) -> String {
         let mut css = String::new();
         
-        css.push_str(&format!("    r-s[{}*=\"shadow:none\"] {{ box-shadow: none; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"shadow:sm\"] {{ box-shadow: 0 1px 2px 0 rgb(0 0 0 / 0.05); }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"shadow:md\"] {{ box-shadow: 0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1); }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"shadow:lg\"] {{ box-shadow: 0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1); }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"shadow:xl\"] {{ box-shadow: 0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1); }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"shadow:2xl\"] {{ box-shadow: 0 25px 50px -12px rgb(0 0 0 / 0.25); }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"shadow:inner\"] {{ box-shadow: inset 0 2px 4px 0 rgb(0 0 0 / 0.05); }}\n", namespace));
+        for (key, value) in SHADOW_SCALE {
+            css.push_str(&format!("    r-s[{}*=\"shadow:{}\"] {{ box-shadow: {}; }}\n", namespace, key, value));
+        }
         
         // Colored shadows
         for letter in ['a', 'b', 'c'] {
@@ -1,6 +1,24 @@
 use anyhow::Result;
 use crate::config::{Config, FontsConfig, ColorsConfig};
 use crate::css::breakpoints::BREAKPOINTS;
+use crate::css::fonts;
+
+/// Text sizes, written to `:root` as `--rs-size-<name>`
+pub const FONT_SIZES: &[(&str, &str)] = &[
+    ("tiny", "0.75rem"),
+    ("small", "0.875rem"),
+    ("normal", "1rem"),
+    ("large", "1.25rem"),
+    ("huge", "1.5rem"),
+    ("mega", "2rem"),
+    ("ultra", "3rem"),
+    // Additional sizes for headings
+    ("2xl", "1.5rem"),
+    ("3xl", "1.875rem"),
+    ("4xl", "2.25rem"),
+    ("5xl", "3rem"),
+    ("6xl", "3.75rem"),
+];
 
 pub struct TextNamespace;
 
@@ -41,7 +59,8 @@ impl TextNamespace {
         // Generate all text properties
         css.push_str(&Self::generate_font_family(namespace, fonts));
         css.push_str(&Self::generate_font_size(namespace));
-        css.push_str(&Self::generate_font_weight(namespace));
+        css.push_str(&Self::generate_font_weight(namespace, fonts)?);
+        css.push_str(&Self::generate_font_axes(namespace, fonts)?);
         css.push_str(&Self::generate_text_color(namespace));
         css.push_str(&Self::generate_text_align(namespace));
         css.push_str(&Self::generate_line_height(namespace));
@@ -52,6 +71,7 @@ impl TextNamespace {
         css.push_str(&Self::generate_text_overflow(namespace));
         css.push_str(&Self::generate_whitespace(namespace));
         css.push_str(&Self::generate_word_break(namespace));
+        css.push_str(&Self::generate_text_wrap(namespace));
         css.push_str(&Self::generate_list_style(namespace));
         
         // Typography features only for base namespace
@@ -84,38 +104,77 @@ impl TextNamespace {
     fn generate_font_size(namespace: &str) -> String {
         let mut css = String::new();
         
-        css.push_str(&format!("    r-s[{}*=\"size:tiny\"] {{ font-size: 0.75rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:small\"] {{ font-size: 0.875rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:normal\"] {{ font-size: 1rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:large\"] {{ font-size: 1.25rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:huge\"] {{ font-size: 1.5rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:mega\"] {{ font-size: 2rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:ultra\"] {{ font-size: 3rem; }}\n", namespace));
-        
-        // Additional sizes for headings
-        css.push_str(&format!("    r-s[{}*=\"size:2xl\"] {{ font-size: 1.5rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:3xl\"] {{ font-size: 1.875rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:4xl\"] {{ font-size: 2.25rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:5xl\"] {{ font-size: 3rem; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"size:6xl\"] {{ font-size: 3.75rem; }}\n", namespace));
+        for (name, _) in FONT_SIZES {
+            css.push_str(&format!("    r-s[{}*=\"size:{}\"] {{ font-size: var(--rs-size-{}); }}\n", namespace, name, name));
+        }
         
         css
     }
     
-    fn generate_font_weight(namespace: &str) -> String {
+    fn generate_font_weight(namespace: &str, fonts: &FontsConfig) -> Result<String> {
         let mut css = String::new();
+        let has_wght = fonts.fonts.values().any(|stack| stack.axes.contains_key("wght"));
+        
+        // Weights declared in reedstyle.fonts.yaml (fixed 100-900 table if none are)
+        for (name, value) in fonts::weight_tokens(fonts) {
+            if has_wght {
+                // Keep the wght axis in step when font-variation-settings is active
+                css.push_str(&format!("    r-s[{}*=\"weight:{}\"] {{ font-weight: {}; --rs-wght: {}; }}\n", namespace, name, value, value));
+            } else {
+                css.push_str(&format!("    r-s[{}*=\"weight:{}\"] {{ font-weight: {}; }}\n", namespace, name, value));
+            }
+        }
         
-        css.push_str(&format!("    r-s[{}*=\"weight:thin\"] {{ font-weight: 100; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:light\"] {{ font-weight: 300; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:normal\"] {{ font-weight: 400; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:regular\"] {{ font-weight: 400; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:medium\"] {{ font-weight: 500; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:semibold\"] {{ font-weight: 600; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:bold\"] {{ font-weight: 700; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:extrabold\"] {{ font-weight: 800; }}\n", namespace));
-        css.push_str(&format!("    r-s[{}*=\"weight:black\"] {{ font-weight: 900; }}\n", namespace));
+        // Font-specific values win when the element names its font
+        let mut names: Vec<&String> = fonts.fonts.keys().collect();
+        names.sort();
+        for font in names {
+            for (name, _) in fonts::font_weights(&fonts.fonts[font]) {
+                let value = format!("var(--rs-{}-weight-{})", font, name);
+                let wght = if has_wght { format!(" --rs-wght: {};", value) } else { String::new() };
+                css.push_str(&format!(
+                    "    r-s[{}*=\"font:{}\"][{}*=\"weight:{}\"] {{ font-weight: {};{} }}\n",
+                    namespace, font, namespace, name, value, wght
+                ));
+            }
+        }
         
-        css
+        Ok(css)
+    }
+    
+    fn generate_font_axes(namespace: &str, fonts: &FontsConfig) -> Result<String> {
+        let mut css = String::new();
+        
+        let Some(settings) = fonts::variation_settings(fonts) else {
+            return Ok(css);
+        };
+        
+        // Axis tokens only set --rs-<axis>; one rule composes them
+        for (axis, name, value) in fonts::axis_tokens(fonts)? {
+            css.push_str(&format!("    r-s[{}*=\"{}:{}\"] {{ --rs-{}: {}; }}\n", namespace, axis, name, axis, value));
+        }
+        
+        let mut names: Vec<&String> = fonts.fonts.keys().collect();
+        names.sort();
+        for font in names {
+            for (axis, name, _) in fonts::font_axes(font, &fonts.fonts[font])? {
+                css.push_str(&format!(
+                    "    r-s[{}*=\"font:{}\"][{}*=\"{}:{}\"] {{ --rs-{}: var(--rs-{}-{}-{}); }}\n",
+                    namespace, font, namespace, axis, name, axis, font, axis, name
+                ));
+            }
+        }
+        
+        let mut selectors: Vec<String> = fonts::AXES.iter()
+            .filter(|(tag, _)| fonts.fonts.values().any(|stack| stack.axes.contains_key(*tag)))
+            .map(|(tag, _)| format!("r-s[{}*=\"{}:\"]", namespace, tag))
+            .collect();
+        if fonts.fonts.values().any(|stack| stack.axes.contains_key("wght")) {
+            selectors.push(format!("r-s[{}*=\"weight:\"]", namespace));
+        }
+        css.push_str(&format!("    {} {{ font-variation-settings: {}; }}\n", selectors.join(", "), settings));
+        
+        Ok(css)
     }
     
     fn generate_text_color(namespace: &str) -> String {
@@ -268,6 +327,25 @@ impl TextNamespace {
         css
     }
     
+    fn generate_text_wrap(namespace: &str) -> String {
+        let mut css = String::new();
+        
+        css.push_str(&format!("    r-s[{}*=\"wrap:balance\"] {{ text-wrap: balance; }}\n", namespace));
+        css.push_str(&format!("    r-s[{}*=\"wrap:pretty\"] {{ text-wrap: pretty; }}\n", namespace));
+        css.push_str(&format!("    r-s[{}*=\"wrap:stable\"] {{ text-wrap: stable; }}\n", namespace));
+        
+        // Page breaks in print keep at least two lines together; on screen,
+        // browsers without text-wrap rely on the widow joiner
+        css.push_str("    @media print {\n");
+        css.push_str(&format!(
+            "      r-s[{ns}*=\"wrap:balance\"], r-s[{ns}*=\"wrap:pretty\"] {{ widows: 2; orphans: 2; }}\n",
+            ns = namespace
+        ));
+        css.push_str("    }\n");
+        
+        css
+    }
+    
     fn generate_list_style(namespace: &str) -> String {
         let mut css = String::new();
         
@@ -286,4 +364,39 @@ impl TextNamespace {
         // Use the typography module to generate CSS
         crate::typography::generate_css()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights_and_axes() {
+        let fonts: FontsConfig = serde_yaml::from_str(r#"
+fonts:
+  font-a:
+    family: Inter
+    fallback: sans-serif
+    weights: { regular: 400, bold: 700 }
+    axes:
+      wght: { default: 400 }
+      wdth: { condensed: 75 }
+"#).unwrap();
+
+        let weights = TextNamespace::generate_font_weight("text", &fonts).unwrap();
+        assert!(weights.contains("    r-s[text*=\"weight:bold\"] { font-weight: 700; --rs-wght: 700; }\n"));
+        assert!(weights.contains(concat!(
+            "    r-s[text*=\"font:font-a\"][text*=\"weight:regular\"] ",
+            "{ font-weight: var(--rs-font-a-weight-regular); --rs-wght: var(--rs-font-a-weight-regular); }\n",
+        )));
+        assert!(!weights.contains("weight:medium"));
+
+        let axes = TextNamespace::generate_font_axes("text-md", &fonts).unwrap();
+        assert_eq!(axes, concat!(
+            "    r-s[text-md*=\"wdth:condensed\"] { --rs-wdth: var(--rs-font-a-wdth-condensed); }\n",
+            "    r-s[text-md*=\"font:font-a\"][text-md*=\"wdth:condensed\"] { --rs-wdth: var(--rs-font-a-wdth-condensed); }\n",
+            "    r-s[text-md*=\"wght:\"], r-s[text-md*=\"wdth:\"], r-s[text-md*=\"weight:\"] ",
+            "{ font-variation-settings: \"wght\" var(--rs-wght, 400), \"wdth\" var(--rs-wdth, 100); }\n",
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{bail, Result};
+use regex::Regex;
+use crate::config::{ApiConfig, AuthType};
+
+/// Members of `ReedStyle.api` besides the endpoint groups
+const HELPERS: [&str; 3] = ["request", "getToken", "setToken"];
+
+const METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+/// `__API_SETTINGS__` for api.ts
+pub(super) fn settings(api: &ApiConfig) -> Result<String> {
+    let auth = &api.config.auth;
+    if matches!(auth.kind, AuthType::Bearer | AuthType::Basic) && auth.token_key.is_empty() {
+        bail!("reedstyle.api.yaml: {:?} auth needs config.auth.tokenKey", auth.kind);
+    }
+    let mut settings = serde_json::to_value(&api.config)?;
+    settings["defaults"]["method"] = method(&api.config.defaults.method)?.into();
+    Ok(serde_json::to_string(&settings)?)
+}
+
+/// The `endpoints` runtime module: `ReedStyle.api`, with a typed function
+/// for each endpoint of reedstyle.api.yaml
+pub(super) fn module(api: &ApiConfig) -> Result<String> {
+    let parameter = Regex::new(r"\{(\w+)\}").unwrap();
+    let mut interface = String::new();
+    let mut object = String::new();
+
+    for (group, endpoints) in &api.endpoints {
+        if HELPERS.contains(&group.as_str()) {
+            bail!("reedstyle.api.yaml: '{}' cannot be an endpoint group, ReedStyle.api.{} is a helper", group, group);
+        }
+        interface.push_str(&format!("  {}: {{\n", key(group)));
+        object.push_str(&format!("  {}: {{\n", key(group)));
+
+        for (name, endpoint) in endpoints {
+            let method = method(endpoint.method().unwrap_or(&api.config.defaults.method))?;
+            let path = endpoint.path();
+
+            // `{id}` segments are filled from the first argument
+            let params: Vec<_> = parameter.captures_iter(path)
+                .map(|capture| format!("{}: string | number", key(&capture[1])))
+                .collect();
+            let params = if params.is_empty() { String::new() } else { format!("params: {{ {} }}, ", params.join("; ")) };
+
+            interface.push_str(&format!("    /** `{} {}` */\n", method, path.replace("*/", "*\\/")));
+            interface.push_str(&format!(
+                "    {}<T = unknown>({}body?: ApiBody, options?: ApiOptions): Promise<T>;\n",
+                key(name), params
+            ));
+            object.push_str(&format!(
+                "    {}: endpoint({}, {}{}),\n",
+                key(name), serde_json::to_string(method)?, serde_json::to_string(path)?,
+                if endpoint.retry() { ", true" } else { "" }
+            ));
+        }
+
+        interface.push_str("  };\n");
+        object.push_str("  },\n");
+    }
+
+    Ok(format!(r#"/**
+ * Endpoints of reedstyle.api.yaml, generated by the build
+ */
+
+import type {{ ApiBody, ApiHelpers, ApiOptions }} from './api';
+import {{ apiHelpers, endpoint }} from './api';
+import {{ registerEngine }} from './core';
+
+export {{ ApiError, getToken, request, setToken }} from './api';
+
+/** `ReedStyle.api`: a function per endpoint, by group and name */
+export interface ApiClient extends ApiHelpers {{
+{}}}
+
+export const api: ApiClient = {{
+  ...apiHelpers,
+{}}};
+
+declare module './core' {{
+  interface ReedStyleApi {{
+    api: ApiClient;
+  }}
+}}
+
+registerEngine({{ name: 'api', init() {{}} }}, {{ api }});
+"#, interface, object))
+}
+
+fn method(method: &str) -> Result<&'static str> {
+    match METHODS.iter().find(|known| known.eq_ignore_ascii_case(method)) {
+        Some(known) => Ok(known),
+        None => bail!("reedstyle.api.yaml: unknown method '{}'", method),
+    }
+}
+
+/// A property name, quoted unless it is an identifier
+fn key(name: &str) -> String {
+    let identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        name.to_string()
+    } else {
+        serde_json::to_string(name).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> ApiConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_endpoints_module() {
+        let api = config(concat!(
+            "endpoints:\n  items:\n    item: { path: '/api/items/{id}', method: get }\n    odd-name: /api/odd\n",
+            "    import: { path: /api/import, method: post, retry: true }\n",
+        ));
+        let source = module(&api).unwrap();
+        assert!(source.contains("    item<T = unknown>(params: { id: string | number }, body?: ApiBody, options?: ApiOptions): Promise<T>;"));
+        assert!(source.contains(r#"    item: endpoint("GET", "/api/items/{id}"),"#));
+        assert!(source.contains(r#"    "odd-name": endpoint("POST", "/api/odd"),"#));
+        assert!(source.contains(r#"    import: endpoint("POST", "/api/import", true),"#));
+
+        assert!(module(&config("endpoints:\n  items:\n    item: { path: /api/items, method: fetch }\n")).is_err());
+        assert!(module(&config("endpoints:\n  request:\n    item: /api/items\n")).is_err());
+        assert!(settings(&config("config:\n  auth:\n    type: bearer\n")).is_err());
+    }
+}
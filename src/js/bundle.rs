@@ -0,0 +1,375 @@
+use anyhow::{anyhow, bail, Result};
+use std::collections::{HashMap, HashSet};
+use swc_core::common::comments::SingleThreadedComments;
+use swc_core::common::sync::Lrc;
+use swc_core::common::{FileName, Globals, Mark, SourceMap, Spanned, DUMMY_SP, GLOBALS};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::codegen::text_writer::JsWriter;
+use swc_core::ecma::codegen::{Config, Emitter};
+use swc_core::ecma::parser::error::Error as ParseError;
+use swc_core::ecma::parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_core::ecma::transforms::base::{fixer::fixer, resolver};
+use swc_core::ecma::transforms::typescript::strip;
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use super::{declarations, RuntimeModule};
+
+/// The runtime in every output format
+pub struct Bundle {
+    /// UMD script: auto-initializes as a plain `<script>`, returns the
+    /// exports to AMD and CommonJS loaders
+    pub script: String,
+    /// ES module with the exports of the public modules
+    pub module: String,
+    /// Type declarations for both
+    pub declarations: String,
+    /// Minified bytes of each module, in bundle order
+    pub sizes: Vec<(&'static str, usize)>,
+}
+
+/// Runs the UMD factory; a plain `<script>` starts the runtime at once
+const UMD: &str = r#"(function(factory) {
+    if (typeof define === "function" && define.amd) define([], factory);
+    else if (typeof module === "object" && module.exports) module.exports = factory();
+    else factory().autoInit();
+})(function() {})"#;
+
+/// Bundle runtime modules
+///
+/// Each module is parsed as TypeScript, its `__NAME__` placeholders are
+/// replaced with the JS literals in `data` and its types are stripped. The
+/// modules are then concatenated, in order, into one scope, so a module may
+/// only import names exported by an earlier one; the exports of the public
+/// modules (including `export { … } from`) are the API. Parse errors, placeholders without data, unused data, unknown imports and
+/// top-level names declared twice fail the build. Comments stay in the
+/// sources, except for the type declarations.
+pub fn bundle(modules: &[&RuntimeModule], data: &[(&str, String)]) -> Result<Bundle> {
+    GLOBALS.set(&Globals::new(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let comments = SingleThreadedComments::default();
+        let Linked { parsed, stmts, public, sizes } = link(&cm, &comments, modules, data)?;
+
+        let module = Module {
+            span: DUMMY_SP,
+            body: stmts.iter().cloned()
+                .map(ModuleItem::Stmt)
+                .chain([ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export_names(&public)))])
+                .collect(),
+            shebang: None,
+        };
+
+        let mut factory = vec![Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Lit(Lit::Str("use strict".into()))),
+        })];
+        factory.extend(stmts);
+        factory.push(Stmt::Return(ReturnStmt { span: DUMMY_SP, arg: Some(Box::new(object_of(&public))) }));
+        let script = Script { span: DUMMY_SP, body: vec![umd(&cm, factory)?], shebang: None };
+
+        Ok(Bundle {
+            script: emit(&cm, Program::Script(script), false)?,
+            module: emit(&cm, Program::Module(module), false)?,
+            declarations: declarations::generate(&cm, &comments, &parsed, &public)?,
+            sizes,
+        })
+    })
+}
+
+/// A minified script of runtime modules that calls `entry`, for inlining
+/// into a page: the modules are linked like `bundle` does, in a function
+/// scope of their own
+pub fn inline(modules: &[&RuntimeModule], data: &[(&str, String)], entry: &str) -> Result<String> {
+    GLOBALS.set(&Globals::new(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let comments = SingleThreadedComments::default();
+        let Linked { mut stmts, .. } = link(&cm, &comments, modules, data)?;
+
+        stmts.push(Stmt::Expr(ExprStmt { span: DUMMY_SP, expr: parse_expr(&cm, "<entry>", entry)? }));
+        let script = Script { span: DUMMY_SP, body: vec![iife(&cm, stmts)?], shebang: None };
+        emit(&cm, Program::Script(script), true)
+    })
+}
+
+/// Runtime modules in one scope
+struct Linked {
+    parsed: Vec<(&'static str, Module)>,
+    stmts: Vec<Stmt>,
+    /// Exports of the public modules
+    public: Vec<String>,
+    /// Minified bytes of each module
+    sizes: Vec<(&'static str, usize)>,
+}
+
+fn link(cm: &Lrc<SourceMap>, comments: &SingleThreadedComments, modules: &[&RuntimeModule], data: &[(&str, String)]) -> Result<Linked> {
+    let mut literals = HashMap::new();
+    for (name, literal) in data {
+        let file = format!("<{}>", name);
+        literals.insert(format!("__{}__", name), parse_expr(cm, &file, literal)?);
+    }
+
+    let mut parsed = Vec::new();
+    for module in modules {
+        parsed.push((module.name, parse_module(cm, comments, &format!("{}.ts", module.name), module.source)?));
+    }
+
+    let mut inject = Inject { literals: &literals, used: HashSet::new(), missing: None };
+    let mut exports: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut declared = HashSet::new();
+    let mut public = Vec::new();
+    let mut sizes = Vec::new();
+    let mut stmts = Vec::new();
+
+    for ((name, module), runtime_module) in parsed.iter().zip(modules) {
+        let file = format!("{}.ts", name);
+        let mut module = module.clone();
+
+        module.visit_mut_with(&mut inject);
+        if let Some(placeholder) = inject.missing.take() {
+            bail!("{}: no build data for {}", file, placeholder);
+        }
+
+        let mut program = Program::Module(module);
+        let top_level_mark = Mark::new();
+        program.visit_mut_with(&mut resolver(Mark::new(), top_level_mark, true));
+        program.visit_mut_with(&mut strip(top_level_mark));
+        let Program::Module(module) = program else {
+            unreachable!("stripping types keeps a module a module");
+        };
+
+        let start = stmts.len();
+        let mut exported = Vec::new();
+        for item in module.body {
+            let stmt = match item {
+                ModuleItem::Stmt(stmt) => stmt,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                    exported.extend(declared_names(&export.decl));
+                    Stmt::Decl(export.decl)
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    let names = import.specifiers.iter()
+                        .map(|specifier| match specifier {
+                            ImportSpecifier::Named(named) if named.imported.is_none() => Ok(named.local.sym.to_string()),
+                            _ => Err(anyhow!("{}: only named imports are supported ('{}')", file, import.src.value)),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    check_names(&file, &import.src.value, &names, &exports)?;
+                    continue;
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                    let names = export.specifiers.iter()
+                        .map(|specifier| match specifier {
+                            ExportSpecifier::Named(ExportNamedSpecifier { orig: ModuleExportName::Ident(orig), exported: None, .. }) => Ok(orig.sym.to_string()),
+                            _ => Err(anyhow!("{}: only named exports are supported", file)),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    match &export.src {
+                        Some(src) => check_names(&file, &src.value, &names, &exports)?,
+                        None => {
+                            if let Some(unknown) = names.iter().find(|name| !declared.contains(*name)) {
+                                bail!("{}: cannot export undeclared `{}`", file, unknown);
+                            }
+                        }
+                    }
+                    exported.extend(names);
+                    continue;
+                }
+                ModuleItem::ModuleDecl(_) => {
+                    bail!("{}: only named imports and exports are supported", file);
+                }
+            };
+            if let Stmt::Decl(decl) = &stmt {
+                for declared_name in declared_names(decl) {
+                    if !declared.insert(declared_name.clone()) {
+                        bail!("{}: `{}` is already declared by another runtime module", file, declared_name);
+                    }
+                }
+            }
+            stmts.push(stmt);
+        }
+
+        let script = Script { span: DUMMY_SP, body: stmts[start..].to_vec(), shebang: None };
+        sizes.push((runtime_module.name, emit(cm, Program::Script(script), true)?.len()));
+        if runtime_module.public {
+            for name in &exported {
+                if !public.contains(name) {
+                    public.push(name.clone());
+                }
+            }
+        }
+        exports.insert(name, exported);
+    }
+
+    if let Some(unused) = literals.keys().find(|placeholder| !inject.used.contains(*placeholder)) {
+        bail!("No runtime module uses the build data {}", unused);
+    }
+
+    Ok(Linked { parsed, stmts, public, sizes })
+}
+
+/// Reprint a script without whitespace or comments
+pub fn minify(source: &str) -> Result<String> {
+    GLOBALS.set(&Globals::new(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Custom("reedstyle.js".to_string()), source.to_string());
+        let lexer = Lexer::new(Syntax::Es(Default::default()), EsVersion::latest(), StringInput::from(&*fm), None);
+        let mut parser = Parser::new_from(lexer);
+
+        let script = parser.parse_script().map_err(|error| parse_error(&cm, "reedstyle.js", error))?;
+        emit(&cm, Program::Script(script), true)
+    })
+}
+
+fn parse_module(cm: &Lrc<SourceMap>, comments: &SingleThreadedComments, file: &str, source: &str) -> Result<Module> {
+    let fm = cm.new_source_file(FileName::Custom(file.to_string()), source.to_string());
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsConfig::default()),
+        EsVersion::latest(),
+        StringInput::from(&*fm),
+        Some(comments),
+    );
+    let mut parser = Parser::new_from(lexer);
+
+    let module = parser.parse_module().map_err(|error| parse_error(cm, file, error))?;
+    if let Some(error) = parser.take_errors().into_iter().next() {
+        return Err(parse_error(cm, file, error));
+    }
+    Ok(module)
+}
+
+fn parse_expr(cm: &Lrc<SourceMap>, file: &str, source: &str) -> Result<Box<Expr>> {
+    let fm = cm.new_source_file(FileName::Custom(file.to_string()), source.to_string());
+    let lexer = Lexer::new(Syntax::Es(Default::default()), EsVersion::latest(), StringInput::from(&*fm), None);
+    let mut parser = Parser::new_from(lexer);
+
+    let expr = parser.parse_expr().map_err(|error| parse_error(cm, file, error))?;
+    if let Some(error) = parser.take_errors().into_iter().next() {
+        return Err(parse_error(cm, file, error));
+    }
+    Ok(expr)
+}
+
+fn parse_error(cm: &Lrc<SourceMap>, file: &str, error: ParseError) -> anyhow::Error {
+    let position = cm.lookup_char_pos(error.span().lo);
+    anyhow!("{}:{}:{}: {}", file, position.line, position.col_display + 1, error.kind().msg())
+}
+
+/// Replaces `__NAME__` identifiers with build data
+struct Inject<'a> {
+    literals: &'a HashMap<String, Box<Expr>>,
+    used: HashSet<String>,
+    missing: Option<String>,
+}
+
+impl VisitMut for Inject<'_> {
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        if let Expr::Ident(ident) = expr {
+            let name = ident.sym.to_string();
+            if name.len() > 4 && name.starts_with("__") && name.ends_with("__") {
+                match self.literals.get(&name) {
+                    Some(literal) => *expr = (**literal).clone(),
+                    None => self.missing = Some(name.clone()),
+                }
+                self.used.insert(name);
+            }
+            return;
+        }
+        expr.visit_mut_children_with(self);
+    }
+}
+
+/// Imported and re-exported names must be exports of an earlier module
+fn check_names(file: &str, source: &str, names: &[String], exports: &HashMap<&str, Vec<String>>) -> Result<()> {
+    let Some(exported) = source.strip_prefix("./").and_then(|module| exports.get(module)) else {
+        bail!("{}: cannot import '{}' (only earlier runtime modules can be imported)", file, source);
+    };
+    if let Some(unknown) = names.iter().find(|name| !exported.contains(name)) {
+        bail!("{}: '{}' does not export `{}`", file, source, unknown);
+    }
+    Ok(())
+}
+
+pub(super) fn declared_names(decl: &Decl) -> Vec<String> {
+    match decl {
+        Decl::Class(class) => vec![class.ident.sym.to_string()],
+        Decl::Fn(function) => vec![function.ident.sym.to_string()],
+        Decl::Var(var) => var.decls.iter()
+            .filter_map(|declarator| declarator.name.as_ident())
+            .map(|ident| ident.sym.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `export { a, b };`
+fn export_names(names: &[String]) -> NamedExport {
+    NamedExport {
+        span: DUMMY_SP,
+        specifiers: names.iter()
+            .map(|name| ExportSpecifier::Named(ExportNamedSpecifier {
+                span: DUMMY_SP,
+                orig: ModuleExportName::Ident(Ident::new(name.as_str().into(), DUMMY_SP)),
+                exported: None,
+                is_type_only: false,
+            }))
+            .collect(),
+        src: None,
+        type_only: false,
+        with: None,
+    }
+}
+
+/// `{ a, b }`
+fn object_of(names: &[String]) -> Expr {
+    Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props: names.iter()
+            .map(|name| PropOrSpread::Prop(Box::new(Prop::Shorthand(Ident::new(name.as_str().into(), DUMMY_SP)))))
+            .collect(),
+    })
+}
+
+/// The UMD wrapper around a factory body
+fn umd(cm: &Lrc<SourceMap>, body: Vec<Stmt>) -> Result<Stmt> {
+    let mut wrapper = parse_expr(cm, "<umd>", UMD)?;
+    let Expr::Call(call) = &mut *wrapper else {
+        unreachable!("the UMD wrapper is a call");
+    };
+    let Some(Expr::Fn(factory)) = call.args.first_mut().map(|arg| &mut *arg.expr) else {
+        unreachable!("the UMD wrapper is called with the factory");
+    };
+    factory.function.body = Some(BlockStmt { span: DUMMY_SP, stmts: body });
+
+    Ok(Stmt::Expr(ExprStmt { span: DUMMY_SP, expr: wrapper }))
+}
+
+/// `(function() { body })()`
+fn iife(cm: &Lrc<SourceMap>, body: Vec<Stmt>) -> Result<Stmt> {
+    let mut wrapper = parse_expr(cm, "<iife>", "(function() {})()")?;
+    let Expr::Call(CallExpr { callee: Callee::Expr(callee), .. }) = &mut *wrapper else {
+        unreachable!("the wrapper is a call");
+    };
+    let Expr::Paren(ParenExpr { expr: function, .. }) = &mut **callee else {
+        unreachable!("the wrapper calls a parenthesized function");
+    };
+    let Expr::Fn(function) = &mut **function else {
+        unreachable!("the wrapper calls a function");
+    };
+    function.function.body = Some(BlockStmt { span: DUMMY_SP, stmts: body });
+
+    Ok(Stmt::Expr(ExprStmt { span: DUMMY_SP, expr: wrapper }))
+}
+
+fn emit(cm: &Lrc<SourceMap>, mut program: Program, minify: bool) -> Result<String> {
+    program.visit_mut_with(&mut fixer(None));
+
+    let mut output = Vec::new();
+    {
+        let mut emitter = Emitter {
+            cfg: Config::default().with_minify(minify),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm.clone(), "\n", &mut output, None),
+        };
+        emitter.emit_program(&program)?;
+    }
+    Ok(String::from_utf8(output)?)
+}
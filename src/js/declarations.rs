@@ -0,0 +1,193 @@
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashSet;
+use swc_core::common::comments::{CommentKind, Comments, SingleThreadedComments};
+use swc_core::common::errors::SourceMapper;
+use swc_core::common::sync::Lrc;
+use swc_core::common::{SourceMap, Span, Spanned, DUMMY_SP};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::codegen::text_writer::JsWriter;
+use swc_core::ecma::codegen::{Config, Emitter};
+use super::bundle::declared_names;
+
+/// reedstyle.d.ts for the bundled runtime
+///
+/// Exported interfaces and type aliases, interfaces added to other modules
+/// and `declare global` blocks are copied from the sources; the public functions, classes and constants are
+/// reprinted as ambient declarations, which is why their parameters, return
+/// types and properties need annotations (literal-valued properties excepted).
+pub(super) fn generate(
+    cm: &Lrc<SourceMap>,
+    comments: &SingleThreadedComments,
+    modules: &[(&str, Module)],
+    public: &[String],
+) -> Result<String> {
+    let mut items = Vec::new();
+    let mut declared = HashSet::new();
+
+    for (name, module) in modules {
+        let file = format!("{}.ts", name);
+        for item in &module.body {
+            let declaration = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match &export.decl {
+                    Decl::TsInterface(_) | Decl::TsTypeAlias(_) | Decl::TsEnum(_) => source(cm, export.span)?,
+                    decl => {
+                        let names: Vec<_> = declared_names(decl).into_iter().filter(|name| public.contains(name)).collect();
+                        if names.is_empty() {
+                            continue;
+                        }
+                        let decl = ambient(&file, decl, &names)?;
+                        declared.extend(names);
+                        print(cm, ExportDecl { span: DUMMY_SP, decl })?
+                    }
+                },
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(module))) if module.global => source(cm, module.span)?,
+                // `declare module './core'` adds members to a runtime interface,
+                // which merges with it as a second declaration in the one file
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(module))) if matches!(module.id, TsModuleName::Str(_)) => {
+                    let Some(TsNamespaceBody::TsModuleBlock(block)) = &module.body else {
+                        continue;
+                    };
+                    for item in &block.body {
+                        let ModuleItem::Stmt(Stmt::Decl(Decl::TsInterface(interface))) = item else {
+                            bail!("{}: only interfaces can be added to runtime modules", file);
+                        };
+                        let declaration = source(cm, interface.span)?.replace("\n  ", "\n");
+                        items.push(format!("{}export {}", doc_comment(cm, comments, item.span()), declaration));
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            items.push(format!("{}{}", doc_comment(cm, comments, item.span()), declaration));
+        }
+    }
+
+    if let Some(missing) = public.iter().find(|name| !declared.contains(*name)) {
+        bail!("`{}` is exported but not declared by a runtime module", missing);
+    }
+    Ok(items.join("\n\n") + "\n")
+}
+
+/// The `/** … */` right above an item (a blank line in between makes it a
+/// file comment), on its own line
+fn doc_comment(cm: &Lrc<SourceMap>, comments: &SingleThreadedComments, span: Span) -> String {
+    comments.get_leading(span.lo).unwrap_or_default().last()
+        .filter(|comment| comment.kind == CommentKind::Block && comment.text.starts_with('*'))
+        .filter(|comment| cm.span_to_snippet(Span::new(comment.span.hi, span.lo, Default::default()))
+            .is_ok_and(|gap| !gap.contains("\n\n")))
+        .map(|comment| format!("/*{}*/\n", comment.text))
+        .unwrap_or_default()
+}
+
+fn source(cm: &Lrc<SourceMap>, span: Span) -> Result<String> {
+    cm.span_to_snippet(span).map_err(|error| anyhow!("Cannot copy a declaration: {:?}", error))
+}
+
+/// A declaration without bodies or values
+fn ambient(file: &str, decl: &Decl, names: &[String]) -> Result<Decl> {
+    let mut decl = decl.clone();
+    match &mut decl {
+        Decl::Fn(function) => {
+            function.declare = true;
+            signature(file, &function.ident.sym, &mut function.function)?;
+        }
+        Decl::Class(class) => {
+            class.declare = true;
+            let owner = class.ident.sym.to_string();
+            let mut members = Vec::new();
+            for member in std::mem::take(&mut class.class.body) {
+                match member {
+                    ClassMember::Constructor(mut constructor) if constructor.accessibility != Some(Accessibility::Private) => {
+                        constructor.body = None;
+                        members.push(ClassMember::Constructor(constructor));
+                    }
+                    ClassMember::Method(mut method) if method.accessibility != Some(Accessibility::Private) => {
+                        signature(file, &format!("{}.{}", owner, prop_name(&method.key)), &mut method.function)?;
+                        members.push(ClassMember::Method(method));
+                    }
+                    ClassMember::ClassProp(mut prop) if prop.accessibility != Some(Accessibility::Private) => {
+                        if prop.type_ann.is_none() {
+                            prop.type_ann = prop.value.as_deref().and_then(literal_type);
+                        }
+                        if prop.type_ann.is_none() {
+                            bail!("{}: `{}.{}` needs a type annotation", file, owner, prop_name(&prop.key));
+                        }
+                        prop.value = None;
+                        members.push(ClassMember::ClassProp(prop));
+                    }
+                    ClassMember::TsIndexSignature(signature) => members.push(ClassMember::TsIndexSignature(signature)),
+                    _ => {}
+                }
+            }
+            class.class.body = members;
+        }
+        Decl::Var(var) => {
+            var.declare = true;
+            var.decls.retain(|declarator| declarator.name.as_ident().is_some_and(|ident| names.contains(&ident.sym.to_string())));
+            for declarator in &mut var.decls {
+                if let Pat::Ident(ident) = &declarator.name && ident.type_ann.is_none() {
+                    bail!("{}: `{}` needs a type annotation", file, ident.sym);
+                }
+                declarator.init = None;
+            }
+        }
+        _ => bail!("{}: cannot declare `{}`", file, names.join("`, `")),
+    }
+    Ok(decl)
+}
+
+/// Drop the body of a function, which then needs a typed signature
+fn signature(file: &str, name: &str, function: &mut Function) -> Result<()> {
+    for param in &function.params {
+        if !matches!(&param.pat, Pat::Ident(ident) if ident.type_ann.is_some()) {
+            bail!("{}: the parameters of `{}` need type annotations (and no defaults)", file, name);
+        }
+    }
+    if function.return_type.is_none() {
+        bail!("{}: `{}` needs a return type", file, name);
+    }
+    function.body = None;
+    function.is_async = false;
+    function.is_generator = false;
+    Ok(())
+}
+
+fn prop_name(key: &PropName) -> String {
+    match key {
+        PropName::Ident(ident) => ident.sym.to_string(),
+        PropName::Str(string) => string.value.to_string(),
+        _ => "[computed]".to_string(),
+    }
+}
+
+/// `boolean`, `number` or `string` for a literal initializer
+fn literal_type(value: &Expr) -> Option<Box<TsTypeAnn>> {
+    let kind = match value {
+        Expr::Lit(Lit::Bool(_)) => TsKeywordTypeKind::TsBooleanKeyword,
+        Expr::Lit(Lit::Num(_)) => TsKeywordTypeKind::TsNumberKeyword,
+        Expr::Lit(Lit::Str(_)) => TsKeywordTypeKind::TsStringKeyword,
+        _ => return None,
+    };
+    Some(Box::new(TsTypeAnn {
+        span: DUMMY_SP,
+        type_ann: Box::new(TsType::TsKeywordType(TsKeywordType { span: DUMMY_SP, kind })),
+    }))
+}
+
+fn print(cm: &Lrc<SourceMap>, export: ExportDecl) -> Result<String> {
+    let module = Module {
+        span: DUMMY_SP,
+        body: vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export))],
+        shebang: None,
+    };
+
+    let mut output = Vec::new();
+    {
+        let mut writer = JsWriter::new(cm.clone(), "\n", &mut output, None);
+        // The copied declarations use two spaces
+        writer.set_indent_str("  ");
+        let mut emitter = Emitter { cfg: Config::default(), cm: cm.clone(), comments: None, wr: writer };
+        emitter.emit_module(&module)?;
+    }
+    Ok(String::from_utf8(output)?.trim_end().to_string())
+}
@@ -0,0 +1,183 @@
+use anyhow::{bail, Result};
+use regex::Regex;
+use std::fs;
+use crate::config::{ApiConfig, ComponentsConfig, RuntimeConfig, Switch, ThemesConfig};
+
+/// An optional part of the runtime, compiled in only when a project uses it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Engine {
+    Components,
+    Bridge,
+    Colors,
+    Typography,
+    Effects,
+    Forms,
+    Theme,
+    Api,
+}
+
+impl Engine {
+    pub const ALL: [Engine; 8] = [
+        Engine::Components, Engine::Bridge, Engine::Colors, Engine::Typography,
+        Engine::Effects, Engine::Forms, Engine::Theme, Engine::Api,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Engine::Components => "components",
+            Engine::Bridge => "bridge",
+            Engine::Colors => "colors",
+            Engine::Typography => "typography",
+            Engine::Effects => "effects",
+            Engine::Forms => "forms",
+            Engine::Theme => "theme",
+            Engine::Api => "api",
+        }
+    }
+
+    fn switch(self, runtime: &RuntimeConfig) -> Switch {
+        match self {
+            Engine::Components => runtime.components,
+            Engine::Bridge => runtime.bridge,
+            Engine::Colors => runtime.colors,
+            Engine::Typography => runtime.typography,
+            Engine::Effects => runtime.effects,
+            Engine::Forms => runtime.forms,
+            Engine::Theme => runtime.theme,
+            Engine::Api => runtime.api,
+        }
+    }
+}
+
+/// The engines to compile in: `true` and `false` switches as set, `auto`
+/// ones when the content files use them (components also need definitions),
+/// and the theme and API engines when there are themes and endpoints
+pub fn select(runtime: &RuntimeConfig, components: &ComponentsConfig, themes: &ThemesConfig, api: &ApiConfig) -> Result<Vec<Engine>> {
+    let used = if runtime.content.is_empty() { None } else { Some(scan(&runtime.content)?) };
+
+    Ok(Engine::ALL.into_iter()
+        .filter(|engine| match engine.switch(runtime) {
+            Switch::On => true,
+            Switch::Off => false,
+            Switch::Auto => match engine {
+                Engine::Components if components.components.is_empty() => false,
+                // Used by scripts, so there is no markup to find
+                Engine::Theme => !themes.available.is_empty(),
+                Engine::Api => !api.endpoints.is_empty(),
+                _ => used.as_ref().is_none_or(|used| used.contains(engine)),
+            },
+        })
+        .collect())
+}
+
+/// Engines used by the files matching `patterns`
+fn scan(patterns: &[String]) -> Result<Vec<Engine>> {
+    let detector = Detector::new();
+    let mut used = Vec::new();
+    let mut files = 0;
+
+    for pattern in patterns {
+        for entry in glob::glob(pattern)? {
+            let path = entry?;
+            if !path.is_file() {
+                continue;
+            }
+            files += 1;
+            let content = fs::read(&path)?;
+            for engine in detector.detect(&String::from_utf8_lossy(&content)) {
+                if !used.contains(&engine) {
+                    used.push(engine);
+                }
+            }
+        }
+    }
+
+    if files == 0 {
+        bail!("runtime.content matches no files ({})", patterns.join(", "));
+    }
+    Ok(used)
+}
+
+/// Finds the markup each engine acts on
+struct Detector {
+    reed: Regex,
+    component: Regex,
+    filter: Regex,
+    typeset: Regex,
+    effect: Regex,
+    fetch_config: Regex,
+    data_root: Regex,
+    form: Regex,
+}
+
+impl Detector {
+    fn new() -> Self {
+        Self {
+            reed: Regex::new(r"(?i)<r-s\b[^>]*>").unwrap(),
+            component: Regex::new(r"(?i)\sas\s*=").unwrap(),
+            filter: Regex::new(r#"(?i)\stext\s*=\s*["'][^"']*\bfilter:(\w+)"#).unwrap(),
+            typeset: Regex::new(r"(?i)\sdata-typeset\b").unwrap(),
+            effect: Regex::new(r#"(?i)\sfx\s*=\s*["'][^"']*\b(?:click|scroll|stagger):"#).unwrap(),
+            fetch_config: Regex::new(r"(?i)\bdata-fetch-config\b").unwrap(),
+            data_root: Regex::new(r"(?i)\bdata-root\s*=").unwrap(),
+            form: Regex::new(r"(?i)<form\b").unwrap(),
+        }
+    }
+
+    fn detect(&self, content: &str) -> Vec<Engine> {
+        let mut used = Vec::new();
+        let fetch_config = self.fetch_config.is_match(content);
+        if fetch_config {
+            used.push(Engine::Bridge);
+        }
+        if fetch_config || self.data_root.is_match(content) {
+            used.push(Engine::Colors);
+        }
+        if self.form.is_match(content) {
+            used.push(Engine::Forms);
+        }
+
+        for tag in self.reed.find_iter(content).map(|tag| tag.as_str()) {
+            if self.component.is_match(tag) {
+                used.push(Engine::Components);
+            }
+            // Typeset at build time (`reedstyle typeset`) or opted out
+            let filtered = self.filter.captures(tag)
+                .is_some_and(|filter| !filter[1].eq_ignore_ascii_case("none"));
+            if filtered && !self.typeset.is_match(tag) {
+                used.push(Engine::Typography);
+            }
+            if self.effect.is_match(tag) {
+                used.push(Engine::Effects);
+            }
+        }
+
+        used.sort();
+        used.dedup();
+        used
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_engines() {
+        let detector = Detector::new();
+
+        assert!(detector.detect(r#"<r-s box="[padding:4]" fx="hover:lift">Card</r-s>"#).is_empty());
+        assert_eq!(detector.detect(r#"<r-s as="card">"#), [Engine::Components]);
+        assert_eq!(detector.detect(r#"<r-s class="x" fx="click:ripple">"#), [Engine::Effects]);
+        assert_eq!(detector.detect(r#"<R-S text="[size:large, filter:smart]">"#), [Engine::Typography]);
+        assert!(detector.detect(r#"<r-s text="[filter:none]">"#).is_empty());
+        assert!(detector.detect(r#"<r-s text="[filter:smart]" data-typeset>"#).is_empty());
+        assert!(detector.detect(r#"<div as="card" fx="click:ripple">"#).is_empty());
+        assert_eq!(detector.detect(r#"<body data-root="[brand-a:#FF6B6B]">"#), [Engine::Colors]);
+        assert_eq!(detector.detect(r#"<form id="contact" method="post">"#), [Engine::Forms]);
+        assert_eq!(
+            detector.detect(r#"<script src="reedstyle.js" data-fetch-config></script>"#),
+            [Engine::Bridge, Engine::Colors]
+        );
+    }
+}
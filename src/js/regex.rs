@@ -0,0 +1,191 @@
+//! Rust `regex` sources and replacements rewritten for JavaScript
+//!
+//! Rust classes are Unicode-aware; in JavaScript `\b`, `\w`, `\d` and `\s`
+//! stay ASCII even with the `u` flag the engine compiles with, so they are
+//! spelled out as Unicode properties. Syntax without a JavaScript equivalent
+//! fails the build instead of matching something else in the browser.
+
+use anyhow::{bail, Result};
+
+/// Rust's `\w`: Alphabetic, marks, decimal digits, connector punctuation and joiners
+const WORD: &str = r"\p{Alphabetic}\p{M}\p{Nd}\p{Pc}\u{200C}\u{200D}";
+
+/// The characters that keep their backslash in `u` mode
+const SYNTAX: &str = r"^$\.*+?()[]{}|/";
+
+/// A Rust pattern as the source of a JavaScript `RegExp` with the `u` flag
+pub fn pattern(source: &str) -> Result<String> {
+    let mut js = String::new();
+    let mut chars = source.chars().peekable();
+    let mut in_class = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let Some(escaped) = chars.next() else {
+                    bail!("Pattern '{}' ends in a backslash", source);
+                };
+                match (escaped, in_class) {
+                    ('b', false) => js.push_str(&format!("(?:(?<=[{0}])(?![{0}])|(?<![{0}])(?=[{0}]))", WORD)),
+                    ('B', false) => js.push_str(&format!("(?:(?<=[{0}])(?=[{0}])|(?<![{0}])(?![{0}]))", WORD)),
+                    ('w', false) => js.push_str(&format!("[{}]", WORD)),
+                    ('W', false) => js.push_str(&format!("[^{}]", WORD)),
+                    ('w', true) => js.push_str(WORD),
+                    ('d', _) => js.push_str(r"\p{Nd}"),
+                    ('D', _) => js.push_str(r"\P{Nd}"),
+                    ('s', _) => js.push_str(r"\p{White_Space}"),
+                    ('S', _) => js.push_str(r"\P{White_Space}"),
+                    ('A', false) => js.push('^'),
+                    ('z', false) => js.push('$'),
+                    ('p' | 'P', _) => {
+                        js.push('\\');
+                        js.push(escaped);
+                        match chars.next() {
+                            Some('{') => {
+                                js.push('{');
+                                for c in chars.by_ref() {
+                                    js.push(c);
+                                    if c == '}' {
+                                        break;
+                                    }
+                                }
+                            }
+                            // \pL
+                            Some(class) => js.push_str(&format!("{{{}}}", class)),
+                            None => bail!("Pattern '{}' ends in \\{}", source, escaped),
+                        }
+                    }
+                    ('x', _) if chars.peek() == Some(&'{') => {
+                        js.push_str("\\u");
+                        for c in chars.by_ref() {
+                            js.push(c);
+                            if c == '}' {
+                                break;
+                            }
+                        }
+                    }
+                    ('x' | 'n' | 't' | 'r' | 'f' | 'v', _) => {
+                        js.push('\\');
+                        js.push(escaped);
+                    }
+                    ('-', true) => js.push_str(r"\-"),
+                    (c, _) if SYNTAX.contains(c) => {
+                        js.push('\\');
+                        js.push(c);
+                    }
+                    // Rust escapes any punctuation; `u` mode only syntax characters
+                    (c, _) if c.is_ascii_punctuation() => js.push(c),
+                    (c, _) => bail!("Pattern '{}' uses \\{}, which JavaScript reads differently", source, c),
+                }
+            }
+            '[' if in_class => bail!("Pattern '{}' nests character classes, which JavaScript has not", source),
+            '[' => {
+                in_class = true;
+                js.push('[');
+                if chars.peek() == Some(&'^') {
+                    js.push(chars.next().unwrap());
+                }
+                // A leading `]` is literal in Rust
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                    js.push_str(r"\]");
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                js.push(']');
+            }
+            '&' | '~' if in_class && chars.peek() == Some(&c) => {
+                bail!("Pattern '{}' uses class set operations, which JavaScript has not", source);
+            }
+            '.' if !in_class => js.push_str(r"[^\n]"),
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                chars.next();
+                js.push_str("(?");
+                match chars.next() {
+                    Some(':') => js.push(':'),
+                    Some('<') => js.push('<'),
+                    Some('P') if chars.peek() == Some(&'<') => {}
+                    _ => bail!("Pattern '{}' uses inline flags, which JavaScript has not", source),
+                }
+            }
+            c => js.push(c),
+        }
+    }
+
+    Ok(js)
+}
+
+/// A Rust replacement (`$1`, `${1}`, `${name}`, `$$`) for `String.replace`
+pub fn replacement(source: &str) -> Result<String> {
+    let mut js = String::new();
+    let mut rest = source;
+
+    while let Some(at) = rest.find('$') {
+        js.push_str(&rest[..at]);
+        rest = &rest[at + 1..];
+
+        let (name, braced) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], true),
+                None => ("", false),
+            },
+            None => {
+                let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+                (&rest[..end], false)
+            }
+        };
+        if name.is_empty() {
+            // `$$`, or a `$` that is no reference: literal in Rust, and `$&` is not
+            js.push_str("$$");
+            rest = rest.strip_prefix('$').unwrap_or(rest);
+            continue;
+        }
+        rest = &rest[name.len() + if braced { 2 } else { 0 }..];
+
+        match name.parse::<u8>() {
+            // Two digits, so a digit that follows is not read as part of it
+            Ok(group) if group < 10 => js.push_str(&format!("$0{}", group)),
+            Ok(group) if group < 100 && !rest.starts_with(|c: char| c.is_ascii_digit()) => js.push_str(&format!("${}", group)),
+            Ok(_) => bail!("Replacement '{}' refers to group {}, which JavaScript cannot", source, name),
+            Err(_) => js.push_str(&format!("$<{}>", name)),
+        }
+    }
+    js.push_str(rest);
+
+    Ok(js)
+}
+
+/// A literal replacement, with `$` kept as written
+pub fn literal(replacement: &str) -> String {
+    replacement.replace('$', "$$")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern() {
+        assert_eq!(
+            pattern(r"\b([aiz]) ").unwrap(),
+            format!("(?:(?<=[{0}])(?![{0}])|(?<![{0}])(?=[{0}]))([aiz]) ", WORD)
+        );
+        assert_eq!(pattern(r"([¿¡])\s+").unwrap(), r"([¿¡])\p{White_Space}+");
+        assert_eq!(pattern(r"(\w)[\w\-]*\.\d").unwrap(), format!(r"([{0}])[{0}\-]*\.\p{{Nd}}", WORD));
+        assert_eq!(pattern(r"(?P<n>\pL)\#.\x{2019}[]a]").unwrap(), r"(?<n>\p{L})#[^\n]\u{2019}[\]a]");
+
+        for unsupported in [r"(?i)abc", r"[a-z&&[^aeiou]]", r"[[:alpha:]]", r"\h", r"[\W]"] {
+            assert!(pattern(unsupported).is_err(), "{}", unsupported);
+        }
+    }
+
+    #[test]
+    fn test_replacement() {
+        assert_eq!(replacement("$1\u{00A0}").unwrap(), "$01\u{00A0}");
+        assert_eq!(replacement("${1}0 ${2}").unwrap(), "$010 $02");
+        assert_eq!(replacement("$name: ${other}!").unwrap(), "$<name>: $<other>!");
+        assert_eq!(replacement("$$5 $& $").unwrap(), "$$5 $$& $$");
+        assert_eq!(literal("US$ 5"), "US$$ 5");
+    }
+}
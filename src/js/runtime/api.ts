@@ -0,0 +1,189 @@
+/**
+ * API client: requests with the base URL, headers, auth, timeout and
+ * retries of reedstyle.api.yaml
+ *
+ * `ReedStyle.api` has a function per endpoint, generated from the YAML into
+ * the script - endpoints never need to appear in DOM attributes.
+ */
+
+declare const __API_SETTINGS__: ApiSettings;
+
+/** The `config:` section of reedstyle.api.yaml */
+export interface ApiSettings {
+  baseUrl: string;
+  defaults: { method: string; headers: Record<string, string> };
+  auth: { type: 'none' | 'bearer' | 'basic' | 'cookie'; tokenKey: string };
+  errors: { retry: number; timeout: number };
+}
+
+/** Sent as JSON (as the query string of GET requests), or as it is when `fetch` takes it */
+export type ApiBody = BodyInit | Record<string, unknown> | unknown[] | null;
+
+export interface ApiOptions {
+  /** Added to the configured headers */
+  headers?: Record<string, string>;
+  /** Query string parameters */
+  query?: Record<string, string | number | boolean>;
+  /** Milliseconds per attempt, instead of the configured timeout (0: none) */
+  timeout?: number;
+  /** Retries, instead of the configured number (or none, for methods other than GET, HEAD, PUT, DELETE and OPTIONS) */
+  retry?: number;
+  signal?: AbortSignal;
+}
+
+/** `ReedStyle.api` besides the endpoints */
+export interface ApiHelpers {
+  request: typeof request;
+  getToken: typeof getToken;
+  setToken: typeof setToken;
+}
+
+/** A failed request: `status` is 0 for network errors and timeouts */
+export class ApiError extends Error {
+  status: number;
+  /** The response body, parsed like a successful one */
+  data: unknown;
+
+  constructor(message: string, status: number, data: unknown) {
+    super(message);
+    this.name = 'ApiError';
+    this.status = status;
+    this.data = data;
+  }
+}
+
+const SETTINGS: ApiSettings = __API_SETTINGS__;
+
+/** Responses worth another attempt: the server did not handle the request */
+const RETRY_STATUS = [408, 429, 502, 503, 504];
+
+/**
+ * Methods that are safe to send twice; a timed-out POST may have been
+ * handled, so others are only retried when the endpoint or call asks
+ */
+const IDEMPOTENT = ['GET', 'HEAD', 'PUT', 'DELETE', 'OPTIONS'];
+
+/** The token sent with bearer and basic auth (for basic: base64 `user:password`) */
+export function getToken(): string | null {
+  try {
+    return SETTINGS.auth.tokenKey ? localStorage.getItem(SETTINGS.auth.tokenKey) : null;
+  } catch {
+    return null;
+  }
+}
+
+/** Store the token sent with bearer and basic auth; `null` removes it */
+export function setToken(token: string | null): void {
+  if (!SETTINGS.auth.tokenKey) return;
+  try {
+    if (token === null) {
+      localStorage.removeItem(SETTINGS.auth.tokenKey);
+    } else {
+      localStorage.setItem(SETTINGS.auth.tokenKey, token);
+    }
+  } catch {
+    // Storage disabled: requests go without the token
+  }
+}
+
+/** The names `headers` has for a header, in any case */
+function headerNames(headers: Record<string, string>, header: string): string[] {
+  return Object.keys(headers).filter(name => name.toLowerCase() === header);
+}
+
+function isPlain(body: ApiBody): body is Record<string, unknown> | unknown[] {
+  return Array.isArray(body) || Object.getPrototypeOf(body) === Object.prototype;
+}
+
+/** Send a request: JSON responses are parsed, others are text, 204 is `null` */
+export async function request<T = unknown>(method: string, path: string, body?: ApiBody, options?: ApiOptions): Promise<T> {
+  const { auth, errors } = SETTINGS;
+  const headers: Record<string, string> = { ...SETTINGS.defaults.headers };
+  const token = auth.type === 'bearer' || auth.type === 'basic' ? getToken() : null;
+  if (token) {
+    headers.Authorization = `${auth.type === 'bearer' ? 'Bearer' : 'Basic'} ${token}`;
+  }
+
+  const query = new URLSearchParams();
+  Object.entries(options?.query ?? {}).forEach(([name, value]) => query.append(name, String(value)));
+  const init: RequestInit = { method, credentials: auth.type === 'cookie' ? 'include' : 'same-origin' };
+
+  if (body !== undefined && body !== null) {
+    if ((method === 'GET' || method === 'HEAD') && isPlain(body)) {
+      Object.entries(body).forEach(([name, value]) => query.append(name, String(value)));
+    } else if (isPlain(body)) {
+      init.body = JSON.stringify(body);
+      if (headerNames(headers, 'content-type').length === 0) {
+        headers['Content-Type'] = 'application/json';
+      }
+    } else {
+      // fetch sets the type of form data (with its boundary) itself
+      init.body = body;
+      headerNames(headers, 'content-type').forEach(name => delete headers[name]);
+    }
+  }
+  init.headers = { ...headers, ...options?.headers };
+
+  const search = query.toString();
+  const url = SETTINGS.baseUrl + path + (search ? (path.includes('?') ? '&' : '?') + search : '');
+  const retries = options?.retry ?? (IDEMPOTENT.includes(method.toUpperCase()) ? errors.retry : 0);
+  const timeout = options?.timeout ?? errors.timeout;
+
+  for (let attempt = 0; ; attempt++) {
+    let error: ApiError;
+    try {
+      const { response, data } = await send(url, init, timeout, options?.signal);
+      if (response.ok) return data as T;
+      error = new ApiError(`${method} ${path}: ${response.status} ${response.statusText}`, response.status, data);
+      if (!RETRY_STATUS.includes(response.status)) throw error;
+    } catch (cause) {
+      // Cancelled by the caller, or not worth retrying
+      if (cause instanceof ApiError || options?.signal?.aborted) throw cause;
+      error = new ApiError(`${method} ${path}: ${(cause as Error).message}`, 0, null);
+    }
+    if (attempt >= retries) throw error;
+    // 0.25s, 0.5s, 1s, …
+    await new Promise(resolve => setTimeout(resolve, 250 * 2 ** attempt));
+  }
+}
+
+/** One attempt, response body included, within `timeout` */
+async function send(url: string, init: RequestInit, timeout: number, signal?: AbortSignal): Promise<{ response: Response; data: unknown }> {
+  const controller = new AbortController();
+  const abort = () => controller.abort();
+  if (signal?.aborted) abort();
+  signal?.addEventListener('abort', abort);
+  const timer = timeout > 0 ? setTimeout(abort, timeout) : undefined;
+
+  try {
+    const response = await fetch(url, { ...init, signal: controller.signal });
+    const json = response.headers.get('content-type')?.includes('json');
+    const data = response.status === 204 ? null : json ? await response.json() : await response.text();
+    return { response, data };
+  } catch (error) {
+    if (controller.signal.aborted && !signal?.aborted) {
+      throw new Error(`timed out after ${timeout}ms`);
+    }
+    throw error;
+  } finally {
+    clearTimeout(timer);
+    signal?.removeEventListener('abort', abort);
+  }
+}
+
+/**
+ * The function of an endpoint; a path with `{name}` segments takes their values first.
+ * `retry` opts a non-idempotent endpoint into the configured retries.
+ */
+export function endpoint(method: string, path: string, retry = false): (...args: any[]) => Promise<any> {
+  const withRetry = (options?: ApiOptions) => retry ? { retry: SETTINGS.errors.retry, ...options } : options;
+  if (!/\{\w+\}/.test(path)) {
+    return (body?: ApiBody, options?: ApiOptions) => request(method, path, body, withRetry(options));
+  }
+  return (params: Record<string, string | number>, body?: ApiBody, options?: ApiOptions) => {
+    const filled = path.replace(/\{(\w+)\}/g, (_, name: string) => encodeURIComponent(String(params[name])));
+    return request(method, filled, body, withRetry(options));
+  };
+}
+
+export const apiHelpers: ApiHelpers = { request, getToken, setToken };
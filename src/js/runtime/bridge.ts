@@ -0,0 +1,180 @@
+/**
+ * Bridge layer for third-party CSS frameworks
+ *
+ * The layer is compiled into reedstyle.css; loading reedstyle.bridge.yaml at
+ * runtime is a development aid (`fetchConfig`).
+ */
+
+import type { BridgeFramework, BridgeOverride } from './types';
+import { registerEngine } from './core';
+
+/** Load bridge layer for third-party CSS frameworks from YAML (fetchConfig only) */
+export async function initBridge(): Promise<void> {
+  try {
+    // Try to load bridge configuration - check multiple locations
+    let response = await fetch('reedstyle.bridge.yaml');
+    if (!response.ok) {
+      // Try parent directory if we're in a subdirectory
+      response = await fetch('../reedstyle.bridge.yaml');
+    }
+    if (!response.ok) {
+      console.log('No bridge configuration found');
+      return;
+    }
+
+    const bridges = parseBridgeYaml(await response.text());
+    if (Object.keys(bridges).length === 0) {
+      console.log('No bridge frameworks configured');
+      return;
+    }
+
+    const styleEl = document.createElement('style');
+    styleEl.id = 'reedstyle-bridge';
+    let cssText = '@layer bridge {\n';
+
+    for (const [name, config] of Object.entries(bridges)) {
+      if (!config.enabled) {
+        console.log(`✗ Bridge layer '${name}' disabled`);
+        continue;
+      }
+      console.log(`✓ Bridge layer '${name}' enabled`);
+      cssText += `  @layer ${name} {\n`;
+
+      // Add @import for the framework CSS
+      if (config.path) {
+        cssText += `    @import url("${config.path}");\n`;
+      }
+
+      if (config.overrides && Array.isArray(config.overrides)) {
+        cssText += '\n    /* Custom overrides */\n';
+        for (const override of config.overrides) {
+          cssText += `    ${override.selector} {\n`;
+          const rules = override.rules.split('\n').filter(r => r.trim());
+          for (const rule of rules) {
+            cssText += `      ${rule.trim()}\n`;
+          }
+          cssText += '    }\n';
+        }
+      }
+
+      if (config.css) {
+        cssText += '\n    /* Custom CSS */\n';
+        for (const line of config.css.split('\n')) {
+          cssText += `    ${line}\n`;
+        }
+      }
+
+      cssText += '  }\n\n';
+    }
+
+    cssText += '}\n';
+
+    // Insert bridge layer BEFORE the main ReedSTYLE CSS
+    styleEl.textContent = cssText;
+    const reedstyleLink = document.querySelector('link[href*="reedstyle.css"]');
+    if (reedstyleLink) {
+      document.head.insertBefore(styleEl, reedstyleLink);
+    } else {
+      document.head.appendChild(styleEl);
+    }
+  } catch (error) {
+    console.log('Bridge layer initialization skipped:', (error as Error).message);
+  }
+}
+
+/** Parse bridge YAML configuration */
+export function parseBridgeYaml(yamlText: string): Record<string, BridgeFramework> {
+  const bridges: Record<string, BridgeFramework> = {};
+  let currentBridge: string | null = null;
+  let currentOverride: BridgeOverride | null = null;
+  let inRules = false;
+  let inCss = false;
+  let cssBuffer: string[] = [];
+
+  for (const line of yamlText.split('\n')) {
+    const trimmed = line.trim();
+
+    // Skip comments and empty lines
+    if (!trimmed || trimmed.startsWith('#')) continue;
+    if (trimmed === 'bridge:') continue;
+
+    // Stop at the next top-level section ('migration:', 'profiles:')
+    if (!line.startsWith(' ') && trimmed.endsWith(':')) break;
+
+    // Framework name (2 spaces indent)
+    if (line.startsWith('  ') && !line.startsWith('    ')) {
+      if (!trimmed.includes(':')) continue;
+      currentBridge = trimmed.replace(':', '');
+      bridges[currentBridge] = { enabled: false, overrides: [] };
+      currentOverride = null;
+      inRules = false;
+      inCss = false;
+    }
+
+    // Framework properties (4 spaces indent)
+    if (currentBridge && line.startsWith('    ') && !line.startsWith('      ')) {
+      const bridge = bridges[currentBridge];
+      if (trimmed.startsWith('enabled:')) {
+        bridge.enabled = trimmed.includes('true');
+      } else if (trimmed.startsWith('path:')) {
+        bridge.path = trimmed.split('path:')[1].trim().replace(/["']/g, '');
+      } else if (trimmed.startsWith('css:')) {
+        inCss = true;
+        cssBuffer = [];
+      } else if (trimmed.startsWith('- selector:')) {
+        currentOverride = {
+          selector: trimmed.split('selector:')[1].trim().replace(/["']/g, ''),
+          rules: '',
+        };
+        bridge.overrides!.push(currentOverride);
+        inRules = false;
+      }
+    }
+
+    // Override rules (6+ spaces indent)
+    if (currentOverride && line.startsWith('      ')) {
+      if (trimmed.startsWith('rules:')) {
+        inRules = true;
+        const inlineRules = trimmed.split('rules:')[1].trim();
+        if (inlineRules && inlineRules !== '|') {
+          currentOverride.rules = inlineRules.replace(/["']/g, '');
+          inRules = false;
+        }
+      } else if (inRules) {
+        currentOverride.rules += (currentOverride.rules ? '\n' : '') + trimmed;
+      }
+    }
+
+    // CSS content (multiline)
+    if (inCss && line.startsWith('      ')) {
+      cssBuffer.push(trimmed);
+    } else if (inCss) {
+      if (currentBridge && cssBuffer.length > 0) {
+        bridges[currentBridge].css = cssBuffer.join('\n');
+      }
+      inCss = false;
+    }
+  }
+
+  if (inCss && currentBridge && cssBuffer.length > 0) {
+    bridges[currentBridge].css = cssBuffer.join('\n');
+  }
+
+  return bridges;
+}
+
+declare module './core' {
+  interface ReedStyleApi {
+    initBridge: typeof initBridge;
+    parseBridgeYaml: typeof parseBridgeYaml;
+  }
+}
+
+registerEngine({
+  name: 'bridge',
+  async init({ fetchConfig }) {
+    if (fetchConfig) {
+      await initBridge();
+    }
+  },
+}, { initBridge, parseBridgeYaml });
@@ -0,0 +1,224 @@
+/**
+ * Color overrides: `data-root` on the body, or reedstyle.colors.yaml with
+ * `fetchConfig`
+ */
+
+import type { Oklch } from './types';
+import { registerEngine } from './core';
+
+/** Apply data-root colors, or colors from YAML with fetchConfig */
+export async function initColors(fetchConfig: boolean): Promise<void> {
+  // data-root on the body has the highest priority and applies in production too
+  const dataRoot = document.body.getAttribute('data-root');
+  if (dataRoot) {
+    parseDataRoot(dataRoot);
+    return;
+  }
+  if (!fetchConfig) return;
+
+  try {
+    const response = await fetch('/reedstyle.colors.yaml');
+    if (response.ok) {
+      parseYamlColors(await response.text());
+    }
+  } catch (e) {
+    // No YAML file is fine - use defaults from CSS
+    console.log('No custom colors defined (reedstyle.colors.yaml not found)');
+  }
+}
+
+/** Style element for color overrides - MUST come after the stylesheets */
+function colorStyle(): HTMLStyleElement {
+  let styleEl = document.getElementById('reedstyle-colors') as HTMLStyleElement | null;
+  if (!styleEl) {
+    styleEl = document.createElement('style');
+    styleEl.id = 'reedstyle-colors';
+    const links = document.head.querySelectorAll('link[rel="stylesheet"]');
+    if (links.length > 0) {
+      links[links.length - 1].after(styleEl);
+    } else {
+      document.head.appendChild(styleEl);
+    }
+  }
+  return styleEl;
+}
+
+/** Custom properties for a color's 1-9 scale and its base (scale-5) */
+function colorProperties(name: string, oklch: Oklch): string {
+  const scale = generateColorScale(oklch);
+  let cssText = '';
+  for (let i = 1; i <= 9; i++) {
+    cssText += `    --rs-color-${name}-${i}: ${scale[i - 1]};\n`;
+  }
+  cssText += `    --rs-color-${name}: ${scale[4]};\n`;
+  return cssText;
+}
+
+/** Parse YAML colors and apply them */
+export function parseYamlColors(yamlText: string): void {
+  let inColors = false;
+  let cssText = '@layer settings {\n  :root {\n';
+
+  for (const line of yamlText.split('\n')) {
+    if (line.trim().startsWith('#') || !line.trim()) continue;
+    if (line.trim() === 'colors:') {
+      inColors = true;
+      continue;
+    }
+
+    if (inColors && line.startsWith('  ')) {
+      const match = line.match(/^\s+(\S+):\s*["']?([^"']+)["']?/);
+      if (match) {
+        const [, name, value] = match;
+        const oklch = toOklch(value.trim());
+        if (oklch) {
+          cssText += colorProperties(name, oklch);
+        }
+      }
+    }
+  }
+
+  cssText += '  }\n}';
+  colorStyle().textContent = cssText;
+}
+
+/** Parse data-root attribute and set colors: [brand-a:#FF6B6B, brand-b:rgb(78,205,196)] */
+export function parseDataRoot(dataRoot: string): void {
+  const colorPattern = /(brand-[a-f]|state-\w+)\s*:\s*(#[0-9a-fA-F]{6}|rgb[a]?\([^)]+\)|hsl[a]?\([^)]+\)|oklch\([^)]+\))/gi;
+  let cssText = '@layer settings {\n  :root {\n';
+
+  let match: RegExpExecArray | null;
+  while ((match = colorPattern.exec(dataRoot)) !== null) {
+    const [, name, value] = match;
+    const oklch = toOklch(value);
+    if (oklch) {
+      cssText += colorProperties(name, oklch);
+    } else {
+      console.error(`Failed to convert ${name} color: ${value}`);
+    }
+  }
+
+  cssText += '  }\n}';
+  colorStyle().textContent = cssText;
+}
+
+/** Convert any color format to OKLCH */
+export function toOklch(color: string): Oklch | null {
+  let r = 0;
+  let g = 0;
+  let b = 0;
+
+  if (color.startsWith('#')) {
+    const hex = color.slice(1);
+    r = parseInt(hex.substr(0, 2), 16) / 255;
+    g = parseInt(hex.substr(2, 2), 16) / 255;
+    b = parseInt(hex.substr(4, 2), 16) / 255;
+  } else if (color.startsWith('rgb')) {
+    const match = color.match(/rgba?\(\s*(\d+)\s*,\s*(\d+)\s*,\s*(\d+)/);
+    if (match) {
+      r = parseInt(match[1]) / 255;
+      g = parseInt(match[2]) / 255;
+      b = parseInt(match[3]) / 255;
+    }
+  } else if (color.startsWith('hsl')) {
+    const match = color.match(/hsla?\(\s*(\d+)\s*,\s*(\d+)%\s*,\s*(\d+)%/);
+    if (match) {
+      const h = parseInt(match[1]) / 360;
+      const s = parseInt(match[2]) / 100;
+      const l = parseInt(match[3]) / 100;
+
+      // HSL to RGB conversion
+      const c = (1 - Math.abs(2 * l - 1)) * s;
+      const x = c * (1 - Math.abs((h * 6) % 2 - 1));
+      const m = l - c / 2;
+
+      if (h < 1 / 6) { r = c + m; g = x + m; b = m; }
+      else if (h < 2 / 6) { r = x + m; g = c + m; b = m; }
+      else if (h < 3 / 6) { r = m; g = c + m; b = x + m; }
+      else if (h < 4 / 6) { r = m; g = x + m; b = c + m; }
+      else if (h < 5 / 6) { r = x + m; g = m; b = c + m; }
+      else { r = c + m; g = m; b = x + m; }
+    }
+  } else if (color.startsWith('oklch')) {
+    // Pass through
+    const match = color.match(/oklch\((\d+(?:\.\d+)?)%\s+(\d+(?:\.\d+)?)\s+(\d+(?:\.\d+)?)/);
+    if (match) {
+      return {
+        l: parseFloat(match[1]) / 100,
+        c: parseFloat(match[2]),
+        h: parseFloat(match[3]),
+      };
+    }
+  } else {
+    return null;
+  }
+
+  // Linear RGB
+  const toLinear = (c: number) => c <= 0.04045 ? c / 12.92 : Math.pow((c + 0.055) / 1.055, 2.4);
+  const rLin = toLinear(r);
+  const gLin = toLinear(g);
+  const bLin = toLinear(b);
+
+  // OKLAB
+  const l = 0.4122214708 * rLin + 0.5363325363 * gLin + 0.0514459929 * bLin;
+  const m = 0.2119034982 * rLin + 0.6806995451 * gLin + 0.1073969566 * bLin;
+  const s = 0.0883024619 * rLin + 0.2817188376 * gLin + 0.6299787005 * bLin;
+
+  const lRoot = Math.cbrt(l);
+  const mRoot = Math.cbrt(m);
+  const sRoot = Math.cbrt(s);
+
+  const L = 0.2104542553 * lRoot + 0.7936177850 * mRoot - 0.0040720468 * sRoot;
+  const a = 1.9779984951 * lRoot - 2.4285922050 * mRoot + 0.4505937099 * sRoot;
+  const bValue = 0.0259040371 * lRoot + 0.7827717662 * mRoot - 0.8086757660 * sRoot;
+
+  // LCH
+  const C = Math.sqrt(a * a + bValue * bValue);
+  let H = Math.atan2(bValue, a) * 180 / Math.PI;
+  if (H < 0) H += 360;
+
+  return { l: L, c: C, h: H };
+}
+
+/** Generate 1-9 color scale from OKLCH */
+export function generateColorScale(oklch: Oklch): string[] {
+  const scale: string[] = [];
+  const targetLightness = [0.95, 0.85, 0.75, 0.65, 0.55, 0.45, 0.35, 0.25, 0.15];
+
+  for (let i = 0; i < 9; i++) {
+    const l = targetLightness[i];
+    // Reduce chroma at extremes
+    let chromaFactor = 1;
+    if (i === 0) chromaFactor = 0.1;
+    else if (i === 1) chromaFactor = 0.3;
+    else if (i === 2) chromaFactor = 0.5;
+    else if (i >= 6) chromaFactor = 0.8 - (i - 6) * 0.2;
+
+    const c = oklch.c * chromaFactor;
+    scale.push(`oklch(${(l * 100).toFixed(2)}% ${c.toFixed(3)} ${oklch.h.toFixed(1)})`);
+  }
+  return scale;
+}
+
+declare module './core' {
+  interface ReedStyleApi {
+    initColors: typeof initColors;
+    parseYamlColors: typeof parseYamlColors;
+    parseDataRoot: typeof parseDataRoot;
+    toOklch: typeof toOklch;
+    generateColorScale: typeof generateColorScale;
+  }
+}
+
+registerEngine({
+  name: 'colors',
+  // Colors MUST be set before the stylesheet renders
+  early() {
+    const dataRoot = document.body?.getAttribute('data-root');
+    if (dataRoot) {
+      parseDataRoot(dataRoot);
+    }
+  },
+  // data-root overrides apply in production too
+  init: ({ fetchConfig }) => initColors(fetchConfig),
+}, { initColors, parseYamlColors, parseDataRoot, toOklch, generateColorScale });
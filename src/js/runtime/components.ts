@@ -0,0 +1,236 @@
+/**
+ * Component system: applies component definitions to `<r-s as="…">` elements,
+ * registers more at runtime and creates reed elements
+ */
+
+import type { Component } from './types';
+import { registerEngine } from './core';
+
+declare const __COMPONENTS__: Record<string, Component>;
+
+/** Component definitions from reedstyle.components.yaml, then registered ones */
+const definitions: Record<string, Component> = { ...__COMPONENTS__ };
+
+const NAMESPACES = ['box', 'face', 'text', 'layout', 'device', 'fx'] as const;
+const FIELDS = ['element', 'extends', ...NAMESPACES] as const;
+
+/** Props of `createElement`: namespaces, other attributes, `on…` listeners and children */
+export interface ElementProps {
+  box?: string;
+  face?: string;
+  /** A value without brackets is the text content (`{ as: 'h3', text: 'Title' }`) */
+  text?: string;
+  layout?: string;
+  device?: string;
+  fx?: string;
+  /** Text content */
+  content?: string;
+  children?: (Node | string | ChildProps)[];
+  [attribute: string]: unknown;
+}
+
+export interface ChildProps extends ElementProps {
+  as: string;
+}
+
+/** `ReedStyle.components` */
+export interface ComponentsApi {
+  /** Build-time and registered definitions, by name */
+  definitions: Record<string, Component>;
+  register: typeof registerComponent;
+  load: typeof loadComponents;
+  apply: typeof applyComponent;
+  resolve: typeof resolveComponent;
+}
+
+/**
+ * Resolve a component with the fields of the one it extends, like the build
+ * does: fields set on the component win, only the direct base is merged in,
+ * and an unknown base is ignored
+ */
+export function resolveComponent(name: string): Component | null {
+  const component = definitions[name];
+  if (!component) return null;
+
+  const base = component.extends ? definitions[component.extends] : undefined;
+  if (!base) return component;
+
+  const resolved: Component = { ...component };
+  for (const field of FIELDS) {
+    if (resolved[field] === undefined && base[field] !== undefined) {
+      resolved[field] = base[field];
+    }
+  }
+  return resolved;
+}
+
+/** Add or replace a component and apply it to the elements already using it */
+export function registerComponent(name: string, definition: Component): void {
+  const component: Component = {};
+  for (const field of FIELDS) {
+    const value = definition[field];
+    if (typeof value === 'string') {
+      component[field] = value;
+    }
+  }
+  definitions[name] = component;
+
+  document.querySelectorAll('r-s[as]').forEach(element => {
+    if (element.getAttribute('as') === name) {
+      applyComponent(element);
+    }
+  });
+}
+
+/** Fetch a components YAML file and register its components */
+export async function loadComponents(yamlPath: string): Promise<Record<string, Component>> {
+  const response = await fetch(yamlPath);
+  if (!response.ok) {
+    throw new Error(`Cannot load ${yamlPath}: ${response.status} ${response.statusText}`);
+  }
+  const loaded = parseComponentsYaml(await response.text());
+  Object.entries(loaded).forEach(([name, definition]) => registerComponent(name, definition));
+  return loaded;
+}
+
+/** Parse the `components:` section of reedstyle.components.yaml */
+export function parseComponentsYaml(yamlText: string): Record<string, Component> {
+  const parsed: Record<string, Component> = {};
+  let inComponents = false;
+  let nameIndent = -1;
+  let current: Component | null = null;
+
+  for (const line of yamlText.split('\n')) {
+    const trimmed = line.trim();
+    if (!trimmed || trimmed.startsWith('#')) continue;
+
+    const indent = line.length - line.trimStart().length;
+    if (indent === 0) {
+      inComponents = trimmed === 'components:';
+      current = null;
+      continue;
+    }
+    if (!inComponents) continue;
+
+    if (nameIndent < 0) nameIndent = indent;
+    const match = trimmed.match(/^([\w-]+)\s*:\s*(.*)$/);
+    if (!match) continue;
+
+    if (indent === nameIndent) {
+      current = parsed[match[1]] = {};
+    } else if (current && (FIELDS as readonly string[]).includes(match[1])) {
+      current[match[1] as keyof Component] = yamlScalar(match[2]);
+    }
+  }
+  return parsed;
+}
+
+/** A quoted or plain YAML scalar (plain ones end at a comment) */
+function yamlScalar(value: string): string {
+  const quoted = value.match(/^(["'])(.*)\1\s*(#.*)?$/);
+  if (quoted) return quoted[2];
+  return value.replace(/\s+#.*$/, '').trim();
+}
+
+/** Apply component attributes to element (attributes already present win) */
+export function applyComponentToElement(element: Element, component: Component): void {
+  for (const namespace of NAMESPACES) {
+    const value = component[namespace];
+    if (value && !element.hasAttribute(namespace)) {
+      element.setAttribute(namespace, value);
+    }
+  }
+}
+
+/** Apply the component named by the element's `as` */
+export function applyComponent(element: Element): void {
+  const component = resolveComponent(element.getAttribute('as') ?? '');
+  if (component) {
+    applyComponentToElement(element, component);
+  }
+}
+
+/** Create `<r-s as="…">` with props, then the component's attributes */
+export function createElement(as: string, props?: ElementProps): HTMLElement {
+  const element = document.createElement('r-s');
+  element.setAttribute('as', as);
+
+  for (const [name, value] of Object.entries(props ?? {})) {
+    if (value === undefined || value === null || name === 'children') continue;
+    if (name === 'content' || (name === 'text' && !String(value).trim().startsWith('['))) {
+      element.textContent = String(value);
+    } else if (name.startsWith('on') && typeof value === 'function') {
+      element.addEventListener(name.slice(2).toLowerCase(), value as EventListener);
+    } else {
+      element.setAttribute(name, String(value));
+    }
+  }
+  for (const child of props?.children ?? []) {
+    element.append(typeof child === 'string' || !('as' in child) ? child : createElement(child.as, child));
+  }
+
+  applyComponent(element);
+  return element;
+}
+
+/** Apply component definitions to all matching elements */
+export function applyComponents(): void {
+  document.querySelectorAll('r-s[as]').forEach(applyComponent);
+}
+
+/** Start observing DOM for new component elements */
+export function observeComponents(): void {
+  const observer = new MutationObserver(mutations => {
+    mutations.forEach(mutation => {
+      mutation.addedNodes.forEach(node => {
+        if (node.nodeType !== 1) return;
+        const element = node as Element;
+        if (element.tagName === 'R-S' && element.hasAttribute('as')) {
+          applyComponent(element);
+        }
+        element.querySelectorAll('r-s[as]').forEach(applyComponent);
+      });
+    });
+  });
+  observer.observe(document.body, { childList: true, subtree: true });
+}
+
+export const components: ComponentsApi = {
+  definitions,
+  register: registerComponent,
+  load: loadComponents,
+  apply: applyComponent,
+  resolve: resolveComponent,
+};
+
+declare module './core' {
+  interface ReedStyleApi {
+    components: ComponentsApi;
+    createElement: typeof createElement;
+    /** Component definitions from reedstyle.components.yaml */
+    componentDefinitions: Record<string, Component>;
+    applyComponents: typeof applyComponents;
+    resolveComponent: typeof resolveComponent;
+    applyComponentToElement: typeof applyComponentToElement;
+    observeComponents: typeof observeComponents;
+  }
+}
+
+registerEngine({
+  name: 'components',
+  async init({ components: yamlPath }) {
+    if (yamlPath) {
+      await loadComponents(yamlPath).catch(error => console.warn('Components not loaded:', error.message));
+    }
+    applyComponents();
+    observeComponents();
+  },
+}, {
+  components,
+  createElement,
+  componentDefinitions: definitions,
+  applyComponents,
+  resolveComponent,
+  applyComponentToElement,
+  observeComponents,
+});
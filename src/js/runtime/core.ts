@@ -0,0 +1,93 @@
+/**
+ * ReedSTYLE runtime core: `ReedStyle`, the engine registry and `init`
+ *
+ * Reed elements work purely through CSS; the browser treats `<r-s>` as an
+ * unknown element and the stylesheet handles the rest. This runtime is an
+ * optional enhancement, and each engine in it is optional too: the build
+ * includes the engines a project uses (`runtime:` in reedstyle.config.yaml),
+ * and each registers itself here.
+ *
+ * Importing the ES module has no side effects: call `init()` (or `autoInit()`
+ * for the `<script>` behaviour). reedstyle.js calls `autoInit()` itself when
+ * loaded as a plain script.
+ */
+
+import type { BridgeFramework, Palette, ReedStyleConfig } from './types';
+import { BRIDGE, FETCH_CONFIG, PALETTE, VERSION } from './data';
+import type { StateApi } from './state';
+import { state } from './state';
+
+export { VERSION } from './data';
+
+/** `window.ReedStyle`; engines add their members */
+export interface ReedStyleApi {
+  version: string;
+  /** Bridge frameworks from reedstyle.bridge.yaml */
+  bridge: Record<string, BridgeFramework>;
+  /** Resolved OKLCH colors and scales, as written to reedstyle.css */
+  palette: Palette;
+  /** Engines in this build */
+  engines: string[];
+  /** Global store; `state.create()` makes more */
+  state: StateApi;
+  init(config?: ReedStyleConfig): Promise<void>;
+}
+
+/** An optional part of the runtime */
+export interface Engine {
+  name: string;
+  /** Runs as soon as `autoInit()` is called, before the DOM is ready */
+  early?(): void;
+  /** Runs on `init()`, in bundle order */
+  init(config: EngineConfig): void | Promise<void>;
+}
+
+/** `init()` options, with `fetchConfig` resolved */
+export type EngineConfig = ReedStyleConfig & { fetchConfig: boolean };
+
+const engines: Engine[] = [];
+
+/** Add an engine and its members of `ReedStyle` */
+export function registerEngine(engine: Engine, members: Partial<ReedStyleApi>): void {
+  engines.push(engine);
+  ReedStyle.engines.push(engine.name);
+  Object.assign(ReedStyle, members);
+}
+
+/** Initialize ReedSTYLE: every engine in this build */
+export async function init(config?: ReedStyleConfig): Promise<void> {
+  console.log('ReedSTYLE initializing...');
+  // Bridge layer and colors are compiled into reedstyle.css; loading the
+  // YAML at runtime is a development aid for editing without rebuilds
+  const engineConfig = { ...config, fetchConfig: config?.fetchConfig ?? FETCH_CONFIG };
+  // Engines start in order; loading ones finish in parallel
+  await Promise.all(engines.map(engine => engine.init(engineConfig)));
+  console.log('ReedSTYLE initialized');
+}
+
+export const ReedStyle: ReedStyleApi = {
+  version: VERSION,
+  bridge: BRIDGE,
+  palette: PALETTE,
+  engines: [],
+  state,
+  init,
+};
+
+declare global {
+  interface Window {
+    ReedStyle: ReedStyleApi;
+  }
+}
+
+/** What loading reedstyle.js does: expose `window.ReedStyle` and initialize once the DOM is ready */
+export function autoInit(): void {
+  window.ReedStyle = ReedStyle;
+  engines.forEach(engine => engine.early?.());
+
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', () => init());
+  } else {
+    init();
+  }
+}
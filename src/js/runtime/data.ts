@@ -0,0 +1,24 @@
+/**
+ * Build-time data
+ *
+ * `js::generate` replaces each `__NAME__` placeholder with a literal from the
+ * YAML configuration; a placeholder without data fails the build. Data only
+ * an engine needs is declared in the engine's module.
+ */
+
+import type { BridgeFramework, Palette } from './types';
+
+declare const __VERSION__: string;
+declare const __BRIDGE__: Record<string, BridgeFramework>;
+declare const __PALETTE__: Palette;
+
+export const VERSION: string = __VERSION__;
+
+/** Bridge frameworks from reedstyle.bridge.yaml */
+export const BRIDGE: Record<string, BridgeFramework> = __BRIDGE__;
+
+/** Resolved OKLCH colors and scales, as written to reedstyle.css */
+export const PALETTE: Palette = __PALETTE__;
+
+// <script src="reedstyle.js" data-fetch-config> loads the YAML at runtime instead (development only)
+export const FETCH_CONFIG: boolean = typeof document !== 'undefined' && (document.currentScript?.hasAttribute('data-fetch-config') ?? false);
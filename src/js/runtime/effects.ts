@@ -0,0 +1,134 @@
+/**
+ * Effects engine: click ripples, scroll reveals and stagger delays for
+ * `r-s[fx]`
+ */
+
+import { ReedStyle, registerEngine } from './core';
+
+const STAGGER_DELAYS: Record<string, number> = {
+  tiny: 50,
+  small: 100,
+  normal: 200,
+  large: 300,
+};
+
+const EFFECT_STYLES = `
+  .r-s-ripple {
+    position: absolute;
+    border-radius: 50%;
+    background: rgba(255, 255, 255, 0.6);
+    transform: translate(-50%, -50%);
+    pointer-events: none;
+    animation: ripple 600ms ease-out;
+  }
+  @keyframes ripple {
+    to {
+      width: 200px;
+      height: 200px;
+      opacity: 0;
+    }
+  }
+`;
+
+/** Inject the CSS for dynamic effects */
+export function injectEffectStyles(): void {
+  const style = document.createElement('style');
+  style.textContent = EFFECT_STYLES;
+  document.head.appendChild(style);
+}
+
+export class EffectsEngine {
+  constructor() {
+    this.init();
+  }
+
+  init(): void {
+    this.initClickEffects();
+    this.initScrollEffects();
+  }
+
+  initClickEffects(): void {
+    document.addEventListener('click', event => {
+      const reed = (event.target as Element).closest('r-s[fx*="click:"]');
+      if (reed?.getAttribute('fx')?.includes('click:ripple')) {
+        this.createRipple(event, reed as HTMLElement);
+      }
+    });
+  }
+
+  createRipple(event: MouseEvent, element: HTMLElement): void {
+    const ripple = document.createElement('span');
+    ripple.className = 'r-s-ripple';
+    const rect = element.getBoundingClientRect();
+    const x = event.clientX - rect.left;
+    const y = event.clientY - rect.top;
+    ripple.style.cssText = `
+      position: absolute;
+      left: ${x}px;
+      top: ${y}px;
+      transform: translate(-50%, -50%);
+      width: 20px;
+      height: 20px;
+      border-radius: 50%;
+      background: rgba(255, 255, 255, 0.5);
+      animation: ripple 600ms ease-out;
+      pointer-events: none;
+    `;
+    element.appendChild(ripple);
+    setTimeout(() => ripple.remove(), 600);
+  }
+
+  initScrollEffects(): void {
+    const elements = document.querySelectorAll('r-s[fx*="scroll:"]');
+    if (elements.length === 0) return;
+
+    const observer = new IntersectionObserver(
+      entries => {
+        entries.forEach(entry => {
+          const fx = entry.target.getAttribute('fx');
+          const repeat = fx?.includes('repeat:true') ?? false;
+          if (entry.isIntersecting) {
+            entry.target.classList.add('in-view');
+            if (fx?.includes('stagger:')) {
+              this.applyStagger(entry.target as HTMLElement, fx);
+            }
+            if (!repeat) {
+              observer.unobserve(entry.target);
+            }
+          } else if (repeat) {
+            entry.target.classList.remove('in-view');
+          }
+        });
+      },
+      { threshold: 0.1, rootMargin: '0px' }
+    );
+
+    elements.forEach(element => observer.observe(element));
+  }
+
+  applyStagger(element: HTMLElement, fx: string): void {
+    const siblings = [...element.parentElement!.children].filter(sibling =>
+      sibling.tagName === 'R-S' && sibling.getAttribute('fx')?.includes('stagger:')
+    );
+    const index = siblings.indexOf(element);
+    const staggerMatch = fx.match(/stagger:(\w+)/);
+    const delay = staggerMatch ? STAGGER_DELAYS[staggerMatch[1]] || 100 : 100;
+    element.style.animationDelay = `${index * delay}ms`;
+  }
+}
+
+declare module './core' {
+  interface ReedStyleApi {
+    /** Set by `init()` */
+    effects?: EffectsEngine;
+  }
+}
+
+registerEngine({
+  name: 'effects',
+  init() {
+    injectEffectStyles();
+    ReedStyle.effects = new EffectsEngine();
+    console.log('Effects Engine initialized');
+  },
+}, {});
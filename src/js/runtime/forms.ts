@@ -0,0 +1,315 @@
+/**
+ * Forms: validation rules on top of the native constraint API, ajax
+ * submission, and forms sent to an endpoint of reedstyle.api.yaml
+ *
+ * Messages go through `setCustomValidity`, so the browser shows them and
+ * `:invalid` styles apply; forms still submit normally without JS.
+ *
+ * A form with `api="group.name"` (or inside an element with it) shows
+ * validation messages in `<r-s as="form-error" for="field">` instead of the
+ * browser's bubbles, is sent with `ReedStyle.api.group.name`, and has
+ * `state="loading|success|error"` for the CSS. Its `action` is the fallback
+ * without JS or without that endpoint.
+ */
+
+import { ReedStyle, registerEngine } from './core';
+
+export interface ValidationRule {
+  required?: boolean;
+  email?: boolean;
+  pattern?: RegExp;
+  minLength?: number;
+  maxLength?: number;
+}
+
+export interface ValidateOptions {
+  /** By field name */
+  rules: Record<string, ValidationRule>;
+  /** Replace the default messages, by field name and rule */
+  messages?: Record<string, Partial<Record<keyof ValidationRule, string>>>;
+  onValid?(data: Record<string, string>): void;
+  onInvalid?(errors: Record<string, string>): void;
+}
+
+export interface AjaxOptions {
+  /** Defaults to the form's `action` */
+  url?: string;
+  /** Defaults to the form's `method` */
+  method?: string;
+  headers?: Record<string, string>;
+  /** Parsed JSON, or the text of other responses */
+  onSuccess?(data: unknown, response: Response): void;
+  onError?(error: Error): void;
+}
+
+/** `ReedStyle.forms` */
+export interface FormsApi {
+  validate: typeof validateForm;
+  ajax: typeof ajaxForm;
+}
+
+const EMAIL = /^[^\s@]+@[^\s@]+\.[^\s@]+$/;
+
+const MESSAGES: Record<keyof ValidationRule, (rule: ValidationRule) => string> = {
+  required: () => 'This field is required',
+  email: () => 'Please enter a valid email address',
+  pattern: () => 'Please match the requested format',
+  minLength: rule => `Please use at least ${rule.minLength} characters`,
+  maxLength: rule => `Please use at most ${rule.maxLength} characters`,
+};
+
+/** The form with this id, or the form inside an element with it (`<r-s as="form" id>`) */
+function findForm(formId: string): HTMLFormElement {
+  const element = document.getElementById(formId);
+  const form = element?.tagName === 'FORM' ? element : element?.querySelector('form');
+  if (!form) {
+    throw new Error(`No form '${formId}'`);
+  }
+  return form as HTMLFormElement;
+}
+
+function formValues(form: HTMLFormElement): Record<string, string> {
+  const data: Record<string, string> = {};
+  new FormData(form).forEach((value, name) => {
+    if (typeof value === 'string') data[name] = value;
+  });
+  return data;
+}
+
+/** The first rule a value breaks */
+function brokenRule(value: string, rule: ValidationRule): keyof ValidationRule | null {
+  if (!value) return rule.required ? 'required' : null;
+  if (rule.email && !EMAIL.test(value)) return 'email';
+  if (rule.pattern && !rule.pattern.test(value)) return 'pattern';
+  if (rule.minLength !== undefined && value.length < rule.minLength) return 'minLength';
+  if (rule.maxLength !== undefined && value.length > rule.maxLength) return 'maxLength';
+  return null;
+}
+
+/**
+ * Check the rules on submit (invalid forms don't submit); returns a function
+ * that checks them now and returns the errors by field name
+ */
+export function validateForm(formId: string, options: ValidateOptions): () => Record<string, string> {
+  const form = findForm(formId);
+
+  const check = (): Record<string, string> => {
+    const data = formValues(form);
+    const errors: Record<string, string> = {};
+    for (const [name, rule] of Object.entries(options.rules)) {
+      const broken = brokenRule(data[name] ?? '', rule);
+      const field = form.elements.namedItem(name) as HTMLInputElement | null;
+      if (broken) {
+        errors[name] = options.messages?.[name]?.[broken] ?? MESSAGES[broken](rule);
+      }
+      field?.setCustomValidity?.(errors[name] ?? '');
+      field?.setAttribute('aria-invalid', String(Boolean(broken)));
+    }
+    return errors;
+  };
+
+  // A corrected field may submit again
+  form.addEventListener('input', event => {
+    const field = event.target as HTMLInputElement;
+    if (field.name in options.rules) {
+      field.setCustomValidity('');
+      field.removeAttribute('aria-invalid');
+    }
+  });
+
+  form.addEventListener('submit', event => {
+    const errors = check();
+    if (Object.keys(errors).length > 0) {
+      // Also stops ajaxForm
+      event.preventDefault();
+      event.stopImmediatePropagation();
+      form.reportValidity();
+      options.onInvalid?.(errors);
+    } else {
+      options.onValid?.(formValues(form));
+    }
+  });
+
+  return check;
+}
+
+/** Submit the form with `fetch` instead of a page load */
+export function ajaxForm(formId: string, options?: AjaxOptions): void {
+  const form = findForm(formId);
+
+  form.addEventListener('submit', async event => {
+    if (event.defaultPrevented) return;
+    event.preventDefault();
+
+    const method = (options?.method ?? form.getAttribute('method') ?? 'POST').toUpperCase();
+    let url = options?.url ?? form.action;
+    const init: RequestInit = { method, headers: options?.headers };
+    if (method === 'GET') {
+      url += (url.includes('?') ? '&' : '?') + new URLSearchParams(formValues(form));
+    } else {
+      init.body = new FormData(form);
+    }
+
+    form.setAttribute('aria-busy', 'true');
+    try {
+      const response = await fetch(url, init);
+      if (!response.ok) {
+        throw new Error(`${response.status} ${response.statusText}`);
+      }
+      const json = response.headers.get('content-type')?.includes('json');
+      options?.onSuccess?.(json ? await response.json() : await response.text(), response);
+    } catch (error) {
+      options?.onError?.(error as Error);
+    } finally {
+      form.removeAttribute('aria-busy');
+    }
+  });
+}
+
+type Field = HTMLInputElement | HTMLSelectElement | HTMLTextAreaElement;
+
+/** `ReedStyle.api` as far as forms use it; the api engine may not be in the build */
+type Endpoints = Record<string, Record<string, ((body?: unknown) => Promise<unknown>) | undefined> | undefined>;
+
+/** The element with `api` of a form: the form, or one around it */
+function apiHost(form: HTMLFormElement): Element | null {
+  return form.closest('[api]');
+}
+
+/** The endpoint function named by `api`, if the build has it */
+function apiEndpoint(host: Element): ((body?: unknown) => Promise<unknown>) | undefined {
+  const [group, name] = (host.getAttribute('api') ?? '').split('.');
+  const api = (ReedStyle as unknown as { api?: Endpoints }).api;
+  const send = api?.[group]?.[name];
+  if (!send) {
+    console.warn(`ReedSTYLE: no endpoint '${host.getAttribute('api')}', the form submits normally`);
+  }
+  return send;
+}
+
+/** Form values as an object (repeated names as arrays), or form data when there are files */
+function formBody(form: HTMLFormElement): FormData | Record<string, unknown> {
+  const data = new FormData(form);
+  const body: Record<string, unknown> = {};
+  let files = false;
+  data.forEach((value, name) => {
+    files ||= typeof value !== 'string';
+    const previous = body[name];
+    body[name] = previous === undefined ? value : ([] as unknown[]).concat(previous, value);
+  });
+  return files ? data : body;
+}
+
+/** The `<r-s as="form-error">` of a field, created after it (or its label) if the page has none */
+function errorElement(host: Element, field: Field): Element | null {
+  const name = field.name || field.id;
+  if (!name) return null;
+
+  const existing = Array.from(host.querySelectorAll('r-s[as="form-error"]'))
+    .find(element => element.getAttribute('for') === name);
+  if (existing) return existing;
+
+  const element = document.createElement('r-s');
+  element.setAttribute('as', 'form-error');
+  element.setAttribute('for', name);
+  element.setAttribute('aria-live', 'polite');
+  const anchor = field.closest('label') ?? field;
+  anchor.after(element);
+  return element;
+}
+
+function showError(host: Element, field: Field): void {
+  field.setAttribute('state', 'invalid');
+  field.setAttribute('aria-invalid', 'true');
+  const message = errorElement(host, field);
+  if (message) {
+    message.id ||= `rs-error-${++errorIds}`;
+    message.textContent = field.validationMessage;
+    field.setAttribute('aria-describedby', message.id);
+  }
+}
+
+function clearError(host: Element, field: Field): void {
+  field.removeAttribute('state');
+  field.removeAttribute('aria-invalid');
+  const message = errorElement(host, field);
+  if (message) message.textContent = '';
+}
+
+let errorIds = 0;
+let enhanced = false;
+/** Forms whose first invalid field has been focused in this validation */
+const focused = new Set<HTMLFormElement>();
+
+function setState(host: Element, state: 'loading' | 'success' | 'error'): void {
+  host.setAttribute('state', state);
+  host.setAttribute('aria-busy', String(state === 'loading'));
+}
+
+/** Messages instead of the browser's bubbles, live updates once a field is invalid, and endpoint submission */
+function enhanceApiForms(): void {
+  if (enhanced) return;
+  enhanced = true;
+
+  // Fired for each invalid field when a submission is validated
+  document.addEventListener('invalid', event => {
+    const field = event.target as Field;
+    const host = field.form && apiHost(field.form);
+    if (!field.form || !host) return;
+    event.preventDefault();
+    showError(host, field);
+    if (!focused.has(field.form)) {
+      focused.add(field.form);
+      field.focus();
+      queueMicrotask(() => focused.clear());
+    }
+  }, true);
+
+  const recheck = (event: Event) => {
+    const field = event.target as Field;
+    const host = field.form && apiHost(field.form);
+    if (!host || field.getAttribute('state') !== 'invalid') return;
+    if (field.validity.valid) {
+      clearError(host, field);
+    } else {
+      showError(host, field);
+    }
+  };
+  document.addEventListener('input', recheck);
+  document.addEventListener('change', recheck);
+
+  // After the form's own listeners: `validate` and `ajax` may have handled it
+  document.addEventListener('submit', async event => {
+    const form = event.target as HTMLFormElement;
+    const host = apiHost(form);
+    if (!host || event.defaultPrevented) return;
+    if (host.getAttribute('state') === 'loading') {
+      event.preventDefault();
+      return;
+    }
+    const send = apiEndpoint(host);
+    if (!send) return;
+    event.preventDefault();
+
+    setState(host, 'loading');
+    try {
+      const data = await send(formBody(form));
+      setState(host, 'success');
+      form.reset();
+      host.dispatchEvent(new CustomEvent('reedstyle:success', { bubbles: true, detail: data }));
+    } catch (error) {
+      setState(host, 'error');
+      host.dispatchEvent(new CustomEvent('reedstyle:error', { bubbles: true, detail: error }));
+    }
+  });
+}
+
+export const forms: FormsApi = { validate: validateForm, ajax: ajaxForm };
+
+declare module './core' {
+  interface ReedStyleApi {
+    forms: FormsApi;
+  }
+}
+
+registerEngine({ name: 'forms', init: enhanceApiForms }, { forms });
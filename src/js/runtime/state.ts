@@ -0,0 +1,37 @@
+/**
+ * State: small reactive stores for progressive enhancement
+ */
+
+/** `set` merges changes into the state and notifies the subscribers */
+export interface Store<State extends object> {
+  get(): State;
+  set(changes: Partial<State>): void;
+  /** Called with the new and the previous state; returns an unsubscribe function */
+  subscribe(callback: (state: State, previous: State) => void): () => void;
+}
+
+/** The global store, which also makes new ones */
+export interface StateApi extends Store<Record<string, unknown>> {
+  create<State extends object>(initial: State): Store<State>;
+}
+
+/** A store with `initial` as its state */
+export function createStore<State extends object>(initial: State): Store<State> {
+  let current = { ...initial };
+  const subscribers = new Set<(state: State, previous: State) => void>();
+
+  return {
+    get: () => current,
+    set(changes) {
+      const previous = current;
+      current = { ...current, ...changes };
+      subscribers.forEach(callback => callback(current, previous));
+    },
+    subscribe(callback) {
+      subscribers.add(callback);
+      return () => subscribers.delete(callback);
+    },
+  };
+}
+
+export const state: StateApi = { ...createStore<Record<string, unknown>>({}), create: createStore };
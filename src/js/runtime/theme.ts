@@ -0,0 +1,92 @@
+/**
+ * Theme switching: `ReedStyle.theme` applies a theme, remembers the choice
+ * and follows `prefers-color-scheme` until there is one
+ */
+
+import type { Theme } from './themes';
+import { THEMES, applyTheme, defaultTheme, storedTheme } from './themes';
+import { registerEngine } from './core';
+
+/** `ReedStyle.theme` */
+export interface ThemeApi {
+  /** Themes from reedstyle.config.yaml, by name */
+  available: Record<string, Theme>;
+  /** The active theme, `null` for none */
+  get(): string | null;
+  /** Switch to a theme and remember it; `null` forgets the choice */
+  set(name: string | null): void;
+  /** Called with the new and the previous theme; returns an unsubscribe function */
+  onChange(callback: (theme: string | null, previous: string | null) => void): () => void;
+}
+
+const listeners = new Set<(theme: string | null, previous: string | null) => void>();
+let current: string | null = null;
+let chosen: string | null = null;
+let started = false;
+
+function switchTheme(name: string | null): void {
+  const previous = current;
+  current = name;
+  applyTheme(name);
+  if (name !== previous) {
+    listeners.forEach(callback => callback(name, previous));
+  }
+}
+
+/** Switch to a theme and remember it; `null` forgets the choice and returns to the default */
+export function setTheme(name: string | null): void {
+  if (name !== null && !(name in THEMES.available)) {
+    throw new Error(`Unknown theme '${name}'`);
+  }
+  chosen = name;
+  try {
+    if (THEMES.storage && name === null) {
+      localStorage.removeItem(THEMES.storage);
+    } else if (THEMES.storage && name !== null) {
+      localStorage.setItem(THEMES.storage, name);
+    }
+  } catch {
+    // Storage is disabled: the choice lasts as long as the page
+  }
+  switchTheme(chosen ?? defaultTheme());
+}
+
+/** Apply the remembered or default theme and follow changes to either */
+function startThemes(): void {
+  if (started) return;
+  started = true;
+
+  chosen = storedTheme();
+  switchTheme(chosen ?? defaultTheme());
+
+  if (typeof matchMedia === 'function') {
+    matchMedia('(prefers-color-scheme: dark)').addEventListener('change', () => {
+      if (chosen === null) switchTheme(defaultTheme());
+    });
+  }
+  // A choice made in another tab
+  window.addEventListener('storage', event => {
+    if (THEMES.storage && event.key === THEMES.storage) {
+      chosen = storedTheme();
+      switchTheme(chosen ?? defaultTheme());
+    }
+  });
+}
+
+export const theme: ThemeApi = {
+  available: THEMES.available,
+  get: () => current,
+  set: setTheme,
+  onChange(callback) {
+    listeners.add(callback);
+    return () => listeners.delete(callback);
+  },
+};
+
+declare module './core' {
+  interface ReedStyleApi {
+    theme: ThemeApi;
+  }
+}
+
+registerEngine({ name: 'theme', early: startThemes, init: startThemes }, { theme });
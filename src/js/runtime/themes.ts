@@ -0,0 +1,74 @@
+/**
+ * Themes from reedstyle.config.yaml and the choice between them
+ *
+ * Also inlined on its own as dist/reedstyle.theme.html, which applies the
+ * choice before first paint.
+ */
+
+declare const __THEMES__: Themes;
+
+export interface Theme {
+  /** Matched against `prefers-color-scheme`, and set as `color-scheme` */
+  scheme?: 'light' | 'dark';
+  /** Stylesheet URL loaded while the theme is active */
+  stylesheet?: string;
+}
+
+export interface Themes {
+  /** Theme without a stored choice; `auto` picks by `prefers-color-scheme` */
+  default: string;
+  /** localStorage key of the chosen theme (empty: not remembered) */
+  storage: string;
+  /** Themes by name */
+  available: Record<string, Theme>;
+}
+
+export const THEMES: Themes = __THEMES__;
+
+/** The remembered choice, if it is still a theme */
+export function storedTheme(): string | null {
+  try {
+    const name = THEMES.storage ? localStorage.getItem(THEMES.storage) : null;
+    return name !== null && name in THEMES.available ? name : null;
+  } catch {
+    // Storage is disabled
+    return null;
+  }
+}
+
+/** The theme without a choice: `default`, or the one for `prefers-color-scheme` */
+export function defaultTheme(): string | null {
+  if (THEMES.default !== 'auto') return THEMES.default;
+
+  const dark = typeof matchMedia === 'function' && matchMedia('(prefers-color-scheme: dark)').matches;
+  const scheme = dark ? 'dark' : 'light';
+  return Object.keys(THEMES.available).find(name => THEMES.available[name].scheme === scheme) ?? null;
+}
+
+/** Set `data-theme` and `color-scheme` on the document and load the theme's stylesheet (`null`: no theme) */
+export function applyTheme(name: string | null): void {
+  const root = document.documentElement;
+  const theme = name === null ? undefined : THEMES.available[name];
+  if (name === null) {
+    root.removeAttribute('data-theme');
+  } else {
+    root.setAttribute('data-theme', name);
+  }
+  root.style.colorScheme = theme?.scheme ?? '';
+
+  let link = document.getElementById('reedstyle-theme') as HTMLLinkElement | null;
+  if (!theme?.stylesheet) {
+    link?.remove();
+    return;
+  }
+  if (!link) {
+    // After reedstyle.css, when the bootstrap follows it in the <head>
+    link = document.createElement('link');
+    link.id = 'reedstyle-theme';
+    link.rel = 'stylesheet';
+    document.head.appendChild(link);
+  }
+  if (link.getAttribute('href') !== theme.stylesheet) {
+    link.setAttribute('href', theme.stylesheet);
+  }
+}
@@ -0,0 +1,99 @@
+/**
+ * ReedSTYLE runtime types
+ *
+ * Type-only module: `js::generate` strips it from the bundle.
+ */
+
+export interface ReedStyleConfig {
+  /** Path to components YAML file */
+  components?: string;
+  /** Enable effects engine */
+  effects?: boolean;
+  /** Enable typography engine */
+  typography?: boolean;
+  /** Enable lazy loading */
+  lazy?: boolean;
+  /** Load reedstyle.bridge.yaml and reedstyle.colors.yaml at runtime (development only) */
+  fetchConfig?: boolean;
+}
+
+/** A component from reedstyle.components.yaml */
+export interface Component {
+  /** HTML element to use */
+  element?: string;
+  /** Extend another component */
+  extends?: string;
+  box?: string;
+  face?: string;
+  text?: string;
+  layout?: string;
+  device?: string;
+  fx?: string;
+}
+
+export interface BridgeOverride {
+  selector: string;
+  rules: string;
+}
+
+/** A framework from reedstyle.bridge.yaml */
+export interface BridgeFramework {
+  enabled: boolean;
+  path?: string | null;
+  version?: string | null;
+  overrides?: BridgeOverride[] | null;
+  css?: string | null;
+  mappings?: Record<string, string> | null;
+}
+
+export interface ColorToken {
+  name: string;
+  /** Base color in OKLCH (absent for the neutral scale) */
+  base?: string;
+  /** Generated 1-9 scale */
+  scale?: string[];
+}
+
+export type SemanticToken =
+  | { name: string; target: string }
+  | { name: string; value: string };
+
+/** Resolved colors, as written to reedstyle.css */
+export interface Palette {
+  colors: ColorToken[];
+  semantic: SemanticToken[];
+}
+
+export interface Oklch {
+  l: number;
+  c: number;
+  h: number;
+}
+
+/** Quotes and replacements of one language */
+export interface TypographyRuleSet<Pattern = RegExp> {
+  quotes: [string, string];
+  singleQuotes: [string, string];
+  /** The ASCII quote that stands for primary quotation marks (`'` in British English) */
+  primaryQuote: string;
+  /** Chinese and Japanese have no spaces between words */
+  unspaced?: boolean;
+  replacements: [Pattern, string][];
+}
+
+export interface TypographyRules<Pattern = RegExp> {
+  /** By lowercase language tag */
+  languages: Record<string, TypographyRuleSet<Pattern>>;
+  default: TypographyRuleSet<Pattern>;
+}
+
+export interface WidowConfig {
+  /** Words kept together at the end of a block */
+  words: number;
+  /** Longest paragraph (in characters) that is protected */
+  shortParagraph: number;
+  /** Longest joined tail (in characters) */
+  maxChars: number;
+  /** Selector for the protected blocks */
+  blocks: string;
+}
@@ -0,0 +1,325 @@
+/**
+ * Typography engine: smart quotes, replacements and widows for
+ * `r-s[text*="filter:"]`, with the rules the build-time typesetter uses
+ */
+
+import type { TypographyRuleSet, TypographyRules, WidowConfig } from './types';
+import { ReedStyle, registerEngine } from './core';
+
+declare const __TYPOGRAPHY_RULES__: TypographyRules<string>;
+declare const __TYPOGRAPHY_WIDOWS__: WidowConfig;
+
+/** Quote and replacement tables (with regex sources), from the registry the build-time typesetter uses */
+const TYPOGRAPHY_RULES: TypographyRules<string> = __TYPOGRAPHY_RULES__;
+
+const TYPOGRAPHY_WIDOWS: WidowConfig = __TYPOGRAPHY_WIDOWS__;
+
+/** Code-like content and opt-outs (translate="no", filter:none) stay as written */
+const TYPOGRAPHY_SKIP = 'script, style, textarea, code, pre, kbd, samp, var, [translate="no"], r-s[text*="filter:none"]';
+
+/** Separates the text nodes of an element while the rules see it as one string */
+const NODE_BREAK = '\uE000';
+
+const APOSTROPHE = '\u2019';
+const PRIME = '\u2032';
+const DOUBLE_PRIME = '\u2033';
+
+const ALPHABETIC = /\p{Alphabetic}/u;
+const ALPHANUMERIC = /[\p{Alphabetic}\p{N}]/u;
+const WHITESPACE = /\p{White_Space}/u;
+const DIGIT = /[0-9]/;
+const LATIN = /[A-Za-z0-9]/;
+const OPENING_PUNCTUATION = '([{-\u2013\u2014/\u00A0\u202F\u201E\u201A\u201C\u2018\u00AB\u2039';
+
+/** An open quotation: the ASCII character that opened it and whether it uses the primary marks */
+interface OpenQuote {
+  quote: string;
+  primary: boolean;
+}
+
+function is(c: string | undefined, pattern: RegExp): boolean {
+  return c !== undefined && pattern.test(c);
+}
+
+function lastOpen(open: OpenQuote[], quote: string): number {
+  for (let i = open.length - 1; i >= 0; i--) {
+    if (open[i].quote === quote) return i;
+  }
+  return -1;
+}
+
+/** The nearest character before or after `index`, skipping node breaks */
+function neighbour(chars: string[], index: number, step: number): string | undefined {
+  for (let i = index + step; i >= 0 && i < chars.length; i += step) {
+    if (chars[i] < '\uE000' || chars[i] > '\uF8FF') return chars[i];
+  }
+  return undefined;
+}
+
+/** `'90s`, `'24`: an apostrophe followed by exactly two digits */
+function isYearAbbreviation(chars: string[], index: number): boolean {
+  let digits = 0;
+  while (is(chars[index + 1 + digits], DIGIT)) digits++;
+  return digits === 2 && chars[index + 3] !== "'" && !is(chars[index + 3], DIGIT);
+}
+
+/**
+ * Quotes read in context, like `quotes::apply`: a quote after a space or
+ * opening punctuation opens, one after a word closes the innermost quotation
+ * opened with the same character. `'` between letters or before `'90s` is an
+ * apostrophe, quotes after a digit with nothing open are feet and inches.
+ */
+function contextQuotes(text: string, rules: TypographyRuleSet): string {
+  const chars = Array.from(text);
+  const open: OpenQuote[] = [];
+  const mark = (primary: boolean, side: 0 | 1) => (primary ? rules.quotes : rules.singleQuotes)[side];
+  let result = '';
+
+  chars.forEach((c, i) => {
+    if (c !== '"' && c !== "'") {
+      result += c;
+      return;
+    }
+
+    const prev = neighbour(chars, i, -1);
+    const next = neighbour(chars, i, 1);
+
+    // Apostrophes: geht's, isn't, '90s
+    if (c === "'" && is(prev, ALPHANUMERIC) && is(next, ALPHABETIC)) {
+      result += APOSTROPHE;
+      return;
+    }
+    if (c === "'" && !is(prev, ALPHANUMERIC) && isYearAbbreviation(chars, i)) {
+      result += APOSTROPHE;
+      return;
+    }
+
+    // Measurements: 6' 2", 12"
+    if (is(prev, DIGIT) && lastOpen(open, c) < 0) {
+      result += c === '"' ? DOUBLE_PRIME : PRIME;
+      return;
+    }
+
+    const afterSpace = prev === undefined || WHITESPACE.test(prev) || OPENING_PUNCTUATION.includes(prev);
+    const beforeSpace = next === undefined || WHITESPACE.test(next);
+
+    if (afterSpace && !beforeSpace) {
+      const outer = open[open.length - 1];
+      const primary = outer ? !outer.primary : c === rules.primaryQuote;
+      result += mark(primary, 0);
+      open.push({ quote: c, primary });
+    } else if (!afterSpace) {
+      const index = lastOpen(open, c);
+      if (index >= 0) {
+        result += mark(open[index].primary, 1);
+        open.length = index;
+      } else if (c === "'") {
+        // Trailing apostrophe (the students' books)
+        result += APOSTROPHE;
+      } else {
+        result += mark(c === rules.primaryQuote, 1);
+      }
+    } else {
+      // Free-standing quote between spaces
+      result += c;
+    }
+  });
+
+  return result;
+}
+
+/** Quotes paired by order, like `quotes::pair`, for languages written without spaces */
+function pairQuotes(text: string, rules: TypographyRuleSet): string {
+  const chars = Array.from(text);
+  const open: OpenQuote[] = [];
+  const mark = (primary: boolean, side: 0 | 1) => (primary ? rules.quotes : rules.singleQuotes)[side];
+  let result = '';
+
+  chars.forEach((c, i) => {
+    const index = lastOpen(open, c);
+    if (c !== '"' && c !== "'") {
+      result += c;
+    } else if (c === "'" && is(chars[i - 1], LATIN) && is(chars[i + 1], LATIN)) {
+      result += APOSTROPHE;
+    } else if (index >= 0) {
+      result += mark(open[index].primary, 1);
+      open.length = index;
+    } else {
+      const outer = open[open.length - 1];
+      const primary = outer ? !outer.primary : c === rules.primaryQuote;
+      result += mark(primary, 0);
+      open.push({ quote: c, primary });
+    }
+  });
+
+  return result;
+}
+
+function compile(rules: TypographyRuleSet<string>): TypographyRuleSet {
+  return {
+    ...rules,
+    replacements: rules.replacements.map(([source, replacement]) => [new RegExp(source, 'gu'), replacement]),
+  };
+}
+
+export class TypographyEngine {
+  initialized = false;
+  rules: TypographyRules = {
+    languages: Object.fromEntries(
+      Object.entries(TYPOGRAPHY_RULES.languages).map(([tag, rules]) => [tag, compile(rules)])
+    ),
+    default: compile(TYPOGRAPHY_RULES.default),
+  };
+
+  init(): void {
+    if (this.initialized) return;
+    this.processAll();
+    this.observeChanges();
+    this.initialized = true;
+    console.log('Typography Engine initialized');
+  }
+
+  processAll(): void {
+    document.querySelectorAll('r-s[text*="filter:"]').forEach(element => this.processElement(element));
+  }
+
+  processElement(element: Element): void {
+    // Already typeset at build time (reedstyle typeset)
+    if (element.hasAttribute('data-typeset')) return;
+
+    const match = element.getAttribute('text')?.match(/filter:(\w+)/);
+    const filter = match ? match[1] : null;
+    if (!filter) return;
+
+    const rules = this.rulesFor(this.detectLanguage(element));
+    this.applyTypography(element, rules, filter);
+    if (filter === 'smart' || filter === 'professional') {
+      this.preventWidows(element);
+    }
+  }
+
+  detectLanguage(element: Element): string {
+    const scope = element.closest('[lang]') as HTMLElement | null;
+    return scope ? scope.lang : (document.documentElement.lang || '');
+  }
+
+  rulesFor(lang: string): TypographyRuleSet {
+    // de-CH-1996 -> de-ch -> de -> default, like the Rust registry
+    const parts = lang.toLowerCase().split('-');
+    while (parts.length) {
+      const rules = this.rules.languages[parts.join('-')];
+      if (rules) return rules;
+      parts.pop();
+    }
+    return this.rules.default;
+  }
+
+  applyTypography(element: Element, rules: TypographyRuleSet, filter: string): void {
+    if (filter !== 'smart' && filter !== 'professional') return;
+
+    const walker = document.createTreeWalker(element, NodeFilter.SHOW_TEXT, {
+      acceptNode(node: Node) {
+        return node.parentElement!.closest(TYPOGRAPHY_SKIP) ? NodeFilter.FILTER_REJECT : NodeFilter.FILTER_ACCEPT;
+      },
+    });
+
+    const textNodes: Node[] = [];
+    let node: Node | null;
+    while ((node = walker.nextNode())) {
+      textNodes.push(node);
+    }
+
+    // One string for the whole element, so quotes pair across inline markup
+    const typeset = (text: string) => this.applyQuotes(this.applyReplacements(text, rules), rules);
+    let segments = typeset(textNodes.map(node => node.textContent ?? '').join(NODE_BREAK)).split(NODE_BREAK);
+    if (segments.length !== textNodes.length) {
+      // A rule swallowed a node boundary; fall back to node by node
+      segments = textNodes.map(node => typeset(node.textContent ?? ''));
+    }
+    textNodes.forEach((node, index) => {
+      if (segments[index] !== node.textContent) {
+        node.textContent = segments[index];
+      }
+    });
+  }
+
+  /** Replacements and abbreviations, in registry order */
+  applyReplacements(text: string, rules: TypographyRuleSet): string {
+    rules.replacements.forEach(([pattern, replacement]) => {
+      text = text.replace(pattern, replacement);
+    });
+    return text;
+  }
+
+  /** Smart quotes, like `quotes::apply` (or `quotes::pair` for Chinese and Japanese) */
+  applyQuotes(text: string, rules: TypographyRuleSet): string {
+    return rules.unspaced ? pairQuotes(text, rules) : contextQuotes(text, rules);
+  }
+
+  /** Join the last words of headings and short paragraphs (like `reedstyle typeset`) */
+  preventWidows(element: Element): void {
+    const { words, shortParagraph, maxChars, blocks } = TYPOGRAPHY_WIDOWS;
+    if (words < 2) return;
+    const targets = element.matches(blocks) ? [element] : [];
+    targets.push(...element.querySelectorAll(blocks));
+
+    targets.forEach(block => {
+      if (block.closest(TYPOGRAPHY_SKIP)) return;
+      const tag = (block.tagName === 'R-S' ? block.getAttribute('as') || '' : block.tagName).toLowerCase();
+      const text = block.textContent ?? '';
+      if (!/^h[1-6]$/.test(tag) && text.length > shortParagraph) return;
+
+      // Words need a letter or digit; dashes in between stay where they are
+      const found = [...text.matchAll(/[^ \t\n\r]+/g)].filter(word => /[\p{L}\p{N}]/u.test(word[0]));
+      if (found.length <= words) return;
+      const start = found[found.length - words].index!;
+      const last = found[found.length - 1];
+      const lastIndex = last.index!;
+      if (lastIndex + last[0].length - start > maxChars) return;
+
+      const walker = document.createTreeWalker(block, NodeFilter.SHOW_TEXT);
+      let offset = 0;
+      let node: Text | null;
+      while ((node = walker.nextNode() as Text | null)) {
+        const from = offset;
+        offset += node.data.length;
+        if (offset <= start || from >= lastIndex || node.parentElement!.closest(TYPOGRAPHY_SKIP)) continue;
+        const data = node.data.replace(/[ \t\n\r]+/g, (spaces, i) =>
+          from + i > start && from + i < lastIndex ? '\u00A0' : spaces);
+        if (data !== node.data) node.data = data;
+      }
+    });
+  }
+
+  observeChanges(): void {
+    const observer = new MutationObserver(mutations => {
+      mutations.forEach(mutation => {
+        mutation.addedNodes.forEach(node => {
+          if (node.nodeType !== 1) return;
+          const element = node as Element;
+          if (element.tagName === 'R-S' && element.getAttribute('text')?.includes('filter:')) {
+            this.processElement(element);
+          }
+          element.querySelectorAll('r-s[text*="filter:"]').forEach(child => this.processElement(child));
+        });
+      });
+    });
+
+    observer.observe(document.body, { childList: true, subtree: true });
+  }
+}
+
+declare module './core' {
+  interface ReedStyleApi {
+    /** Set by `init()` */
+    typography?: TypographyEngine;
+  }
+}
+
+registerEngine({
+  name: 'typography',
+  init() {
+    ReedStyle.typography = new TypographyEngine();
+    ReedStyle.typography.init();
+  },
+}, {});
@@ -5,6 +5,7 @@ pub mod css;
 pub mod js;
 pub mod optimizer;
 pub mod parser;
+pub mod tokens;
 pub mod typography;
 pub mod utils;
 
@@ -28,15 +29,41 @@ impl ReedStyle {
         let fonts = self.config.load_fonts()?;
         let components = self.config.load_components()?;
         let bridge = self.config.load_bridge()?;
+        let typography = self.config.load_typography()?;
+        let api = self.config.load_api()?;
         
         // Generate CSS
-        let css_output = css::generate(&self.config, &colors, &fonts, &components, &bridge)?;
+        let css_output = css::generate(&self.config, &colors, &fonts, &components, &bridge, &typography)?;
         
-        // Generate JavaScript
-        let js_output = js::generate(&components)?;
+        // Generate JavaScript (bridge and palette are compiled in, not fetched),
+        // with the engines the project uses - none means no JS at all
+        let design_tokens = tokens::DesignTokens::resolve(&colors, &fonts)?;
+        let languages = typography::LanguageRegistry::from_config(&typography);
+        let engines = js::engines::select(&self.config.runtime, &components, &self.config.themes, &api)?;
+        let js_output = if engines.is_empty() {
+            None
+        } else {
+            let project = js::RuntimeData {
+                components: &components,
+                bridge: &bridge,
+                tokens: &design_tokens,
+                languages: &languages,
+                widows: &typography.widows,
+                themes: &self.config.themes,
+                api: &api,
+            };
+            Some(js::generate(&engines, &project)?)
+        };
         
         // Write output files
-        builder::write_output(&css_output, &js_output)?;
+        builder::write_output(&css_output, js_output.as_ref())?;
+        builder::write_fonts(&fonts)?;
+        builder::write_themes(&self.config.themes)?;
+        
+        // Export design tokens for other tools (Figma, SCSS, TypeScript)
+        if !self.config.tokens.formats.is_empty() {
+            builder::write_tokens(&design_tokens, &self.config.tokens)?;
+        }
         
         Ok(())
     }
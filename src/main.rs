@@ -1,6 +1,9 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::*;
+use reedstyle::tokens::import::{self, ImportFormat};
+use reedstyle::typography::{Hyphenator, LanguageRegistry, TypesetOptions};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 #[command(name = "reedstyle")]
@@ -8,6 +11,9 @@ use colored::*;
 #[command(version = "0.1.0")]
 #[command(about = "Semantic HTML styling system", long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Watch for file changes
     #[arg(short, long)]
     watch: bool,
@@ -25,9 +31,42 @@ struct Args {
     sourcemaps: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Import design tokens from a DTCG JSON or Tailwind theme file
+    Import {
+        /// Token file (JSON)
+        file: PathBuf,
+
+        /// Source format (detected from the file if omitted)
+        #[arg(long, value_parser = ["dtcg", "tailwind"])]
+        from: Option<String>,
+
+        /// Directory for the generated YAML files
+        #[arg(long, default_value = "import")]
+        out: String,
+    },
+
+    /// Apply the language typography rules to static HTML files
+    Typeset {
+        /// HTML files to process (glob, e.g. "public/**/*.html")
+        pattern: String,
+
+        /// Write to this directory instead of overwriting the files
+        #[arg(long)]
+        out: Option<String>,
+    },
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    match &args.command {
+        Some(Command::Import { file, from, out }) => return run_import(file, from.as_deref(), out),
+        Some(Command::Typeset { pattern, out }) => return run_typeset(pattern, out.as_deref()),
+        None => {}
+    }
+
     println!("{}", "ReedSTYLE Build System".bright_cyan().bold());
     println!("{}", "═══════════════════════".bright_cyan());
 
@@ -42,4 +81,44 @@ fn main() -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+fn run_typeset(pattern: &str, out: Option<&str>) -> Result<()> {
+    println!("{} {}", "Typesetting".bright_cyan().bold(), pattern);
+    let typography = reedstyle::config::Config::load()?.load_typography()?;
+    let languages = LanguageRegistry::from_config(&typography);
+    let hyphenator = typography.hyphenate.soft_hyphens.then(|| Hyphenator::new(&typography.hyphenate));
+    let options = TypesetOptions {
+        hyphenator: hyphenator.as_ref(),
+        widows: Some(&typography.widows),
+    };
+    let files = reedstyle::builder::write_typeset(pattern, out, &languages, &options)?;
+    println!("{} {} files typeset", "✓".green().bold(), files.len());
+
+    Ok(())
+}
+
+fn run_import(file: &Path, from: Option<&str>, out: &str) -> Result<()> {
+    let format = from.map(|from| match from {
+        "tailwind" => ImportFormat::Tailwind,
+        _ => ImportFormat::Dtcg,
+    });
+
+    println!("{} {}", "Importing".bright_cyan().bold(), file.display());
+    let result = import::import_file(file, format)?;
+    reedstyle::builder::write_import(&result, out)?;
+
+    println!("{} {} colors, {} fonts", "✓".green().bold(), result.colors.len(), result.fonts.len());
+    for (name, step) in &result.spacing {
+        println!("  spacing {} → padding:{} / margin:{}", name, step, step);
+    }
+
+    if !result.unmapped.is_empty() {
+        println!("{} {} unmapped tokens:", "!".yellow().bold(), result.unmapped.len());
+        for token in &result.unmapped {
+            println!("  {} - {}", token.path, token.reason);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use super::{parse_shadow, split_dimension, DesignTokens, SemanticToken};
+
+/// Generate W3C Design Tokens (DTCG) JSON
+pub fn generate(tokens: &DesignTokens) -> Result<String> {
+    let mut root = Map::new();
+
+    // Colors: one group per color with base and 1-9 steps
+    let mut colors = Map::new();
+    for color in &tokens.colors {
+        let mut group = Map::new();
+        group.insert("$type".to_string(), json!("color"));
+        if let Some(base) = &color.base {
+            group.insert("base".to_string(), json!({ "$value": base }));
+        }
+        if let Some(scale) = &color.scale {
+            for (i, step) in scale.iter().enumerate() {
+                group.insert((i + 1).to_string(), json!({ "$value": step }));
+            }
+        }
+        colors.insert(color.name.clone(), Value::Object(group));
+    }
+
+    // Semantic colors alias the palette through DTCG references
+    let mut semantic = Map::new();
+    semantic.insert("$type".to_string(), json!("color"));
+    for token in &tokens.semantic {
+        match token {
+            SemanticToken::Alias { name, target } => {
+                semantic.insert(name.clone(), json!({ "$value": format!("{{color.{}.base}}", target) }));
+            }
+            SemanticToken::Value { name, value } => {
+                semantic.insert(name.clone(), json!({ "$value": value }));
+            }
+        }
+    }
+    colors.insert("semantic".to_string(), Value::Object(semantic));
+    root.insert("color".to_string(), Value::Object(colors));
+
+    // Font families and weights
+    let mut families = Map::new();
+    families.insert("$type".to_string(), json!("fontFamily"));
+    let mut weights = Map::new();
+    for font in &tokens.fonts {
+        let mut family = font.family.clone();
+        if !family.contains(&font.fallback) {
+            family.push(font.fallback.clone());
+        }
+        families.insert(font.name.clone(), json!({ "$value": family }));
+
+        if !font.weights.is_empty() {
+            let mut group = Map::new();
+            group.insert("$type".to_string(), json!("fontWeight"));
+            for (name, weight) in &font.weights {
+                group.insert(name.clone(), json!({ "$value": weight }));
+            }
+            weights.insert(font.name.clone(), Value::Object(group));
+        }
+    }
+    root.insert("fontFamily".to_string(), Value::Object(families));
+    root.insert("fontWeight".to_string(), Value::Object(weights));
+
+    root.insert("spacing".to_string(), dimension_group(&tokens.spacing));
+    root.insert("radius".to_string(), dimension_group(&tokens.radius));
+    root.insert("breakpoint".to_string(), dimension_group(&tokens.breakpoints));
+
+    // Shadows as composite tokens ("none" has no DTCG representation)
+    let mut shadows = Map::new();
+    shadows.insert("$type".to_string(), json!("shadow"));
+    for (name, value) in &tokens.shadow {
+        let layers: Vec<Value> = parse_shadow(value)
+            .into_iter()
+            .map(|layer| json!({
+                "color": layer.color,
+                "offsetX": dimension(&layer.offset_x),
+                "offsetY": dimension(&layer.offset_y),
+                "blur": dimension(&layer.blur),
+                "spread": dimension(&layer.spread),
+                "inset": layer.inset,
+            }))
+            .collect();
+
+        match layers.len() {
+            0 => continue,
+            1 => shadows.insert(name.clone(), json!({ "$value": layers[0] })),
+            _ => shadows.insert(name.clone(), json!({ "$value": layers })),
+        };
+    }
+    root.insert("shadow".to_string(), Value::Object(shadows));
+
+    Ok(serde_json::to_string_pretty(&Value::Object(root))?)
+}
+
+fn dimension_group(scale: &[(String, String)]) -> Value {
+    let mut group = Map::new();
+    group.insert("$type".to_string(), json!("dimension"));
+    for (name, value) in scale {
+        group.insert(name.clone(), json!({ "$value": dimension(value) }));
+    }
+    Value::Object(group)
+}
+
+/// DTCG dimension object, falling back to the raw string for non-numeric values
+fn dimension(value: &str) -> Value {
+    match split_dimension(value) {
+        Some((number, unit)) => json!({ "value": number, "unit": unit }),
+        None => json!(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ColorsConfig, FontsConfig};
+
+    #[test]
+    fn test_dtcg_structure() {
+        let tokens = DesignTokens::resolve(&ColorsConfig::default(), &FontsConfig::default()).unwrap();
+        let output: Value = serde_json::from_str(&generate(&tokens).unwrap()).unwrap();
+
+        assert_eq!(output["color"]["brand-a"]["$type"], "color");
+        assert!(output["color"]["brand-a"]["5"]["$value"].as_str().unwrap().starts_with("oklch("));
+        assert_eq!(output["color"]["semantic"]["primary"]["$value"], "{color.brand-a.base}");
+        assert_eq!(output["spacing"]["1"]["$value"]["unit"], "rem");
+        assert_eq!(output["shadow"]["md"]["$value"].as_array().unwrap().len(), 2);
+        assert_eq!(output["fontWeight"]["font-a"]["bold"]["$value"], 700);
+    }
+}
@@ -0,0 +1,248 @@
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use super::{SourceColor, SourceTokens};
+
+/// A leaf token with its group path and (possibly inherited) type
+struct FlatToken {
+    path: Vec<String>,
+    kind: Option<String>,
+    value: Value,
+}
+
+/// Read W3C Design Tokens (DTCG) JSON; Tokens Studio's `value`/`type` keys are accepted too
+pub fn read(json: &Value) -> Result<SourceTokens> {
+    let Value::Object(root) = json else {
+        bail!("DTCG file must contain a JSON object");
+    };
+
+    let mut flat = Vec::new();
+    flatten(root, &mut Vec::new(), None, &mut flat);
+
+    let lookup: HashMap<String, Value> = flat.iter()
+        .map(|token| (token.path.join("."), token.value.clone()))
+        .collect();
+
+    let mut source = SourceTokens::default();
+
+    for token in flat {
+        let path = token.path.join(".");
+        let value = match resolve_alias(&token.value, &lookup, 0) {
+            Ok(value) => value,
+            Err(reason) => {
+                source.skipped.push((path, reason));
+                continue;
+            }
+        };
+
+        let group = token.path[0].to_lowercase();
+        let kind = token.kind.clone().unwrap_or_else(|| infer_kind(&group).to_string());
+        let name = token.path.last().cloned().unwrap_or_default();
+
+        match kind.as_str() {
+            "color" => {
+                let Some(color) = color_value(&value) else {
+                    source.skipped.push((path, "unsupported color value".to_string()));
+                    continue;
+                };
+                // Drop a top-level "color" group so color.blue.500 becomes the "blue" ramp
+                let segments = if matches!(group.as_str(), "color" | "colors") && token.path.len() > 1 {
+                    &token.path[1..]
+                } else {
+                    &token.path[..]
+                };
+
+                match segments {
+                    [single] => source.colors.push((single.clone(), SourceColor::Single(color))),
+                    [ramp, step] if ramp != "semantic" => {
+                        let existing = source.colors.iter_mut().find_map(|(name, color)| match color {
+                            SourceColor::Ramp(steps) if name == ramp => Some(steps),
+                            _ => None,
+                        });
+                        match existing {
+                            Some(steps) => steps.push((step.clone(), color)),
+                            None => source.colors.push((ramp.clone(), SourceColor::Ramp(vec![(step.clone(), color)]))),
+                        }
+                    }
+                    _ => source.skipped.push((path, "semantic colors are derived from brand-*/state-*".to_string())),
+                }
+            }
+            "fontFamily" | "fontFamilies" => match &value {
+                Value::Array(items) => {
+                    let family = items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect();
+                    source.fonts.push((name, family));
+                }
+                Value::String(s) => source.fonts.push((name, crate::tokens::split_font_family(s))),
+                _ => source.skipped.push((path, "unsupported fontFamily value".to_string())),
+            },
+            // fontWeight.<family>.<name>, like our own export
+            "fontWeight" | "fontWeights" => match font_weight(&value) {
+                Some(weight) if token.path.len() > 2 => {
+                    let family = token.path[token.path.len() - 2].clone();
+                    source.font_weights.push((family, name, weight));
+                }
+                Some(_) => source.skipped.push((path, "font weights are imported per family (fontWeight.<family>.<name>)".to_string())),
+                None => source.skipped.push((path, "unsupported fontWeight value".to_string())),
+            },
+            "dimension" | "spacing" if matches!(group.as_str(), "spacing" | "space") => {
+                match dimension_value(&value) {
+                    Some(dimension) => source.spacing.push((name, dimension)),
+                    None => source.skipped.push((path, "unsupported dimension value".to_string())),
+                }
+            }
+            other => source.skipped.push((path, format!("type '{}' is not imported", other))),
+        }
+    }
+
+    // A group holding only "base" (our own export) is a single color
+    for (_, color) in source.colors.iter_mut() {
+        if let SourceColor::Ramp(steps) = color
+            && steps.len() == 1 && steps[0].0 == "base"
+        {
+            *color = SourceColor::Single(steps[0].1.clone());
+        }
+    }
+
+    Ok(source)
+}
+
+fn flatten(group: &Map<String, Value>, path: &mut Vec<String>, inherited: Option<&str>, out: &mut Vec<FlatToken>) {
+    let kind = group.get("$type").or_else(|| group.get("type"))
+        .and_then(Value::as_str)
+        .or(inherited);
+
+    if let Some(value) = group.get("$value").or_else(|| group.get("value")) {
+        if !path.is_empty() {
+            out.push(FlatToken { path: path.clone(), kind: kind.map(str::to_string), value: value.clone() });
+        }
+        return;
+    }
+
+    for (key, child) in group {
+        // $type, $description, $extensions and Tokens Studio's $themes/$metadata
+        if key.starts_with('$') {
+            continue;
+        }
+        if let Value::Object(child) = child {
+            path.push(key.clone());
+            flatten(child, path, kind, out);
+            path.pop();
+        }
+    }
+}
+
+/// Follow `{group.token}` references; nested references are limited to keep cycles finite
+fn resolve_alias(value: &Value, lookup: &HashMap<String, Value>, depth: usize) -> std::result::Result<Value, String> {
+    let Some(reference) = value.as_str()
+        .and_then(|s| s.strip_prefix('{'))
+        .and_then(|s| s.strip_suffix('}')) else {
+        return Ok(value.clone());
+    };
+
+    if depth > 16 {
+        return Err(format!("reference chain through '{{{}}}' is too deep", reference));
+    }
+
+    match lookup.get(reference) {
+        Some(target) => resolve_alias(target, lookup, depth + 1),
+        None => Err(format!("reference '{{{}}}' does not exist", reference)),
+    }
+}
+
+fn infer_kind(group: &str) -> &'static str {
+    match group {
+        "color" | "colors" => "color",
+        "font" | "fontfamily" | "fontfamilies" => "fontFamily",
+        "fontweight" | "fontweights" => "fontWeight",
+        "spacing" | "space" => "dimension",
+        _ => "unknown",
+    }
+}
+
+/// Color strings or DTCG 2025 color objects with a `hex` fallback
+fn color_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Object(obj) => obj.get("hex").and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    }
+}
+
+fn font_weight(value: &Value) -> Option<u16> {
+    match value {
+        Value::Number(n) => n.as_u64().and_then(|n| u16::try_from(n).ok()),
+        Value::String(s) => s.parse().ok().or(match s.to_lowercase().as_str() {
+            "thin" | "hairline" => Some(100),
+            "extra-light" | "ultra-light" => Some(200),
+            "light" => Some(300),
+            "normal" | "regular" | "book" => Some(400),
+            "medium" => Some(500),
+            "semi-bold" | "demi-bold" => Some(600),
+            "bold" => Some(700),
+            "extra-bold" | "ultra-bold" => Some(800),
+            "black" | "heavy" => Some(900),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// `"1rem"` or the DTCG `{ "value": 1, "unit": "rem" }` object
+fn dimension_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(format!("{}px", n)),
+        Value::Object(obj) => {
+            let number = obj.get("value")?;
+            let unit = obj.get("unit").and_then(Value::as_str).unwrap_or("px");
+            Some(format!("{}{}", number, unit))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ImportResult;
+    use serde_json::json;
+
+    #[test]
+    fn test_read_dtcg() {
+        let json = json!({
+            "color": {
+                "$type": "color",
+                "blue": { "500": { "$value": "#3b82f6" }, "600": { "$value": "#2563eb" } },
+                "accent": { "$value": "{color.blue.600}" },
+                "broken": { "$value": "{color.missing}" }
+            },
+            "spacing": {
+                "$type": "dimension",
+                "4": { "$value": { "value": 1, "unit": "rem" } }
+            },
+            "fontFamily": {
+                "$type": "fontFamily",
+                "sans": { "$value": ["Inter", "sans-serif"] },
+                "serif": { "$value": ["Lora", "serif"] }
+            },
+            "fontWeight": {
+                "$type": "fontWeight",
+                "sans": { "regular": { "$value": 400 }, "bold": { "$value": 600 } },
+                "serif": { "bold": { "$value": "bold" } },
+                "heavy": { "$value": 900 }
+            },
+            "duration": { "$type": "duration", "fast": { "$value": "100ms" } }
+        });
+
+        let result = ImportResult::from_source(read(&json).unwrap());
+        assert!(result.colors.contains(&("brand-a".to_string(), "#3b82f6".to_string())));
+        assert!(result.colors.contains(&("brand-b".to_string(), "#2563eb".to_string())));
+        assert_eq!(result.spacing, vec![("4".to_string(), "4".to_string())]);
+        assert_eq!(result.fonts[0].0, "font-a");
+        assert_eq!(result.fonts[0].1.weights, Some([("regular".to_string(), 400), ("bold".to_string(), 600)].into()));
+        assert_eq!(result.fonts[1].1.weights, Some([("bold".to_string(), 700)].into()));
+        assert!(result.unmapped.iter().any(|u| u.path == "fontWeight.heavy"));
+        assert!(result.unmapped.iter().any(|u| u.path == "color.broken"));
+        assert!(result.unmapped.iter().any(|u| u.path == "duration.fast"));
+    }
+}
@@ -0,0 +1,526 @@
+pub mod dtcg;
+pub mod tailwind;
+
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::config::{FontStack, FontsConfig};
+use crate::css::namespaces::r#box::SPACING_SCALE;
+
+/// Brand slots available for chromatic ramps, in assignment order
+const BRAND_SLOTS: [&str; 6] = ["brand-a", "brand-b", "brand-c", "brand-d", "brand-e", "brand-f"];
+
+/// Ramp names treated as the neutral (base-*) scale
+const NEUTRAL_RAMPS: [&str; 6] = ["gray", "grey", "slate", "zinc", "neutral", "stone"];
+
+/// Steps of the base-* colors in reedstyle.colors.yaml
+const BASE_STEPS: [u16; 13] = [0, 50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950, 1000];
+
+/// Source names that map onto ReedSTYLE state colors
+const STATE_NAMES: [(&str, &str); 5] = [
+    ("success", "state-success"),
+    ("warning", "state-warning"),
+    ("error", "state-error"),
+    ("danger", "state-error"),
+    ("info", "state-info"),
+];
+
+/// Source formats the importer can read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Dtcg,
+    Tailwind,
+}
+
+impl ImportFormat {
+    /// Any `$value` (or Tokens Studio `value` + `type`) token means DTCG,
+    /// everything else is read as a Tailwind theme
+    pub fn detect(json: &serde_json::Value) -> Self {
+        fn has_token(value: &serde_json::Value) -> bool {
+            match value {
+                serde_json::Value::Object(map) => {
+                    map.contains_key("$value")
+                        || (map.contains_key("value") && map.contains_key("type"))
+                        || map.values().any(has_token)
+                }
+                _ => false,
+            }
+        }
+
+        if json.get("theme").is_none() && has_token(json) {
+            ImportFormat::Dtcg
+        } else {
+            ImportFormat::Tailwind
+        }
+    }
+}
+
+/// Read and map a token file; the format is detected when not given
+pub fn import_file(path: &Path, format: Option<ImportFormat>) -> Result<ImportResult> {
+    let content = fs::read_to_string(path)?;
+    let json: serde_json::Value = serde_json::from_str(&content)?;
+
+    let source = match format.unwrap_or_else(|| ImportFormat::detect(&json)) {
+        ImportFormat::Dtcg => dtcg::read(&json)?,
+        ImportFormat::Tailwind => tailwind::read(&json)?,
+    };
+
+    Ok(ImportResult::from_source(source))
+}
+
+/// A color found in the source file - a single value or a ramp of steps
+#[derive(Debug, Clone)]
+pub enum SourceColor {
+    Single(String),
+    Ramp(Vec<(String, String)>),
+}
+
+/// Tokens collected from a source file before mapping
+#[derive(Debug, Default)]
+pub struct SourceTokens {
+    pub colors: Vec<(String, SourceColor)>,
+    pub fonts: Vec<(String, Vec<String>)>,
+    /// Font family (source name), weight name and value
+    pub font_weights: Vec<(String, String, u16)>,
+    pub spacing: Vec<(String, String)>,
+    /// Tokens the reader could not classify (path, reason)
+    pub skipped: Vec<(String, String)>,
+}
+
+/// A source token that has no place in the ReedSTYLE configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnmappedToken {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Result of an import, kept in source order for readable YAML
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub colors: Vec<(String, String)>,
+    pub semantic: Vec<(String, String)>,
+    pub fonts: Vec<(String, FontStack)>,
+    /// Source spacing name -> ReedSTYLE spacing step with the same value
+    pub spacing: Vec<(String, String)>,
+    pub unmapped: Vec<UnmappedToken>,
+}
+
+impl ImportResult {
+    /// Map collected source tokens onto ReedSTYLE names
+    pub fn from_source(source: SourceTokens) -> Self {
+        let mut result = ImportResult::default();
+        let mut free_brands = BRAND_SLOTS.to_vec();
+        let mut neutral_mapped = false;
+
+        for (path, reason) in source.skipped {
+            result.unmapped.push(UnmappedToken { path, reason });
+        }
+
+        for (name, color) in source.colors {
+            let key = name.to_lowercase();
+
+            match color {
+                SourceColor::Single(value) => {
+                    let value = match normalize_color(&value) {
+                        Ok(value) => value,
+                        Err(reason) => {
+                            result.unmapped.push(UnmappedToken { path: name, reason });
+                            continue;
+                        }
+                    };
+                    if key == "white" {
+                        result.colors.push(("base-0".to_string(), value));
+                    } else if key == "black" {
+                        result.colors.push(("base-1000".to_string(), value));
+                    } else if is_base_name(&key) {
+                        push_unique(&mut result, &key, value, &name);
+                    } else if let Some(state) = state_name(&key) {
+                        push_unique(&mut result, state, value, &name);
+                    } else if let Some(brand) = take_brand(&mut free_brands, &key) {
+                        push_brand(&mut result, brand, value, name);
+                    } else {
+                        result.unmapped.push(UnmappedToken { path: name, reason: "all brand-a..brand-f slots are taken".to_string() });
+                    }
+                }
+                SourceColor::Ramp(steps) => {
+                    // Our own export's neutral-1..neutral-9 is generated, not configured
+                    let base_steps = steps.iter().any(|(step, _)| is_base_step(step));
+                    if key == "neutral" && !base_steps {
+                        result.unmapped.push(UnmappedToken { path: name, reason: "neutral-1..neutral-9 is generated".to_string() });
+                        continue;
+                    }
+                    if NEUTRAL_RAMPS.contains(&key.as_str()) {
+                        if neutral_mapped {
+                            result.unmapped.push(UnmappedToken { path: name, reason: "only one neutral ramp maps onto base-*".to_string() });
+                            continue;
+                        }
+                        neutral_mapped = true;
+                        for (step, value) in steps {
+                            let path = format!("{}.{}", name, step);
+                            if !is_base_step(&step) {
+                                result.unmapped.push(UnmappedToken { path, reason: format!("base-{} is not a base-* step", step) });
+                                continue;
+                            }
+                            match normalize_color(&value) {
+                                Ok(value) => result.colors.push((format!("base-{}", step), value)),
+                                Err(reason) => result.unmapped.push(UnmappedToken { path, reason }),
+                            }
+                        }
+                        continue;
+                    }
+
+                    // ReedSTYLE generates its own 1-9 scale, so only the ramp's middle step is kept
+                    let Some((step, value)) = ramp_base(&steps) else {
+                        result.unmapped.push(UnmappedToken { path: name, reason: "ramp has no color steps".to_string() });
+                        continue;
+                    };
+                    for (other, _) in steps.iter().filter(|(s, _)| *s != step) {
+                        result.unmapped.push(UnmappedToken {
+                            path: format!("{}.{}", name, other),
+                            reason: format!("scale is generated from {}.{}", name, step),
+                        });
+                    }
+
+                    if let Some(state) = state_name(&key) {
+                        push_unique(&mut result, state, value, &name);
+                    } else if let Some(brand) = take_brand(&mut free_brands, &key) {
+                        push_brand(&mut result, brand, value, name);
+                    } else {
+                        result.unmapped.push(UnmappedToken { path: name, reason: "all brand-a..brand-f slots are taken".to_string() });
+                    }
+                }
+            }
+        }
+
+        // Fonts: sans -> font-a, serif -> font-b, mono -> font-c, others fill font-d..font-f
+        let mut free_fonts = vec!["font-d", "font-e", "font-f"].into_iter();
+        let mut mapped_fonts = Vec::new();
+        for (name, family) in source.fonts {
+            let key = name.to_lowercase();
+            let slot = match key.as_str() {
+                "sans" | "body" | "base" | "system" => Some("font-a"),
+                "serif" | "heading" | "display" => Some("font-b"),
+                "mono" | "code" | "monospace" => Some("font-c"),
+                _ => None,
+            };
+            let slot = slot
+                .filter(|s| !result.fonts.iter().any(|(n, _)| n == s))
+                .or_else(|| free_fonts.next());
+
+            let Some(slot) = slot else {
+                result.unmapped.push(UnmappedToken { path: name, reason: "all font-a..font-f slots are taken".to_string() });
+                continue;
+            };
+
+            // Only the weights the source declares for this family
+            let weights: HashMap<String, u16> = source.font_weights.iter()
+                .filter(|(font, _, _)| *font == name)
+                .map(|(_, weight, value)| (weight.clone(), *value))
+                .collect();
+            result.fonts.push((slot.to_string(), FontStack {
+                family: join_font_family(&family),
+                fallback: generic_fallback(&family),
+                weights: if weights.is_empty() { None } else { Some(weights) },
+                ..Default::default()
+            }));
+            mapped_fonts.push(name);
+        }
+        for (font, weight, _) in source.font_weights {
+            if !mapped_fonts.contains(&font) {
+                result.unmapped.push(UnmappedToken {
+                    path: format!("{}.{}", font, weight),
+                    reason: format!("no imported font family '{}'", font),
+                });
+            }
+        }
+
+        // Spacing has no config file - values are matched against the built-in scale
+        for (name, value) in source.spacing {
+            let normalized = match value.trim() {
+                "0px" | "0rem" => "0",
+                other => other,
+            };
+            match SPACING_SCALE.iter().find(|(_, scale)| *scale == normalized) {
+                Some((step, _)) => result.spacing.push((name, step.to_string())),
+                None => result.unmapped.push(UnmappedToken {
+                    path: format!("spacing.{}", name),
+                    reason: format!("{} is not on the spacing scale", value),
+                }),
+            }
+        }
+
+        result
+    }
+
+    pub fn fonts_config(&self) -> FontsConfig {
+        FontsConfig {
+            fonts: self.fonts.iter().map(|(n, f)| (n.clone(), FontStack {
+                family: f.family.clone(),
+                fallback: f.fallback.clone(),
+                weights: f.weights.clone(),
+                ..Default::default()
+            })).collect(),
+            loading: Default::default(),
+            languages: Default::default(),
+        }
+    }
+
+    /// `reedstyle.colors.yaml` content in source order
+    pub fn colors_yaml(&self) -> Result<String> {
+        let mut root = Mapping::new();
+        root.insert("colors".into(), ordered_mapping(&self.colors));
+        root.insert("semantic".into(), ordered_mapping(&self.semantic));
+        Ok(serde_yaml::to_string(&root)?)
+    }
+
+    /// `reedstyle.fonts.yaml` content in slot order
+    pub fn fonts_yaml(&self) -> Result<String> {
+        let mut fonts = self.fonts.iter().collect::<Vec<_>>();
+        fonts.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut stacks = Mapping::new();
+        for (name, font) in fonts {
+            let mut stack = Mapping::new();
+            stack.insert("family".into(), font.family.clone().into());
+            stack.insert("fallback".into(), font.fallback.clone().into());
+            if let Some(weights) = &font.weights {
+                let mut sorted: Vec<_> = weights.iter().collect();
+                sorted.sort_by_key(|(_, w)| **w);
+                let mut map = Mapping::new();
+                for (name, weight) in sorted {
+                    map.insert(name.clone().into(), Value::from(*weight));
+                }
+                stack.insert("weights".into(), Value::Mapping(map));
+            }
+            stacks.insert(name.clone().into(), Value::Mapping(stack));
+        }
+
+        let mut root = Mapping::new();
+        root.insert("fonts".into(), Value::Mapping(stacks));
+        Ok(serde_yaml::to_string(&root)?)
+    }
+}
+
+fn ordered_mapping(entries: &[(String, String)]) -> Value {
+    let mut map = Mapping::new();
+    for (key, value) in entries {
+        map.insert(key.clone().into(), value.clone().into());
+    }
+    Value::Mapping(map)
+}
+
+fn state_name(key: &str) -> Option<&'static str> {
+    STATE_NAMES.iter()
+        .find(|(name, state)| *name == key || *state == key)
+        .map(|(_, state)| *state)
+}
+
+/// `base-0` .. `base-1000` are kept as-is when the source already uses them
+fn is_base_name(key: &str) -> bool {
+    key.strip_prefix("base-").is_some_and(is_base_step)
+}
+
+fn is_base_step(step: &str) -> bool {
+    step.parse::<u16>().is_ok_and(|step| BASE_STEPS.contains(&step))
+}
+
+/// Claim a brand slot, preferring one the source already names (round trips)
+fn take_brand(free: &mut Vec<&'static str>, key: &str) -> Option<&'static str> {
+    let index = free.iter().position(|slot| *slot == key).unwrap_or(0);
+    (!free.is_empty()).then(|| free.remove(index))
+}
+
+fn push_brand(result: &mut ImportResult, brand: &str, value: String, source: String) {
+    result.colors.push((brand.to_string(), value));
+    if source != brand {
+        result.semantic.push((source, brand.to_string()));
+    }
+}
+
+fn push_unique(result: &mut ImportResult, name: &str, value: String, source: &str) {
+    if result.colors.iter().any(|(n, _)| n == name) {
+        result.unmapped.push(UnmappedToken { path: source.to_string(), reason: format!("{} is already defined", name) });
+    } else {
+        result.colors.push((name.to_string(), value));
+    }
+}
+
+/// Pick the step a generated scale should be built from (500/DEFAULT/base, else the middle step)
+fn ramp_base(steps: &[(String, String)]) -> Option<(String, String)> {
+    let colors: Vec<(String, String)> = steps.iter()
+        .filter_map(|(step, value)| normalize_color(value).ok().map(|v| (step.clone(), v)))
+        .collect();
+
+    colors.iter()
+        .find(|(step, _)| ["500", "DEFAULT", "base"].contains(&step.as_str()))
+        .or_else(|| colors.get(colors.len() / 2))
+        .cloned()
+}
+
+/// Accept the formats `color::to_oklch` understands; expand short hex.
+/// The build has no alpha, so translucent colors are refused, not flattened
+pub fn normalize_color(value: &str) -> std::result::Result<String, String> {
+    let value = value.trim();
+    let not_a_color = || format!("'{}' is not a color", value);
+    let translucent = || format!("'{}' has an alpha channel", value);
+
+    if let Some(hex) = value.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(not_a_color());
+        }
+        let opaque = |alpha: &str| alpha.eq_ignore_ascii_case("f") || alpha.eq_ignore_ascii_case("ff");
+        return match hex.len() {
+            3 => Ok(format!("#{}", hex.chars().flat_map(|c| [c, c]).collect::<String>())),
+            4 if opaque(&hex[3..]) => normalize_color(&format!("#{}", &hex[..3])),
+            6 => Ok(value.to_string()),
+            8 if opaque(&hex[6..]) => Ok(format!("#{}", &hex[..6])),
+            4 | 8 => Err(translucent()),
+            _ => Err(not_a_color()),
+        };
+    }
+
+    let lower = value.to_lowercase();
+    if !["rgb(", "rgba(", "hsl(", "hsla(", "oklch("].iter().any(|p| lower.starts_with(p)) {
+        return Err(not_a_color());
+    }
+    // rgb(0 0 0 / 0.5), rgba(0, 0, 0, 0.5)
+    let arguments = lower.trim_end_matches(')').split_once('(').map(|(_, a)| a).unwrap_or_default();
+    let alpha = arguments.split_once('/').map(|(_, a)| a)
+        .or_else(|| arguments.split(',').nth(3));
+    match alpha.map(str::trim) {
+        Some("1") | Some("1.0") | Some("100%") | None => Ok(value.to_string()),
+        Some(_) => Err(translucent()),
+    }
+}
+
+fn join_font_family(family: &[String]) -> String {
+    family.iter()
+        .map(|f| {
+            if f.contains(' ') && !f.starts_with('\'') && !f.starts_with('"') {
+                format!("'{}'", f)
+            } else {
+                f.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn generic_fallback(family: &[String]) -> String {
+    const GENERIC: [&str; 5] = ["sans-serif", "serif", "monospace", "cursive", "system-ui"];
+    family.iter()
+        .rev()
+        .find(|f| GENERIC.contains(&f.as_str()))
+        .cloned()
+        .unwrap_or_else(|| "sans-serif".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ColorsConfig;
+
+    fn ramp(steps: &[(&str, &str)]) -> SourceColor {
+        SourceColor::Ramp(steps.iter().map(|(s, v)| (s.to_string(), v.to_string())).collect())
+    }
+
+    #[test]
+    fn test_ramp_mapping() {
+        let source = SourceTokens {
+            colors: vec![
+                ("white".to_string(), SourceColor::Single("#fff".to_string())),
+                ("slate".to_string(), ramp(&[("50", "#f8fafc"), ("900", "#0f172a")])),
+                ("blue".to_string(), ramp(&[("400", "#60a5fa"), ("500", "#3b82f6"), ("600", "#2563eb")])),
+                ("danger".to_string(), SourceColor::Single("#ef4444".to_string())),
+                ("current".to_string(), SourceColor::Single("currentColor".to_string())),
+            ],
+            ..Default::default()
+        };
+
+        let result = ImportResult::from_source(source);
+        assert!(result.colors.contains(&("base-0".to_string(), "#ffffff".to_string())));
+        assert!(result.colors.contains(&("base-900".to_string(), "#0f172a".to_string())));
+        assert!(result.colors.contains(&("brand-a".to_string(), "#3b82f6".to_string())));
+        assert!(result.colors.contains(&("state-error".to_string(), "#ef4444".to_string())));
+        assert!(result.semantic.contains(&("blue".to_string(), "brand-a".to_string())));
+        assert!(result.unmapped.iter().any(|u| u.path == "blue.400"));
+        assert!(result.unmapped.iter().any(|u| u.path == "current"));
+    }
+
+    #[test]
+    fn test_alpha_and_generated_neutral() {
+        assert_eq!(normalize_color("#abc"), Ok("#aabbcc".to_string()));
+        assert_eq!(normalize_color("#3b82f6ff"), Ok("#3b82f6".to_string()));
+        assert_eq!(normalize_color("rgba(0, 0, 0, 1)"), Ok("rgba(0, 0, 0, 1)".to_string()));
+        for translucent in ["#3b82f680", "#abc8", "rgba(0, 0, 0, 0.5)", "rgb(0 0 0 / 50%)"] {
+            assert_eq!(normalize_color(translucent), Err(format!("'{}' has an alpha channel", translucent)));
+        }
+
+        // Re-importing our own DTCG export
+        let tokens = crate::tokens::DesignTokens::resolve(&ColorsConfig::default(), &FontsConfig::default()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&crate::tokens::dtcg::generate(&tokens).unwrap()).unwrap();
+        let result = ImportResult::from_source(dtcg::read(&json).unwrap());
+        assert!(result.colors.iter().all(|(name, _)| !name.starts_with("base-") || is_base_name(name)));
+        assert!(result.unmapped.iter().any(|u| u.path == "neutral" && u.reason == "neutral-1..neutral-9 is generated"));
+
+        let source = SourceTokens {
+            colors: vec![("gray".to_string(), ramp(&[("100", "#f4f4f5"), ("150", "#ececee"), ("200", "#e4e4e7aa")]))],
+            ..Default::default()
+        };
+        let result = ImportResult::from_source(source);
+        assert_eq!(result.colors, vec![("base-100".to_string(), "#f4f4f5".to_string())]);
+        assert_eq!(result.unmapped.iter().map(|u| u.path.as_str()).collect::<Vec<_>>(), ["gray.150", "gray.200"]);
+    }
+
+    #[test]
+    fn test_font_and_spacing_mapping() {
+        let source = SourceTokens {
+            fonts: vec![
+                ("mono".to_string(), vec!["JetBrains Mono".to_string(), "monospace".to_string()]),
+                ("sans".to_string(), vec!["Inter".to_string(), "sans-serif".to_string()]),
+            ],
+            spacing: vec![("md".to_string(), "1rem".to_string()), ("0.5".to_string(), "0.125rem".to_string())],
+            ..Default::default()
+        };
+
+        let result = ImportResult::from_source(source);
+        let fonts = result.fonts_config();
+        assert_eq!(fonts.fonts["font-c"].family, "'JetBrains Mono', monospace");
+        assert_eq!(fonts.fonts["font-c"].fallback, "monospace");
+        assert_eq!(fonts.fonts["font-a"].family, "Inter, sans-serif");
+        assert_eq!(result.spacing, vec![("md".to_string(), "4".to_string())]);
+        assert!(result.unmapped.iter().any(|u| u.path == "spacing.0.5"));
+    }
+
+    #[test]
+    fn test_weights_per_family() {
+        let source = SourceTokens {
+            fonts: vec![
+                ("sans".to_string(), vec!["Inter".to_string()]),
+                ("serif".to_string(), vec!["Lora".to_string(), "serif".to_string()]),
+                ("mono".to_string(), vec!["JetBrains Mono".to_string()]),
+            ],
+            font_weights: vec![
+                ("sans".to_string(), "regular".to_string(), 400),
+                ("sans".to_string(), "black".to_string(), 900),
+                ("serif".to_string(), "bold".to_string(), 700),
+                ("display".to_string(), "bold".to_string(), 800),
+            ],
+            ..Default::default()
+        };
+
+        let fonts = ImportResult::from_source(source).fonts_config();
+        let weights = |font: &str| fonts.fonts[font].weights.clone().map(|w| w.into_iter().collect::<std::collections::BTreeMap<_, _>>());
+        assert_eq!(weights("font-a"), Some([("black".to_string(), 900), ("regular".to_string(), 400)].into()));
+        assert_eq!(weights("font-b"), Some([("bold".to_string(), 700)].into()));
+        assert_eq!(weights("font-c"), None);
+
+        let result = ImportResult::from_source(SourceTokens {
+            font_weights: vec![("display".to_string(), "bold".to_string(), 800)],
+            ..Default::default()
+        });
+        assert_eq!(result.unmapped, vec![UnmappedToken { path: "display.bold".to_string(), reason: "no imported font family 'display'".to_string() }]);
+    }
+}
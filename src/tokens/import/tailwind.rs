@@ -0,0 +1,135 @@
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+use super::{SourceColor, SourceTokens};
+use crate::tokens::split_font_family;
+
+/// Theme keys the importer understands
+const IMPORTED_KEYS: [&str; 4] = ["colors", "fontFamily", "fontWeight", "spacing"];
+
+/// Read a Tailwind theme exported as JSON (`{ "theme": {...} }` or the theme object itself)
+pub fn read(json: &Value) -> Result<SourceTokens> {
+    let theme = match json.get("theme") {
+        Some(Value::Object(theme)) => theme,
+        Some(_) => bail!("Tailwind 'theme' must be an object"),
+        None => match json {
+            Value::Object(theme) => theme,
+            _ => bail!("Tailwind theme file must contain a JSON object"),
+        },
+    };
+
+    let mut source = SourceTokens::default();
+    read_theme(theme, "theme", &mut source);
+
+    // theme.extend adds to the defaults; for an import both are simply tokens
+    if let Some(Value::Object(extend)) = theme.get("extend") {
+        read_theme(extend, "theme.extend", &mut source);
+    }
+
+    Ok(source)
+}
+
+fn read_theme(theme: &Map<String, Value>, prefix: &str, source: &mut SourceTokens) {
+    for (key, value) in theme {
+        if key == "extend" {
+            continue;
+        }
+        if !IMPORTED_KEYS.contains(&key.as_str()) {
+            source.skipped.push((format!("{}.{}", prefix, key), "theme key is not imported".to_string()));
+            continue;
+        }
+
+        let Value::Object(entries) = value else {
+            source.skipped.push((format!("{}.{}", prefix, key), "expected an object".to_string()));
+            continue;
+        };
+
+        for (name, entry) in entries {
+            let path = format!("{}.{}.{}", prefix, key, name);
+            match key.as_str() {
+                "colors" => read_color(name, entry, &path, source),
+                "fontFamily" => match font_family(entry) {
+                    Some(family) => source.fonts.push((name.clone(), family)),
+                    None => source.skipped.push((path, "unsupported fontFamily value".to_string())),
+                },
+                // Tailwind weights are not tied to a family
+                "fontWeight" => source.skipped.push((path, "font weights are imported per family".to_string())),
+                "spacing" => match entry.as_str() {
+                    Some(value) => source.spacing.push((name.clone(), value.to_string())),
+                    None => source.skipped.push((path, "unsupported spacing value".to_string())),
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+fn read_color(name: &str, entry: &Value, path: &str, source: &mut SourceTokens) {
+    match entry {
+        Value::String(value) => source.colors.push((name.to_string(), SourceColor::Single(value.clone()))),
+        Value::Object(steps) => {
+            let mut ramp = Vec::new();
+            for (step, value) in steps {
+                match value.as_str() {
+                    Some(value) => ramp.push((step.clone(), value.to_string())),
+                    None => source.skipped.push((format!("{}.{}", path, step), "nested color groups are not imported".to_string())),
+                }
+            }
+            source.colors.push((name.to_string(), SourceColor::Ramp(ramp)));
+        }
+        _ => source.skipped.push((path.to_string(), "unsupported color value".to_string())),
+    }
+}
+
+/// `["Inter", "sans-serif"]`, `"Inter, sans-serif"` or the tuple form `[["Inter", ...], { ... }]`
+fn font_family(entry: &Value) -> Option<Vec<String>> {
+    match entry {
+        Value::String(s) => Some(split_font_family(s)),
+        Value::Array(items) => match items.first() {
+            Some(Value::Array(_)) => font_family(&items[0]),
+            _ => Some(items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect()),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ImportResult;
+    use serde_json::json;
+
+    #[test]
+    fn test_read_tailwind_theme() {
+        let json = json!({
+            "theme": {
+                "colors": {
+                    "transparent": "transparent",
+                    "white": "#fff",
+                    "gray": { "100": "#f3f4f6", "900": "#111827" },
+                    "indigo": { "400": "#818cf8", "500": "#6366f1", "600": "#4f46e5" }
+                },
+                "fontFamily": {
+                    "sans": [["Inter", "system-ui", "sans-serif"], { "fontFeatureSettings": "'cv11'" }]
+                },
+                "fontWeight": { "bold": "700" },
+                "screens": { "md": "768px" },
+                "extend": {
+                    "colors": { "success": "#22c55e" },
+                    "spacing": { "4": "1rem", "128": "32rem" }
+                }
+            }
+        });
+
+        let result = ImportResult::from_source(read(&json).unwrap());
+        assert!(result.colors.contains(&("base-0".to_string(), "#ffffff".to_string())));
+        assert!(result.colors.contains(&("base-100".to_string(), "#f3f4f6".to_string())));
+        assert!(result.colors.contains(&("brand-a".to_string(), "#6366f1".to_string())));
+        assert!(result.colors.contains(&("state-success".to_string(), "#22c55e".to_string())));
+        assert_eq!(result.fonts[0].1.family, "Inter, system-ui, sans-serif");
+        assert_eq!(result.spacing, vec![("4".to_string(), "4".to_string())]);
+        assert_eq!(result.fonts[0].1.weights, None);
+        assert!(result.unmapped.iter().any(|u| u.path == "theme.fontWeight.bold"));
+        assert!(result.unmapped.iter().any(|u| u.path == "theme.screens"));
+        assert!(result.unmapped.iter().any(|u| u.path == "spacing.128"));
+    }
+}
@@ -0,0 +1,310 @@
+pub mod dtcg;
+pub mod import;
+pub mod scss;
+pub mod tokens_studio;
+pub mod typescript;
+
+use anyhow::Result;
+use serde::Serialize;
+use crate::config::{ColorsConfig, FontsConfig, TokenFormat};
+use crate::css::breakpoints::BREAKPOINTS;
+use crate::css::namespaces::face::{RADIUS_SCALE, SHADOW_SCALE};
+use crate::css::namespaces::r#box::SPACING_SCALE;
+
+/// The resolved design system - the same values `css::generate` writes
+/// as custom properties, collected for export to other tools
+#[derive(Debug, Clone)]
+pub struct DesignTokens {
+    pub colors: Vec<ColorToken>,
+    pub semantic: Vec<SemanticToken>,
+    pub fonts: Vec<FontToken>,
+    pub spacing: Vec<(String, String)>,
+    pub radius: Vec<(String, String)>,
+    pub shadow: Vec<(String, String)>,
+    pub breakpoints: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorToken {
+    pub name: String,
+    /// Base color in OKLCH (None for the neutral scale, which has no base)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Generated 1-9 scale for brand, state and neutral colors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SemanticToken {
+    /// Points at another color token (e.g. primary -> brand-a)
+    Alias { name: String, target: String },
+    /// Carries its own color value
+    Value { name: String, value: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct FontToken {
+    pub name: String,
+    pub family: Vec<String>,
+    pub fallback: String,
+    pub weights: Vec<(String, u16)>,
+}
+
+/// One layer of a `box-shadow` value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowLayer {
+    pub inset: bool,
+    pub offset_x: String,
+    pub offset_y: String,
+    pub blur: String,
+    pub spread: String,
+    pub color: String,
+}
+
+impl DesignTokens {
+    /// Resolve colors (after OKLCH conversion and scale generation), fonts and scales
+    pub fn resolve(colors: &ColorsConfig, fonts: &FontsConfig) -> Result<Self> {
+        let mut color_tokens = Vec::new();
+        let resolved = crate::color::resolve_colors(colors)?;
+
+        let mut names: Vec<&String> = colors.colors.keys().collect();
+        names.sort();
+
+        for name in names {
+            let base = resolved[name].clone();
+            let scale = if name.starts_with("brand-") || name.starts_with("state-") {
+                let scale = crate::color::generate_color_scale(&base)?;
+                Some(scale.steps().iter().map(|s| s.to_string()).collect())
+            } else {
+                None
+            };
+            color_tokens.push(ColorToken { name: name.clone(), base: Some(base), scale });
+        }
+
+        // Neutral scale is always generated
+        let neutral = crate::color::generate_neutral_scale();
+        color_tokens.push(ColorToken {
+            name: "neutral".to_string(),
+            base: None,
+            scale: Some(neutral.steps().iter().map(|s| s.to_string()).collect()),
+        });
+
+        // Semantic names either alias a color or define their own value
+        let mut semantic_names: Vec<&String> = colors.semantic.keys().collect();
+        semantic_names.sort();
+
+        let mut semantic = Vec::new();
+        for name in semantic_names {
+            let value = &colors.semantic[name];
+            if colors.colors.contains_key(value) {
+                semantic.push(SemanticToken::Alias { name: name.clone(), target: value.clone() });
+            } else {
+                semantic.push(SemanticToken::Value {
+                    name: name.clone(),
+                    value: crate::color::to_oklch(value)?,
+                });
+            }
+        }
+
+        let mut font_names: Vec<&String> = fonts.fonts.keys().collect();
+        font_names.sort();
+
+        let mut font_tokens = Vec::new();
+        for name in font_names {
+            let font = &fonts.fonts[name];
+            let mut weights: Vec<(String, u16)> = font.weights.as_ref()
+                .map(|w| w.iter().map(|(k, v)| (k.clone(), *v)).collect())
+                .unwrap_or_default();
+            weights.sort_by_key(|(_, weight)| *weight);
+
+            font_tokens.push(FontToken {
+                name: name.clone(),
+                family: split_font_family(&font.family),
+                fallback: font.fallback.clone(),
+                weights,
+            });
+        }
+
+        Ok(DesignTokens {
+            colors: color_tokens,
+            semantic,
+            fonts: font_tokens,
+            spacing: to_owned_scale(SPACING_SCALE),
+            radius: to_owned_scale(RADIUS_SCALE),
+            shadow: to_owned_scale(SHADOW_SCALE),
+            breakpoints: BREAKPOINTS.iter()
+                .filter_map(|(suffix, width)| {
+                    width.map(|w| (suffix.trim_start_matches('-').to_string(), w.to_string()))
+                })
+                .collect(),
+        })
+    }
+
+    /// Render the tokens in the requested format
+    pub fn render(&self, format: TokenFormat) -> Result<String> {
+        match format {
+            TokenFormat::Dtcg => dtcg::generate(self),
+            TokenFormat::Scss => scss::generate(self),
+            TokenFormat::Typescript => typescript::generate(self),
+            TokenFormat::TokensStudio => tokens_studio::generate(self),
+        }
+    }
+}
+
+/// Output file name for each token format
+pub fn file_name(format: TokenFormat) -> &'static str {
+    match format {
+        TokenFormat::Dtcg => "reedstyle.tokens.json",
+        TokenFormat::Scss => "_reedstyle.tokens.scss",
+        TokenFormat::Typescript => "reedstyle.tokens.ts",
+        TokenFormat::TokensStudio => "reedstyle.tokens-studio.json",
+    }
+}
+
+fn to_owned_scale(scale: &[(&str, &str)]) -> Vec<(String, String)> {
+    scale.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+/// Split a CSS font-family list into its individual families
+/// "'Inter', -apple-system, sans-serif" -> ["Inter", "-apple-system", "sans-serif"]
+pub fn split_font_family(family: &str) -> Vec<String> {
+    family.split(',')
+        .map(|f| f.trim().trim_matches('\'').trim_matches('"').to_string())
+        .filter(|f| !f.is_empty())
+        .collect()
+}
+
+/// Split a CSS dimension into number and unit ("0.25rem" -> (0.25, "rem"))
+/// Unitless zero is reported in px
+pub fn split_dimension(value: &str) -> Option<(f64, String)> {
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    let number = value[..unit_start].parse::<f64>().ok()?;
+    let unit = &value[unit_start..];
+
+    if unit.is_empty() {
+        if number == 0.0 { Some((0.0, "px".to_string())) } else { None }
+    } else {
+        Some((number, unit.to_string()))
+    }
+}
+
+/// Parse a `box-shadow` value into its layers ("none" yields no layers)
+pub fn parse_shadow(value: &str) -> Vec<ShadowLayer> {
+    let mut layers = Vec::new();
+    if value.trim() == "none" {
+        return layers;
+    }
+
+    for layer in split_top_level(value, ',') {
+        let mut inset = false;
+        let mut lengths = Vec::new();
+        let mut color_parts = Vec::new();
+
+        for part in split_top_level(&layer, ' ') {
+            if part == "inset" {
+                inset = true;
+            } else if color_parts.is_empty() && split_dimension(&part).is_some() {
+                lengths.push(part);
+            } else {
+                color_parts.push(part);
+            }
+        }
+
+        if lengths.len() < 2 {
+            continue;
+        }
+
+        let zero = "0".to_string();
+        layers.push(ShadowLayer {
+            inset,
+            offset_x: lengths[0].clone(),
+            offset_y: lengths[1].clone(),
+            blur: lengths.get(2).unwrap_or(&zero).clone(),
+            spread: lengths.get(3).unwrap_or(&zero).clone(),
+            color: if color_parts.is_empty() { "currentColor".to_string() } else { color_parts.join(" ") },
+        });
+    }
+
+    layers
+}
+
+/// Split on a separator, ignoring separators inside parentheses
+fn split_top_level(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if c == separator && depth == 0 {
+            if !current.trim().is_empty() {
+                parts.push(current.trim().to_string());
+            }
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_font_family() {
+        assert_eq!(
+            split_font_family("'Inter', -apple-system, \"Segoe UI\", sans-serif"),
+            vec!["Inter", "-apple-system", "Segoe UI", "sans-serif"]
+        );
+    }
+
+    #[test]
+    fn test_split_dimension() {
+        assert_eq!(split_dimension("0.25rem"), Some((0.25, "rem".to_string())));
+        assert_eq!(split_dimension("0"), Some((0.0, "px".to_string())));
+        assert_eq!(split_dimension("auto"), None);
+    }
+
+    #[test]
+    fn test_parse_shadow() {
+        let layers = parse_shadow("0 4px 6px -1px rgb(0 0 0 / 0.1), inset 0 2px 4px 0 rgb(0 0 0 / 0.05)");
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].offset_y, "4px");
+        assert_eq!(layers[0].spread, "-1px");
+        assert_eq!(layers[0].color, "rgb(0 0 0 / 0.1)");
+        assert!(layers[1].inset);
+        assert!(parse_shadow("none").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_defaults() {
+        let tokens = DesignTokens::resolve(&ColorsConfig::default(), &FontsConfig::default()).unwrap();
+
+        let brand_a = tokens.colors.iter().find(|c| c.name == "brand-a").unwrap();
+        assert!(brand_a.base.as_ref().unwrap().starts_with("oklch("));
+        assert_eq!(brand_a.scale.as_ref().unwrap().len(), 9);
+
+        let base = tokens.colors.iter().find(|c| c.name == "base-500").unwrap();
+        assert!(base.scale.is_none());
+
+        assert!(tokens.semantic.iter().any(|s| matches!(
+            s, SemanticToken::Alias { name, target } if name == "primary" && target == "brand-a"
+        )));
+        assert_eq!(tokens.breakpoints[0], ("phone".to_string(), "320px".to_string()));
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use super::{DesignTokens, SemanticToken};
+
+/// Generate SCSS variables and maps
+pub fn generate(tokens: &DesignTokens) -> Result<String> {
+    let mut scss = String::new();
+
+    scss.push_str(&format!("// ReedSTYLE design tokens v{} - generated, do not edit\n\n", crate::builder::VERSION));
+
+    // Colors: one variable per value plus a map per color
+    scss.push_str("// Colors\n");
+    for color in &tokens.colors {
+        if let Some(base) = &color.base {
+            scss.push_str(&format!("$rs-color-{}: {};\n", color.name, base));
+        }
+        if let Some(scale) = &color.scale {
+            for (i, step) in scale.iter().enumerate() {
+                scss.push_str(&format!("$rs-color-{}-{}: {};\n", color.name, i + 1, step));
+            }
+        }
+    }
+
+    scss.push_str("\n$rs-colors: (\n");
+    for color in &tokens.colors {
+        let mut entries = Vec::new();
+        if color.base.is_some() {
+            entries.push(format!("\"base\": $rs-color-{}", color.name));
+        }
+        if let Some(scale) = &color.scale {
+            for i in 1..=scale.len() {
+                entries.push(format!("\"{}\": $rs-color-{}-{}", i, color.name, i));
+            }
+        }
+        scss.push_str(&format!("  \"{}\": ({}),\n", color.name, entries.join(", ")));
+    }
+    scss.push_str(");\n\n");
+
+    // Semantic colors reference palette variables where possible
+    scss.push_str("// Semantic colors\n");
+    for token in &tokens.semantic {
+        match token {
+            SemanticToken::Alias { name, target } => {
+                scss.push_str(&format!("$rs-{}: $rs-color-{};\n", name, target));
+            }
+            SemanticToken::Value { name, value } => {
+                scss.push_str(&format!("$rs-{}: {};\n", name, value));
+            }
+        }
+    }
+    scss.push('\n');
+
+    // Fonts
+    scss.push_str("// Fonts\n");
+    for font in &tokens.fonts {
+        scss.push_str(&format!("$rs-{}: {};\n", font.name, scss_font_list(&font.family)));
+    }
+    scss.push_str("\n$rs-fonts: (\n");
+    for font in &tokens.fonts {
+        scss.push_str(&format!("  \"{}\": ({}),\n", font.name, scss_font_list(&font.family)));
+    }
+    scss.push_str(");\n\n");
+
+    scss.push_str("$rs-font-weights: (\n");
+    for font in tokens.fonts.iter().filter(|f| !f.weights.is_empty()) {
+        let weights: Vec<String> = font.weights.iter()
+            .map(|(name, weight)| format!("\"{}\": {}", name, weight))
+            .collect();
+        scss.push_str(&format!("  \"{}\": ({}),\n", font.name, weights.join(", ")));
+    }
+    scss.push_str(");\n\n");
+
+    // Scales
+    scss.push_str(&scss_map("spacing", &tokens.spacing));
+    scss.push_str(&scss_map("radius", &tokens.radius));
+    scss.push_str(&scss_map("shadow", &tokens.shadow));
+    scss.push_str(&scss_map("breakpoints", &tokens.breakpoints));
+
+    Ok(scss)
+}
+
+fn scss_map(name: &str, scale: &[(String, String)]) -> String {
+    let mut scss = format!("$rs-{}: (\n", name);
+    for (key, value) in scale {
+        // Parentheses keep comma-separated values (layered shadows) as one entry
+        if value.contains(',') {
+            scss.push_str(&format!("  \"{}\": ({}),\n", key, value));
+        } else {
+            scss.push_str(&format!("  \"{}\": {},\n", key, value));
+        }
+    }
+    scss.push_str(");\n\n");
+    scss
+}
+
+/// Quote family names that are not CSS keywords or single identifiers
+/// (`-apple-system`, `BlinkMacSystemFont` and `sans-serif` stay bare)
+fn scss_font_list(family: &[String]) -> String {
+    family.iter()
+        .map(|f| if is_identifier(f) { f.clone() } else { format!("\"{}\"", f) })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn is_identifier(name: &str) -> bool {
+    let start = name.trim_start_matches('-');
+    start.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ColorsConfig, FontsConfig};
+
+    #[test]
+    fn test_scss_output() {
+        let tokens = DesignTokens::resolve(&ColorsConfig::default(), &FontsConfig::default()).unwrap();
+        let scss = generate(&tokens).unwrap();
+
+        assert!(scss.contains("$rs-color-brand-a-5: oklch("));
+        assert!(scss.contains("  \"brand-a\": (\"base\": $rs-color-brand-a, \"1\": $rs-color-brand-a-1,"));
+        assert!(scss.contains("$rs-primary: $rs-color-brand-a;\n"));
+        assert!(scss.contains("$rs-font-a: system-ui, -apple-system, \"Segoe UI\", Roboto, \"Helvetica Neue\", Arial, sans-serif;\n"));
+        assert!(scss.contains("  \"font-a\": (\"thin\": 100, \"light\": 300,"));
+        assert!(scss.contains("  \"1\": 0.25rem,\n"));
+        // Layered shadows stay one map entry
+        assert!(scss.contains("  \"md\": (0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)),\n"));
+
+        let system = ["BlinkMacSystemFont", "-apple-system", "Noto Sans", "3Dumb"].map(String::from);
+        assert_eq!(scss_font_list(&system), "BlinkMacSystemFont, -apple-system, \"Noto Sans\", \"3Dumb\"");
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use super::{parse_shadow, DesignTokens, SemanticToken};
+
+/// Generate Tokens Studio for Figma JSON (single "reedstyle" token set)
+pub fn generate(tokens: &DesignTokens) -> Result<String> {
+    let mut set = Map::new();
+
+    let mut colors = Map::new();
+    for color in &tokens.colors {
+        let mut group = Map::new();
+        if let Some(base) = &color.base {
+            group.insert("base".to_string(), token(json!(base), "color"));
+        }
+        if let Some(scale) = &color.scale {
+            for (i, step) in scale.iter().enumerate() {
+                group.insert((i + 1).to_string(), token(json!(step), "color"));
+            }
+        }
+        colors.insert(color.name.clone(), Value::Object(group));
+    }
+
+    // Tokens Studio references use the same {path} syntax
+    let mut semantic = Map::new();
+    for entry in &tokens.semantic {
+        match entry {
+            SemanticToken::Alias { name, target } => {
+                semantic.insert(name.clone(), token(json!(format!("{{color.{}.base}}", target)), "color"));
+            }
+            SemanticToken::Value { name, value } => {
+                semantic.insert(name.clone(), token(json!(value), "color"));
+            }
+        }
+    }
+    colors.insert("semantic".to_string(), Value::Object(semantic));
+    set.insert("color".to_string(), Value::Object(colors));
+
+    let mut families = Map::new();
+    let mut weights = Map::new();
+    for font in &tokens.fonts {
+        families.insert(font.name.clone(), token(json!(font.family.join(", ")), "fontFamilies"));
+        if !font.weights.is_empty() {
+            let group: Map<String, Value> = font.weights.iter()
+                .map(|(name, weight)| (name.clone(), token(json!(weight.to_string()), "fontWeights")))
+                .collect();
+            weights.insert(font.name.clone(), Value::Object(group));
+        }
+    }
+    set.insert("fontFamilies".to_string(), Value::Object(families));
+    set.insert("fontWeights".to_string(), Value::Object(weights));
+
+    set.insert("spacing".to_string(), scale_group(&tokens.spacing, "spacing"));
+    set.insert("borderRadius".to_string(), scale_group(&tokens.radius, "borderRadius"));
+    set.insert("breakpoint".to_string(), scale_group(&tokens.breakpoints, "dimension"));
+
+    let mut shadows = Map::new();
+    for (name, value) in &tokens.shadow {
+        let layers: Vec<Value> = parse_shadow(value)
+            .into_iter()
+            .map(|layer| json!({
+                "x": layer.offset_x,
+                "y": layer.offset_y,
+                "blur": layer.blur,
+                "spread": layer.spread,
+                "color": layer.color,
+                "type": if layer.inset { "innerShadow" } else { "dropShadow" },
+            }))
+            .collect();
+
+        match layers.len() {
+            0 => continue,
+            1 => shadows.insert(name.clone(), token(layers[0].clone(), "boxShadow")),
+            _ => shadows.insert(name.clone(), token(Value::Array(layers), "boxShadow")),
+        };
+    }
+    set.insert("boxShadow".to_string(), Value::Object(shadows));
+
+    let output = json!({
+        "reedstyle": Value::Object(set),
+        "$themes": [],
+        "$metadata": { "tokenSetOrder": ["reedstyle"] },
+    });
+
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+fn token(value: Value, kind: &str) -> Value {
+    json!({ "value": value, "type": kind })
+}
+
+fn scale_group(scale: &[(String, String)], kind: &str) -> Value {
+    Value::Object(scale.iter().map(|(k, v)| (k.clone(), token(json!(v), kind))).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ColorsConfig, FontsConfig};
+
+    #[test]
+    fn test_tokens_studio_structure() {
+        let tokens = DesignTokens::resolve(&ColorsConfig::default(), &FontsConfig::default()).unwrap();
+        let output: Value = serde_json::from_str(&generate(&tokens).unwrap()).unwrap();
+        let set = &output["reedstyle"];
+
+        assert_eq!(output["$metadata"]["tokenSetOrder"], json!(["reedstyle"]));
+        assert_eq!(set["color"]["semantic"]["primary"], json!({ "value": "{color.brand-a.base}", "type": "color" }));
+        assert_eq!(set["color"]["brand-a"]["5"]["type"], "color");
+        assert_eq!(set["fontWeights"]["font-a"]["bold"], json!({ "value": "700", "type": "fontWeights" }));
+        assert_eq!(set["spacing"]["1"], json!({ "value": "0.25rem", "type": "spacing" }));
+
+        // "none" has no layers, one layer is an object, several are an array
+        assert!(set["boxShadow"].get("none").is_none());
+        assert_eq!(set["boxShadow"]["sm"]["value"], json!({
+            "x": "0", "y": "1px", "blur": "2px", "spread": "0", "color": "rgb(0 0 0 / 0.05)", "type": "dropShadow",
+        }));
+        assert_eq!(set["boxShadow"]["md"]["value"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use super::{DesignTokens, SemanticToken};
+
+/// Generate a TypeScript module exporting the tokens as `const` objects
+pub fn generate(tokens: &DesignTokens) -> Result<String> {
+    let mut ts = String::new();
+
+    ts.push_str(&format!("// ReedSTYLE design tokens v{} - generated, do not edit\n\n", crate::builder::VERSION));
+
+    let mut colors = Map::new();
+    for color in &tokens.colors {
+        let mut entry = Map::new();
+        if let Some(base) = &color.base {
+            entry.insert("base".to_string(), json!(base));
+        }
+        if let Some(scale) = &color.scale {
+            for (i, step) in scale.iter().enumerate() {
+                entry.insert((i + 1).to_string(), json!(step));
+            }
+        }
+        colors.insert(color.name.clone(), Value::Object(entry));
+    }
+    ts.push_str(&export_const("colors", &Value::Object(colors))?);
+
+    // Semantic aliases resolve to the palette base value
+    let mut semantic = Map::new();
+    for token in &tokens.semantic {
+        match token {
+            SemanticToken::Alias { name, target } => {
+                let value = tokens.colors.iter()
+                    .find(|c| &c.name == target)
+                    .and_then(|c| c.base.clone())
+                    .unwrap_or_default();
+                semantic.insert(name.clone(), json!(value));
+            }
+            SemanticToken::Value { name, value } => {
+                semantic.insert(name.clone(), json!(value));
+            }
+        }
+    }
+    ts.push_str(&export_const("semantic", &Value::Object(semantic))?);
+
+    let mut fonts = Map::new();
+    let mut weights = Map::new();
+    for font in &tokens.fonts {
+        fonts.insert(font.name.clone(), json!(font.family));
+        if !font.weights.is_empty() {
+            let entry: Map<String, Value> = font.weights.iter()
+                .map(|(name, weight)| (name.clone(), json!(weight)))
+                .collect();
+            weights.insert(font.name.clone(), Value::Object(entry));
+        }
+    }
+    ts.push_str(&export_const("fonts", &Value::Object(fonts))?);
+    ts.push_str(&export_const("fontWeights", &Value::Object(weights))?);
+
+    ts.push_str(&export_const("spacing", &scale_object(&tokens.spacing))?);
+    ts.push_str(&export_const("radius", &scale_object(&tokens.radius))?);
+    ts.push_str(&export_const("shadow", &scale_object(&tokens.shadow))?);
+    ts.push_str(&export_const("breakpoints", &scale_object(&tokens.breakpoints))?);
+
+    // Key types for typed lookups
+    ts.push_str("export type ColorName = keyof typeof colors;\n");
+    ts.push_str("export type SemanticColor = keyof typeof semantic;\n");
+    ts.push_str("export type FontName = keyof typeof fonts;\n");
+    ts.push_str("export type Spacing = keyof typeof spacing;\n");
+    ts.push_str("export type Radius = keyof typeof radius;\n");
+    ts.push_str("export type Shadow = keyof typeof shadow;\n");
+    ts.push_str("export type Breakpoint = keyof typeof breakpoints;\n\n");
+
+    ts.push_str("const tokens = { colors, semantic, fonts, fontWeights, spacing, radius, shadow, breakpoints } as const;\n");
+    ts.push_str("export default tokens;\n");
+
+    Ok(ts)
+}
+
+fn export_const(name: &str, value: &Value) -> Result<String> {
+    Ok(format!("export const {} = {} as const;\n\n", name, serde_json::to_string_pretty(value)?))
+}
+
+fn scale_object(scale: &[(String, String)]) -> Value {
+    Value::Object(scale.iter().map(|(k, v)| (k.clone(), json!(v))).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ColorsConfig, FontsConfig};
+
+    #[test]
+    fn test_typescript_output() {
+        let tokens = DesignTokens::resolve(&ColorsConfig::default(), &FontsConfig::default()).unwrap();
+        let ts = generate(&tokens).unwrap();
+        let brand = tokens.colors.iter().find(|c| c.name == "brand-a").unwrap();
+
+        assert!(ts.contains(&format!("  \"primary\": \"{}\",\n", brand.base.as_deref().unwrap())));
+        assert!(ts.contains("export const fonts = {\n  \"font-a\": [\n    \"system-ui\",\n"));
+        assert!(ts.contains("    \"thin\": 100,\n"));
+        assert!(ts.contains("export const spacing = {\n  \"0\": \"0\",\n  \"1\": \"0.25rem\","));
+        assert!(ts.contains("export type Spacing = keyof typeof spacing;\n"));
+        assert!(ts.ends_with("export default tokens;\n"));
+    }
+}
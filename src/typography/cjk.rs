@@ -0,0 +1,155 @@
+use super::rules::{self, TypographyRules, ELLIPSIS, EMDASH};
+
+// Unicode constants for CJK typography
+const LCORNER: &str = "\u{300C}";     // Left corner bracket 「
+const RCORNER: &str = "\u{300D}";     // Right corner bracket 」
+const LWCORNER: &str = "\u{300E}";    // Left white corner bracket 『
+const RWCORNER: &str = "\u{300F}";    // Right white corner bracket 』
+const LDQUO: &str = "\u{201C}";       // Left double quote "
+const RDQUO: &str = "\u{201D}";       // Right double quote "
+const LSQUO: &str = "\u{2018}";       // Left single quote '
+const RSQUO: &str = "\u{2019}";       // Right single quote '
+
+/// Hiragana, katakana, CJK ideographs (with extension A) and Hangul
+/// syllables, as literal ranges so the pattern reads the same in Rust and JS
+macro_rules! cjk {
+    () => { "[\u{3040}-\u{30FF}\u{3400}-\u{4DBF}\u{4E00}-\u{9FFF}\u{AC00}-\u{D7AF}]" };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Japanese,
+    SimplifiedChinese,
+    TraditionalChinese,
+    Korean,
+}
+
+/// Chinese, Japanese and Korean typography rules
+///
+/// Japanese and Chinese are written without spaces between words, so ASCII
+/// punctuation next to CJK characters becomes full-width and quotes pair up
+/// by order rather than by the spaces around them. Korean keeps Western
+/// punctuation. With `spacing`, a thin space separates CJK characters from
+/// Latin letters and digits.
+pub struct CjkRules {
+    pub script: Script,
+    pub spacing: bool,
+}
+
+impl CjkRules {
+    /// Built-in CJK language tags
+    pub fn languages(spacing: bool) -> Vec<(&'static str, CjkRules)> {
+        vec![
+            ("ja", CjkRules { script: Script::Japanese, spacing }),
+            ("ko", CjkRules { script: Script::Korean, spacing }),
+            ("zh", CjkRules { script: Script::SimplifiedChinese, spacing }),
+            ("zh-Hant", CjkRules { script: Script::TraditionalChinese, spacing }),
+            ("zh-HK", CjkRules { script: Script::TraditionalChinese, spacing }),
+            ("zh-TW", CjkRules { script: Script::TraditionalChinese, spacing }),
+        ]
+    }
+
+    fn full_width_punctuation(&self) -> bool {
+        self.script != Script::Korean
+    }
+}
+
+impl TypographyRules for CjkRules {
+    fn quotes(&self) -> (&str, &str) {
+        match self.script {
+            Script::Japanese | Script::TraditionalChinese => (LCORNER, RCORNER),
+            Script::SimplifiedChinese | Script::Korean => (LDQUO, RDQUO),
+        }
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        match self.script {
+            Script::Japanese | Script::TraditionalChinese => (LWCORNER, RWCORNER),
+            Script::SimplifiedChinese | Script::Korean => (LSQUO, RSQUO),
+        }
+    }
+
+    fn word_spaces(&self) -> bool {
+        !self.full_width_punctuation()
+    }
+
+    fn replacements(&self) -> Vec<(&str, &str)> {
+        let mut replacements = Vec::new();
+
+        if self.full_width_punctuation() {
+            let comma = if self.script == Script::Japanese { "$1\u{3001}" } else { "$1\u{FF0C}" };
+            replacements.extend_from_slice(&[
+                // Ellipsis is two characters wide in CJK text
+                (concat!("(", cjk!(), r")\.\.\."), "$1\u{2026}\u{2026}"),
+
+                // ASCII punctuation after CJK becomes full-width and takes no space
+                (concat!("(", cjk!(), ")[ \t]*,[ \t]*"), comma),
+                (concat!("(", cjk!(), ")[ \t]*\\.(?:[ \t]+|$)"), "$1\u{3002}"),
+                (concat!("(", cjk!(), ")[ \t]*![ \t]*"), "$1\u{FF01}"),
+                (concat!("(", cjk!(), ")[ \t]*\\?[ \t]*"), "$1\u{FF1F}"),
+                (concat!("(", cjk!(), ")[ \t]*:[ \t]*"), "$1\u{FF1A}"),
+                (concat!("(", cjk!(), ")[ \t]*;[ \t]*"), "$1\u{FF1B}"),
+                (concat!("\\((", cjk!(), ")"), "\u{FF08}$1"),
+                (concat!("(", cjk!(), ")\\)"), "$1\u{FF09}"),
+
+                // Full-width punctuation already carries its own space
+                ("([\u{3001}\u{3002}\u{FF0C}\u{FF01}\u{FF1F}\u{FF1A}\u{FF1B}])[ \t]+", "$1"),
+            ]);
+        } else {
+            replacements.extend_from_slice(&[("...", ELLIPSIS), ("--", EMDASH), (" - ", " \u{2013} ")]);
+        }
+
+        // Thin space between CJK and Latin, close to the quarter-em of JIS X 4051
+        if self.spacing {
+            replacements.extend_from_slice(&[
+                (concat!("(", cjk!(), ")([A-Za-z0-9])"), "$1\u{2009}$2"),
+                (concat!("([A-Za-z0-9%])(", cjk!(), ")"), "$1\u{2009}$2"),
+            ]);
+        }
+
+        replacements
+    }
+
+    /// Full-width Latin letters and digits become ASCII before the replacements
+    fn apply_replacements(&self, text: &str) -> String {
+        let text: String = text.chars().map(half_width).collect();
+        rules::replace_all(&text, &self.replacements())
+    }
+}
+
+/// ＡＢＣ１２３ → ABC123
+fn half_width(c: char) -> char {
+    match c {
+        '\u{FF10}'..='\u{FF19}' | '\u{FF21}'..='\u{FF3A}' | '\u{FF41}'..='\u{FF5A}' => {
+            char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JA: CjkRules = CjkRules { script: Script::Japanese, spacing: false };
+    const ZH: CjkRules = CjkRules { script: Script::SimplifiedChinese, spacing: true };
+
+    #[test]
+    fn test_quotes_without_spaces() {
+        assert_eq!(JA.apply_quotes("\"はい\"と言った"), "「はい」と言った");
+        assert_eq!(ZH.apply_quotes("他说\"你好\""), "他说\u{201C}你好\u{201D}");
+    }
+
+    #[test]
+    fn test_full_width_punctuation() {
+        assert_eq!(JA.apply_all("はい, そうです. 本当?"), "はい、そうです。本当\u{FF1F}");
+        assert_eq!(ZH.apply_all("你好,世界!"), "你好\u{FF0C}世界\u{FF01}");
+    }
+
+    #[test]
+    fn test_cjk_latin_spacing() {
+        assert_eq!(ZH.apply_all("使用ReedSTYLE构建"), "使用\u{2009}ReedSTYLE\u{2009}构建");
+        assert_eq!(ZH.apply_all("共１０个"), "共\u{2009}10\u{2009}个");
+        assert_eq!(JA.apply_all("使用ReedSTYLE"), "使用ReedSTYLE");
+    }
+}
@@ -0,0 +1,71 @@
+use super::rules::{TypographyRules, ELLIPSIS, NBSP, NNBSP};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
+
+// Unicode constants for Czech typography
+const LDQUO_CS: &str = "\u{201E}";    // Czech opening double quote „
+const RDQUO_CS: &str = "\u{201C}";    // Czech closing double quote "
+const LSQUO_CS: &str = "\u{201A}";    // Czech opening single quote ‚
+const RSQUO_CS: &str = "\u{2018}";    // Czech closing single quote '
+
+/// Czech typography rules following ČSN 01 6910 (one-letter prepositions
+/// and conjunctions are bound to the next word)
+pub struct CzechRules;
+
+impl TypographyRules for CzechRules {
+    fn quotes(&self) -> (&str, &str) {
+        (LDQUO_CS, RDQUO_CS)
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        (LSQUO_CS, RSQUO_CS)
+    }
+
+    fn replacements(&self) -> Vec<(&str, &str)> {
+        vec![
+            // Single-letter prepositions and conjunctions stay with the next word
+            (r"\b([ksvzouaiKSVZOUAI]) ", "$1\u{00A0}"),
+
+            // Three dots to ellipsis
+            ("...", ELLIPSIS),
+
+            // Czech uses a spaced en dash for dashes
+            ("--", " \u{2013} "),
+            (" - ", " \u{2013} "),
+        ]
+    }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        vec!["s. r. o.", "a. s.", "např. ", "tj. ", "č. ", "Ing. ", "Mgr. ", "MUDr. "]
+    }
+
+    fn space_rules(&self) -> Option<SpaceRules> {
+        Some(SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: NNBSP.to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        })
+    }
+
+    /// Decimal comma and 29,99 Kč
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            decimal: ",".to_string(),
+            currency: CurrencyPosition::After,
+            ..NumberFormat::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_czech() {
+        assert_eq!(
+            CzechRules.apply_all("\"Jdu k vodě\" za 1500 Kč"),
+            format!("{}Jdu k{}vodě{} za 1{}500{}Kč", LDQUO_CS, NBSP, RDQUO_CS, NNBSP, NBSP)
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use super::rules::{TypographyRules, NBSP};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
+
+// Unicode constants for Dutch typography
+const LDQUO: &str = "\u{201C}";    // Left double quote "
+const RDQUO: &str = "\u{201D}";    // Right double quote "
+const LSQUO: &str = "\u{2018}";    // Left single quote '
+const RSQUO: &str = "\u{2019}";    // Right single quote '
+
+/// Dutch typography rules (point as thousands separator, currency sign
+/// before the amount)
+pub struct DutchRules;
+
+impl TypographyRules for DutchRules {
+    fn quotes(&self) -> (&str, &str) {
+        (LDQUO, RDQUO)
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        (LSQUO, RSQUO)
+    }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        vec!["dhr. ", "mevr. ", "dr. ", "prof. ", "ir. ", "mr. ", "drs. "]
+    }
+
+    fn space_rules(&self) -> Option<SpaceRules> {
+        Some(SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: ".".to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        })
+    }
+
+    /// Decimal comma and € 29,99
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            decimal: ",".to_string(),
+            currency: CurrencyPosition::Before,
+            ..NumberFormat::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dutch() {
+        assert_eq!(
+            DutchRules.apply_all("dhr. Jansen betaalt € 1500,00"),
+            format!("dhr.{}Jansen betaalt €{}1.500,00", NBSP, NBSP)
+        );
+    }
+}
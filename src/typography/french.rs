@@ -1,179 +1,127 @@
-use anyhow::Result;
+use super::numbers;
+use super::rules::{TypographyRules, ELLIPSIS, NBSP, NNBSP};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
 
 // Unicode constants for French typography
-const LAQUO: &str = "\u{00AB}";    // Left guillemet «
-const RAQUO: &str = "\u{00BB}";    // Right guillemet »
+const LAQUO: &str = "\u{00AB} ";   // Left guillemet « (with space inside)
+const RAQUO: &str = " \u{00BB}";   // Right guillemet » (with space inside)
 const LDQUO: &str = "\u{201C}";    // Left double quote "
 const RDQUO: &str = "\u{201D}";    // Right double quote "
-const NBSP: &str = "\u{00A0}";     // Non-breaking space
-const NNBSP: &str = "\u{202F}";    // Narrow no-break space
-const ELLIPSIS: &str = "\u{2026}"; // Ellipsis …
-const ENDASH: &str = "\u{2013}";   // En dash –
-const EMDASH: &str = "\u{2014}";   // Em dash —
 
 /// French typography rules
 pub struct FrenchRules;
 
-impl FrenchRules {
-    /// Get all replacement patterns for French typography
-    pub fn get_replacements() -> Vec<(&'static str, &'static str)> {
+impl TypographyRules for FrenchRules {
+    fn quotes(&self) -> (&str, &str) {
+        (LAQUO, RAQUO)
+    }
+
+    /// Secondary quotes in French use English style
+    fn single_quotes(&self) -> (&str, &str) {
+        (LDQUO, RDQUO)
+    }
+
+    fn replacements(&self) -> Vec<(&str, &str)> {
         vec![
             // Three dots to ellipsis
-            ("...", "…"),
-            
+            ("...", ELLIPSIS),
+
             // Double hyphen to em dash (French uses spaced em dash)
             ("--", " — "),
-            
+
             // Single hyphen with spaces to en dash
             (" - ", " – "),
-            
-            // Common abbreviations with non-breaking space
-            ("M. ", "M.\u{00A0}"),
-            ("Mme ", "Mme\u{00A0}"),
-            ("Mlle ", "Mlle\u{00A0}"),
-            ("Dr ", "Dr\u{00A0}"),
-            ("Me ", "Me\u{00A0}"),  // Maître
-            
-            // Numbers before certain punctuation need narrow no-break space
-            // This is complex and would need regex in real implementation
         ]
     }
-    
-    /// Apply French quotation marks (guillemets)
-    pub fn apply_quotes(text: &str) -> String {
-        let mut result = text.to_string();
-        
-        // Simple approach: replace paired double quotes with guillemets
-        if result.contains("\"") {
-            let parts: Vec<&str> = result.split("\"").collect();
-            if parts.len() >= 3 {
-                // We have at least one pair of quotes
-                let mut new_result = String::new();
-                for (i, part) in parts.iter().enumerate() {
-                    new_result.push_str(part);
-                    if i < parts.len() - 1 {
-                        // Alternate between opening and closing guillemets
-                        // French guillemets have spaces inside
-                        if i % 2 == 0 {
-                            new_result.push_str(&format!("{} ", LAQUO)); // « with space
-                        } else {
-                            new_result.push_str(&format!(" {}", RAQUO)); // » with space
-                        }
-                    }
-                }
-                result = new_result;
-            }
-        }
-        
-        // Handle single quotes (secondary quotes in French use English style)
-        if result.contains("'") {
-            let parts: Vec<&str> = result.split("'").collect();
-            if parts.len() >= 3 {
-                let mut new_result = String::new();
-                for (i, part) in parts.iter().enumerate() {
-                    new_result.push_str(part);
-                    if i < parts.len() - 1 {
-                        if i % 2 == 0 {
-                            new_result.push_str(LDQUO); // English opening "
-                        } else {
-                            new_result.push_str(RDQUO); // English closing "
-                        }
-                    }
-                }
-                result = new_result;
-            }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        // Maître included
+        vec!["M. ", "Mme ", "Mlle ", "Dr ", "Me "]
+    }
+
+    fn space_rules(&self) -> Option<SpaceRules> {
+        Some(SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: NBSP.to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        })
+    }
+
+    /// Decimal comma (also for decimals written with a point) and 29,99 €
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            decimal: ",".to_string(),
+            currency: CurrencyPosition::After,
+            normalize_decimal: true,
+            ..NumberFormat::default()
         }
-        
-        result
     }
-    
-    /// Format numbers with French spacing rules
-    pub fn format_numbers(text: &str) -> String {
+
+    /// Number and punctuation spacing (decimal comma, narrow space before : ; ! ?)
+    fn format_numbers(&self, text: &str) -> String {
         let mut result = text.to_string();
-        
-        // Format thousands with non-breaking space (French standard)
-        let re = regex::Regex::new(r"\b(\d{1,3})(\d{3})\b").unwrap();
-        result = re.replace_all(&result, "$1\u{00A0}$2").to_string();
-        
-        // Format decimals with comma (French uses comma, not period)
-        let re_decimal = regex::Regex::new(r"(\d+)\.(\d+)").unwrap();
-        result = re_decimal.replace_all(&result, "$1,$2").to_string();
-        
-        // Add narrow no-break space before : ; ! ?
+
+        if let Some(space_rules) = self.space_rules() {
+            result = numbers::format(&result, &space_rules, &self.number_format());
+        }
+
+        // Add narrow no-break space before : ; ! ? (not inside times such as 14:30)
         let re_punctuation = regex::Regex::new(r"(\w)\s*([;:!?])").unwrap();
-        result = re_punctuation.replace_all(&result, "$1\u{202F}$2").to_string();
-        
-        // Currency (Euro sign after with non-breaking space)
-        let re_currency = regex::Regex::new(r"(\d+(?:,\d{2})?)\s*(€|EUR)").unwrap();
-        result = re_currency.replace_all(&result, "$1\u{00A0}$2").to_string();
-        
-        result
-    }
-    
-    /// Apply all French typography rules
-    pub fn apply_all(text: &str) -> String {
-        let mut result = text.to_string();
-        
-        // Apply replacements
-        for (pattern, replacement) in Self::get_replacements() {
-            if pattern.contains('(') {
-                // Regex pattern
-                if let Ok(re) = regex::Regex::new(pattern) {
-                    result = re.replace_all(&result, replacement).to_string();
-                }
+        re_punctuation.replace_all(&result, |caps: &regex::Captures| {
+            let time = &caps[2] == ":"
+                && caps[1].starts_with(|c: char| c.is_ascii_digit())
+                && result[caps.get(0).map_or(0, |m| m.end())..].starts_with(|c: char| c.is_ascii_digit());
+            if time {
+                caps[0].to_string()
             } else {
-                // Simple string replacement
-                result = result.replace(pattern, replacement);
+                format!("{}{}{}", &caps[1], NNBSP, &caps[2])
             }
-        }
-        
-        // Apply number formatting
-        result = Self::format_numbers(&result);
-        
-        // Apply quotes
-        result = Self::apply_quotes(&result);
-        
-        result
+        }).to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_guillemets() {
         assert_eq!(
-            FrenchRules::apply_quotes("\"Bonjour\""),
-            format!("{} Bonjour {}", LAQUO, RAQUO)
+            FrenchRules.apply_quotes("\"Bonjour\""),
+            "\u{00AB} Bonjour \u{00BB}"
         );
         assert_eq!(
-            FrenchRules::apply_quotes("'Salut'"),
+            FrenchRules.apply_quotes("'Salut'"),
             format!("{}Salut{}", LDQUO, RDQUO)
         );
     }
-    
+
     #[test]
     fn test_number_formatting() {
         assert_eq!(
-            FrenchRules::format_numbers("10000"),
+            FrenchRules.format_numbers("10000"),
             format!("10{}000", NBSP)
         );
         assert_eq!(
-            FrenchRules::format_numbers("29.99"),
+            FrenchRules.format_numbers("29.99"),
             "29,99"
         );
         assert_eq!(
-            FrenchRules::format_numbers("Prix : 29,99 €"),
+            FrenchRules.format_numbers("Prix : 29,99 €"),
             format!("Prix{}: 29,99{}€", NNBSP, NBSP)
         );
+        assert_eq!(
+            FrenchRules.format_numbers("Dès 14:30, 2500 € ou €12.50"),
+            format!("Dès 14:30, 2{}500{}€ ou 12,50{}€", NBSP, NBSP, NBSP)
+        );
     }
-    
+
     #[test]
     fn test_punctuation_spacing() {
         let text = "Quoi ? C'est vrai !";
-        let result = FrenchRules::format_numbers(text);
+        let result = FrenchRules.format_numbers(text);
         assert!(result.contains(&format!("{}?", NNBSP)));
         assert!(result.contains(&format!("{}!", NNBSP)));
     }
-}
\ No newline at end of file
+}
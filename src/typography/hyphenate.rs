@@ -0,0 +1,175 @@
+use hyphenation::{Hyphenator as _, Language, Load, Standard};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use super::HyphenateSettings;
+
+/// Soft hyphen, shown only when the browser breaks the line there
+pub const SHY: char = '\u{00AD}';
+
+/// Hyphenation dictionaries for a language tag, most specific first; only
+/// these are embedded (see dictionaries/README.md)
+const LANGUAGES: &[(&str, Language, &[u8])] = &[
+    ("de-ch", Language::GermanSwiss, include_bytes!("dictionaries/de-ch-1901.standard.bincode")),
+    ("de", Language::German1996, include_bytes!("dictionaries/de-1996.standard.bincode")),
+    ("en-gb", Language::EnglishGB, include_bytes!("dictionaries/en-gb.standard.bincode")),
+    ("en", Language::EnglishUS, include_bytes!("dictionaries/en-us.standard.bincode")),
+    ("fr", Language::French, include_bytes!("dictionaries/fr.standard.bincode")),
+    ("it", Language::Italian, include_bytes!("dictionaries/it.standard.bincode")),
+    ("es", Language::Spanish, include_bytes!("dictionaries/es.standard.bincode")),
+    ("nl", Language::Dutch, include_bytes!("dictionaries/nl.standard.bincode")),
+    ("pl", Language::Polish, include_bytes!("dictionaries/pl.standard.bincode")),
+    ("cs", Language::Czech, include_bytes!("dictionaries/cs.standard.bincode")),
+    ("sv", Language::Swedish, include_bytes!("dictionaries/sv.standard.bincode")),
+];
+
+/// Inserts soft hyphens with Knuth-Liang hyphenation, using the TeX
+/// patterns embedded in the binary
+///
+/// Words shorter than `limit_chars.0` are left alone, and no break leaves
+/// fewer than `limit_chars.1` characters before or `limit_chars.2` after
+/// it. Words in `exceptions` break only where the entry has a `-`.
+pub struct Hyphenator {
+    settings: HyphenateSettings,
+    /// Lowercased word -> break positions in characters
+    exceptions: HashMap<String, Vec<usize>>,
+    /// Dictionaries load on first use (None if a dictionary fails to load)
+    dictionaries: RefCell<HashMap<Language, Option<Standard>>>,
+}
+
+impl Hyphenator {
+    pub fn new(settings: &HyphenateSettings) -> Self {
+        let exceptions = settings.exceptions.iter()
+            .map(|entry| {
+                let mut breaks = Vec::new();
+                let mut word = String::new();
+                for c in entry.chars() {
+                    if c == '-' {
+                        breaks.push(word.chars().count());
+                    } else {
+                        word.extend(c.to_lowercase());
+                    }
+                }
+                (word, breaks)
+            })
+            .collect();
+
+        Hyphenator {
+            settings: settings.clone(),
+            exceptions,
+            dictionaries: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Insert soft hyphens into every long enough word of `text`
+    ///
+    /// Languages without a dictionary come back unchanged; so do words that
+    /// already contain a soft hyphen and words in capitals (acronyms).
+    pub fn hyphenate(&self, text: &str, lang: &str) -> String {
+        let language = dictionary_language(lang);
+        let mut result = String::with_capacity(text.len());
+        let mut word = String::new();
+
+        for c in text.chars() {
+            if c.is_alphabetic() || c == SHY {
+                word.push(c);
+                continue;
+            }
+            self.push_word(&mut result, &word, language);
+            word.clear();
+            result.push(c);
+        }
+        self.push_word(&mut result, &word, language);
+
+        result
+    }
+
+    fn push_word(&self, result: &mut String, word: &str, language: Option<(Language, &[u8])>) {
+        let length = word.chars().count();
+        let (min_word, min_before, min_after) = self.settings.limit_chars;
+
+        let skip = length < min_word
+            || word.contains(SHY)
+            || word.chars().all(|c| !c.is_lowercase());
+        let breaks = if skip { Vec::new() } else { self.breaks(word, language) };
+
+        for (i, c) in word.chars().enumerate() {
+            if breaks.contains(&i) && i >= min_before && length - i >= min_after {
+                result.push(SHY);
+            }
+            result.push(c);
+        }
+    }
+
+    /// Break positions in characters
+    fn breaks(&self, word: &str, language: Option<(Language, &[u8])>) -> Vec<usize> {
+        if let Some(breaks) = self.exceptions.get(&word.to_lowercase()) {
+            return breaks.clone();
+        }
+        let Some((language, mut patterns)) = language else {
+            return Vec::new();
+        };
+
+        let mut dictionaries = self.dictionaries.borrow_mut();
+        let dictionary = dictionaries.entry(language)
+            .or_insert_with(|| Standard::from_reader(language, &mut patterns).ok());
+        let Some(dictionary) = dictionary else {
+            return Vec::new();
+        };
+
+        // The dictionary reports byte offsets
+        dictionary.hyphenate(word).breaks.iter()
+            .map(|&byte| word[..byte].chars().count())
+            .collect()
+    }
+}
+
+/// The dictionary for a language tag, falling back subtag by subtag
+fn dictionary_language(tag: &str) -> Option<(Language, &'static [u8])> {
+    let mut tag = tag.to_ascii_lowercase();
+
+    loop {
+        if let Some((_, language, patterns)) = LANGUAGES.iter().find(|(code, _, _)| *code == tag) {
+            return Some((*language, patterns));
+        }
+        tag.truncate(tag.rfind('-')?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hyphenator(exceptions: &[&str]) -> Hyphenator {
+        Hyphenator::new(&HyphenateSettings {
+            exceptions: exceptions.iter().map(|e| e.to_string()).collect(),
+            ..HyphenateSettings::default()
+        })
+    }
+
+    #[test]
+    fn test_hyphenation() {
+        let hyphenator = hyphenator(&[]);
+
+        assert_eq!(hyphenator.hyphenate("Silbentrennung ist gut", "de-AT"), "Sil\u{AD}ben\u{AD}tren\u{AD}nung ist gut");
+        assert_eq!(hyphenator.hyphenate("hyphenation", "en-US"), "hyphen\u{AD}a\u{AD}tion");
+        // Unknown languages, acronyms and short words stay as they are
+        assert_eq!(hyphenator.hyphenate("Silbentrennung", "tlh"), "Silbentrennung");
+        assert_eq!(hyphenator.hyphenate("UNESCO Haus", "de"), "UNESCO Haus");
+    }
+
+    #[test]
+    fn test_embedded_dictionaries() {
+        for &(tag, language, mut patterns) in LANGUAGES {
+            assert!(Standard::from_reader(language, &mut patterns).is_ok(), "{}", tag);
+        }
+    }
+
+    #[test]
+    fn test_limits_and_exceptions() {
+        let hyphenator = hyphenator(&["Reed-STYLE", "ta-ble-cloth"]);
+
+        assert_eq!(hyphenator.hyphenate("ReedSTYLE", "en"), "Reed\u{AD}STYLE");
+        // 3 characters before a break by default (no "ta-")
+        assert_eq!(hyphenator.hyphenate("Tablecloth", "en"), "Table\u{AD}cloth");
+    }
+}
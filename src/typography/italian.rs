@@ -0,0 +1,57 @@
+use super::rules::{TypographyRules, NBSP};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
+
+// Unicode constants for Italian typography
+const LAQUO: &str = "\u{00AB}";    // Left guillemet «
+const RAQUO: &str = "\u{00BB}";    // Right guillemet »
+const LDQUO: &str = "\u{201C}";    // Left double quote "
+const RDQUO: &str = "\u{201D}";    // Right double quote "
+
+/// Italian typography rules (guillemets without inner spaces, point as
+/// thousands separator)
+pub struct ItalianRules;
+
+impl TypographyRules for ItalianRules {
+    fn quotes(&self) -> (&str, &str) {
+        (LAQUO, RAQUO)
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        (LDQUO, RDQUO)
+    }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        vec!["p. es.", "Sig. ", "Sig.ra ", "Dott. ", "Dott.ssa ", "Prof. ", "Avv. ", "Ing. "]
+    }
+
+    fn space_rules(&self) -> Option<SpaceRules> {
+        Some(SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: ".".to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        })
+    }
+
+    /// Decimal comma and 29,99 €
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            decimal: ",".to_string(),
+            currency: CurrencyPosition::After,
+            ..NumberFormat::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_italian() {
+        assert_eq!(
+            ItalianRules.apply_all("\"Costa 12500 € e pesa 3 kg\""),
+            format!("{}Costa 12.500{}€ e pesa 3{}kg{}", LAQUO, NBSP, NBSP, RAQUO)
+        );
+    }
+}
@@ -1,37 +1,138 @@
+pub mod cjk;
 pub mod german;
-pub mod english;  
+pub mod english;
 pub mod french;
+pub mod hyphenate;
+pub mod numbers;
+pub mod italian;
+pub mod spanish;
+pub mod dutch;
+pub mod polish;
+pub mod czech;
+pub mod swedish;
+pub mod quotes;
+pub mod registry;
+pub mod rules;
+pub mod typeset;
+pub mod widows;
 
-use anyhow::Result;
+pub use hyphenate::Hyphenator;
+pub use registry::LanguageRegistry;
+pub use rules::TypographyRules;
+pub use typeset::TypesetOptions;
+
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::css::token_selector;
 
+/// Loaded from `reedstyle.typography.yaml`; missing fields keep their defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TypographyConfig {
     pub default_filter: String,
     pub features: TypographyFeatures,
+    pub cjk: CjkSettings,
+    pub hyphenate: HyphenateSettings,
+    pub widows: WidowSettings,
+    /// Languages added to (or overriding) the built-in `LanguageRegistry`
     pub languages: HashMap<String, LanguageRules>,
 }
 
+/// Site-wide feature switches, emitted as defaults on `:root`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TypographyFeatures {
     pub ligatures: bool,
     pub kerning: bool,
+    /// `auto`, `manual` or `none`
     pub hyphenation: String,
     pub hanging_punctuation: bool,
 }
 
+/// Build-time hyphenation and the `hyphenate-limit-*` defaults on `:root`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HyphenateSettings {
+    /// Insert soft hyphens when typesetting HTML (`reedstyle typeset`)
+    pub soft_hyphens: bool,
+    /// `hyphenate-limit-chars`: word length, characters before and after a break
+    pub limit_chars: (usize, usize, usize),
+    /// `hyphenate-limit-lines`: consecutive hyphenated lines
+    pub limit_lines: u32,
+    /// `hyphenate-limit-last`: none | always | column | page | spread
+    pub limit_last: String,
+    /// Words with their only allowed breaks marked by `-` (`Reed-STYLE`)
+    pub exceptions: Vec<String>,
+}
+
+impl Default for HyphenateSettings {
+    fn default() -> Self {
+        HyphenateSettings {
+            soft_hyphens: false,
+            limit_chars: (6, 3, 2),
+            limit_lines: 2,
+            limit_last: "always".to_string(),
+            exceptions: Vec::new(),
+        }
+    }
+}
+
+/// Line length of `measure:narrow` in characters; joined words never exceed it
+pub const NARROW_MEASURE: usize = 45;
+
+/// Keeps the last words of headings and short paragraphs on one line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WidowSettings {
+    /// Words joined by no-break spaces (0 turns it off)
+    pub words: usize,
+    /// Paragraphs up to this many characters are protected; headings always are
+    pub short_paragraph: usize,
+}
+
+impl Default for WidowSettings {
+    fn default() -> Self {
+        WidowSettings { words: 2, short_paragraph: 200 }
+    }
+}
+
+/// Chinese, Japanese and Korean text
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CjkSettings {
+    /// Thin space between CJK characters and Latin letters or digits
+    pub spacing: bool,
+}
+
+/// Per-language overrides of the site-wide feature switches
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageFeatures {
+    pub ligatures: Option<bool>,
+    pub kerning: Option<bool>,
+    pub hyphenation: Option<String>,
+    pub hanging_punctuation: Option<bool>,
+}
+
+/// A language declared in YAML; omitted fields fall back to the rules of
+/// the parent tag (`de-CH` → `de`) or the defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LanguageRules {
     pub quotes: (String, String),
     pub single_quotes: (String, String),
     #[serde(skip_serializing_if = "Option::is_none")]
     pub replacements: Option<Vec<(String, String)>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abbreviations: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub space_rules: Option<SpaceRules>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numbers: Option<NumberFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<LanguageFeatures>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpaceRules {
     pub before_unit: String,
     pub in_number: String,
@@ -39,144 +140,225 @@ pub struct SpaceRules {
     pub after_abbr: String,
 }
 
+/// How a language writes numbers and amounts (see `numbers::format`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    /// Decimal separator
+    pub decimal: String,
+    /// Integers with fewer digits are not grouped (5 keeps `1234` but groups `12 345`)
+    pub group_from: usize,
+    /// Where currency symbols (€, $, £) go; codes such as CHF stay as written
+    pub currency: CurrencyPosition,
+    /// Decimals written with the other separator (`29.99`) take `decimal`
+    pub normalize_decimal: bool,
+    /// Spaces inside phone numbers become no-break spaces
+    pub bind_phone_numbers: bool,
+    /// Number ranges (`10-20`, `10 - 20`) take an en dash (`10–20`)
+    pub range_dash: bool,
+    /// Regular expressions for numbers to leave as written (postcodes, product codes)
+    pub keep: Vec<String>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal: ".".to_string(),
+            group_from: 4,
+            currency: CurrencyPosition::Keep,
+            normalize_decimal: false,
+            bind_phone_numbers: true,
+            range_dash: false,
+            keep: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CurrencyPosition {
+    /// $5, € 5
+    Before,
+    /// 5 €
+    After,
+    /// Wherever the text has it
+    Keep,
+}
+
 impl Default for TypographyConfig {
     fn default() -> Self {
-        let mut languages = HashMap::new();
-        
-        // German rules (DIN 5008)
-        let mut de = LanguageRules {
-            quotes: ("\u{201E}".to_string(), "\u{201C}".to_string()),  // „ and "
-            single_quotes: ("\u{201A}".to_string(), "\u{2018}".to_string()),  // ‚ and '
-            replacements: Some(vec![
-                (r"(\d+)-(\d+)".to_string(), r"$1\u{2013}$2".to_string()),  // en dash
-            ]),
-            space_rules: Some(SpaceRules {
-                before_unit: "\u{202F}".to_string(),     // Narrow no-break space
-                in_number: "\u{202F}".to_string(),       // Narrow no-break space
-                before_currency: "\u{00A0}".to_string(), // Non-breaking space
-                after_abbr: "\u{00A0}".to_string(),      // Non-breaking space
-            }),
-        };
-        languages.insert("de".to_string(), de);
-        
-        // English US rules
-        let en_us = LanguageRules {
-            quotes: ("\u{201C}".to_string(), "\u{201D}".to_string()),  // " and "
-            single_quotes: ("\u{2018}".to_string(), "\u{2019}".to_string()),  // ' and '
-            replacements: None,
-            space_rules: None,
-        };
-        languages.insert("en-US".to_string(), en_us);
-        
-        // English GB rules
-        let en_gb = LanguageRules {
-            quotes: ("\u{2018}".to_string(), "\u{2019}".to_string()),  // ' and '
-            single_quotes: ("\u{201C}".to_string(), "\u{201D}".to_string()),  // " and "
-            replacements: None,
-            space_rules: None,
-        };
-        languages.insert("en-GB".to_string(), en_gb);
-        
-        // French rules
-        let fr = LanguageRules {
-            quotes: ("\u{00AB} ".to_string(), " \u{00BB}".to_string()),  // « and »
-            single_quotes: ("\u{201C}".to_string(), "\u{201D}".to_string()),  // " and "
-            replacements: None,
-            space_rules: Some(SpaceRules {
-                before_unit: "\u{00A0}".to_string(),
-                in_number: "\u{00A0}".to_string(),
-                before_currency: "\u{00A0}".to_string(),
-                after_abbr: "\u{00A0}".to_string(),
-            }),
-        };
-        languages.insert("fr".to_string(), fr);
-        
         TypographyConfig {
             default_filter: "smart".to_string(),
-            features: TypographyFeatures {
-                ligatures: true,
-                kerning: true,
-                hyphenation: "auto".to_string(),
-                hanging_punctuation: false,
-            },
-            languages,
+            features: TypographyFeatures::default(),
+            cjk: CjkSettings::default(),
+            hyphenate: HyphenateSettings::default(),
+            widows: WidowSettings::default(),
+            // Built-in languages live in the registry
+            languages: HashMap::new(),
         }
     }
 }
 
-/// Generate CSS for OpenType features and typography settings
-pub fn generate_css() -> Result<String> {
+impl Default for TypographyFeatures {
+    fn default() -> Self {
+        TypographyFeatures {
+            ligatures: true,
+            kerning: true,
+            hyphenation: "auto".to_string(),
+            hanging_punctuation: false,
+        }
+    }
+}
+
+/// Generate site-wide typography defaults and per-language `:lang()` rules
+pub fn generate_defaults(config: &TypographyConfig) -> Result<String> {
     let mut css = String::new();
+    let features = &config.features;
     
-    css.push_str("  /* ========== Typography Features ========== */\n");
+    css.push_str("\n  /* Typography defaults (reedstyle.typography.yaml) */\n");
+    css.push_str("  :root {\n");
+    css.push_str(&feature_declarations(&LanguageFeatures {
+        ligatures: Some(features.ligatures),
+        kerning: Some(features.kerning),
+        hyphenation: Some(features.hyphenation.clone()),
+        hanging_punctuation: Some(features.hanging_punctuation),
+    })?);
+    css.push_str(&limit_declarations(&config.hyphenate)?);
+    css.push_str("  }\n");
     
-    // OpenType features
-    css.push_str(r#"  reed[text*="ligatures:true"] {
-    font-variant-ligatures: common-ligatures;
-    font-feature-settings: "liga" 1, "clig" 1;
-  }
-
-"#);
+    let registry = LanguageRegistry::from_config(config);
     
-    css.push_str(r#"  reed[text*="small-caps:true"] {
-    font-variant-caps: small-caps;
-    font-feature-settings: "smcp" 1;
-  }
-
-"#);
+    for (lang, rules) in registry.languages() {
+        let (open, close) = rules.quotes();
+        let (single_open, single_close) = rules.single_quotes();
+        css.push_str(&format!("  :lang({}) {{\n", lang));
+        css.push_str(&format!(
+            "    quotes: \"{}\" \"{}\" \"{}\" \"{}\";\n",
+            open, close, single_open, single_close
+        ));
+        if let Some(overrides) = config.languages.get(lang).and_then(|rules| rules.features.as_ref()) {
+            css.push_str(&feature_declarations(overrides)?);
+        }
+        css.push_str("  }\n");
+    }
     
-    css.push_str(r#"  reed[text*="numbers:tabular"] {
-    font-variant-numeric: tabular-nums;
-    font-feature-settings: "tnum" 1;
-  }
+    Ok(css)
+}
 
-"#);
+fn feature_declarations(features: &LanguageFeatures) -> Result<String> {
+    let mut css = String::new();
     
-    css.push_str(r#"  reed[text*="numbers:oldstyle"] {
-    font-variant-numeric: oldstyle-nums;
-    font-feature-settings: "onum" 1;
-  }
+    if let Some(ligatures) = features.ligatures {
+        let value = if ligatures { "common-ligatures" } else { "no-common-ligatures" };
+        css.push_str(&format!("    font-variant-ligatures: {};\n", value));
+    }
+    if let Some(kerning) = features.kerning {
+        css.push_str(&format!("    font-kerning: {};\n", if kerning { "normal" } else { "none" }));
+    }
+    if let Some(hyphenation) = &features.hyphenation {
+        if !matches!(hyphenation.as_str(), "auto" | "manual" | "none") {
+            bail!("Unknown hyphenation '{}' (expected auto, manual or none)", hyphenation);
+        }
+        css.push_str(&format!("    hyphens: {};\n", hyphenation));
+        css.push_str(&format!("    -webkit-hyphens: {};\n", hyphenation));
+    }
+    if let Some(hanging) = features.hanging_punctuation {
+        css.push_str(&format!("    hanging-punctuation: {};\n", if hanging { "first last" } else { "none" }));
+    }
+    
+    Ok(css)
+}
 
-"#);
+fn limit_declarations(hyphenate: &HyphenateSettings) -> Result<String> {
+    let mut css = String::new();
+    let (word, before, after) = hyphenate.limit_chars;
     
-    // Hyphenation
-    css.push_str(r#"  reed[text*="hyphenate:true"] {
-    hyphens: auto;
-    -webkit-hyphens: auto;
-    hyphenate-limit-lines: 2;
-    hyphenate-limit-last: always;
-  }
-
-"#);
+    if !matches!(hyphenate.limit_last.as_str(), "none" | "always" | "column" | "page" | "spread") {
+        bail!("Unknown hyphenate limit_last '{}' (expected none, always, column, page or spread)", hyphenate.limit_last);
+    }
+    css.push_str(&format!("    hyphenate-limit-chars: {} {} {};\n", word, before, after));
+    css.push_str(&format!("    hyphenate-limit-lines: {};\n", hyphenate.limit_lines));
+    css.push_str(&format!("    hyphenate-limit-last: {};\n", hyphenate.limit_last));
     
-    // Hanging punctuation
-    css.push_str(r#"  reed[text*="hanging-punctuation:true"] {
-    hanging-punctuation: first last;
-  }
+    Ok(css)
+}
 
-"#);
+/// Generate CSS for OpenType features and typography settings
+pub fn generate_css() -> Result<String> {
+    let mut css = String::new();
     
-    // Line height (leading)
-    css.push_str(r#"  reed[text*="leading:tight"] { line-height: 1.25; }
-  reed[text*="leading:normal"] { line-height: 1.5; }
-  reed[text*="leading:relaxed"] { line-height: 1.75; }
-  reed[text*="leading:loose"] { line-height: 2; }
-
-"#);
+    css.push_str("  /* ========== Typography Features ========== */\n");
     
-    // Measure (line length)
-    css.push_str(r#"  reed[text*="measure:narrow"] { max-width: 45ch; }
-  reed[text*="measure:normal"] { max-width: 65ch; }
-  reed[text*="measure:wide"] { max-width: 85ch; }
-
-"#);
+    let rules: &[(&str, &str)] = &[
+        // OpenType features
+        ("ligatures:true", "font-variant-ligatures: common-ligatures; font-feature-settings: \"liga\" 1, \"clig\" 1;"),
+        ("ligatures:false", "font-variant-ligatures: no-common-ligatures; font-feature-settings: \"liga\" 0, \"clig\" 0;"),
+        ("kerning:true", "font-kerning: normal;"),
+        ("kerning:false", "font-kerning: none;"),
+        ("small-caps:true", "font-variant-caps: small-caps; font-feature-settings: \"smcp\" 1;"),
+        ("numbers:tabular", "font-variant-numeric: tabular-nums; font-feature-settings: \"tnum\" 1;"),
+        ("numbers:oldstyle", "font-variant-numeric: oldstyle-nums; font-feature-settings: \"onum\" 1;"),
+        // Hyphenation
+        // Limits (hyphenate-limit-*) are inherited from the :root defaults
+        ("hyphenate:true", "hyphens: auto; -webkit-hyphens: auto;"),
+        ("hyphenate:false", "hyphens: manual; -webkit-hyphens: manual;"),
+        // Hanging punctuation
+        ("hanging-punctuation:true", "hanging-punctuation: first last;"),
+        ("hanging-punctuation:false", "hanging-punctuation: none;"),
+        // Line height (leading)
+        ("leading:tight", "line-height: 1.25;"),
+        ("leading:normal", "line-height: 1.5;"),
+        ("leading:relaxed", "line-height: 1.75;"),
+        ("leading:loose", "line-height: 2;"),
+        // CJK line breaking and punctuation spacing
+        ("line-break:strict", "line-break: strict;"),
+        ("line-break:normal", "line-break: normal;"),
+        ("line-break:loose", "line-break: loose;"),
+        ("line-break:anywhere", "line-break: anywhere;"),
+        ("spacing-trim:normal", "text-spacing-trim: normal;"),
+        ("spacing-trim:space-all", "text-spacing-trim: space-all;"),
+        ("spacing-trim:space-first", "text-spacing-trim: space-first;"),
+        ("spacing-trim:trim-start", "text-spacing-trim: trim-start;"),
+        ("autospace:true", "text-autospace: normal;"),
+        ("autospace:false", "text-autospace: no-autospace;"),
+        // Measure (line length); measure:narrow uses NARROW_MEASURE below
+        ("measure:normal", "max-width: 65ch;"),
+        ("measure:wide", "max-width: 85ch;"),
+    ];
     
-    // Text filters (placeholder for JS processing)
-    css.push_str(r#"  reed[text*="filter:minimal"] { /* Processed by JS */ }
-  reed[text*="filter:smart"] { /* Processed by JS */ }
-  reed[text*="filter:professional"] { /* Processed by JS */ }
-
-"#);
+    for (token, declarations) in rules {
+        css.push_str(&format!("  {} {{ {} }}\n", token_selector("text", token), declarations));
+    }
+    css.push_str(&format!("  {} {{ max-width: {}ch; }}\n", token_selector("text", "measure:narrow"), NARROW_MEASURE));
+    
+    // Text filters (filter:minimal|smart|professional) are applied by the JS
+    // typography engine and need no CSS
     
     Ok(css)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_selectors_target_r_s() {
+        let css = generate_css().unwrap();
+        assert!(!css.contains("reed["));
+        assert!(css.contains("r-s:is([text~=\"leading:tight\"]"));
+        assert!(css.contains("[text~=\"[numbers:tabular,\"]"));
+    }
+
+    #[test]
+    fn test_defaults_from_config() {
+        let config: TypographyConfig = serde_yaml::from_str(
+            "features:\n  hyphenation: manual\nlanguages:\n  de:\n    quotes: [\"\u{201E}\", \"\u{201C}\"]\n    single_quotes: [\"\u{201A}\", \"\u{2018}\"]\n    features:\n      hyphenation: auto\n"
+        ).unwrap();
+        let css = generate_defaults(&config).unwrap();
+
+        assert!(css.contains(":root {\n    font-variant-ligatures: common-ligatures;"));
+        assert!(css.contains("    hyphens: manual;"));
+        assert!(css.contains("    hyphenate-limit-chars: 6 3 2;\n    hyphenate-limit-lines: 2;"));
+        assert!(css.contains(":lang(de) {\n    quotes: \"\u{201E}\" \"\u{201C}\" \"\u{201A}\" \"\u{2018}\";\n    hyphens: auto;"));
+    }
+}
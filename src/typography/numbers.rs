@@ -0,0 +1,310 @@
+use regex::Regex;
+use std::ops::Range;
+use super::rules::{NBSP, UNITS};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
+
+/// Currency symbols, which may move to the side the language writes them on
+const SYMBOLS: &str = r"[€$£]";
+
+/// Currency codes (only as whole words); they stay where they are written
+const CODES: &str = r"(?:EUR|CHF|USD|GBP|kr|Kč|zł)\b";
+
+/// Numbers that are not amounts, found before any grouping
+const KEPT: &[&str] = &[
+    // ISO dates and date-times (2024-10-18, 2024-10-18T14:30)
+    r"\d{4}-\d{2}-\d{2}(?:[T ]\d{2}:\d{2}(?::\d{2})?)?",
+    // Numeric dates (18.10.2024, 10/18/24)
+    r"\b\d{1,2}[./]\d{1,2}[./](?:\d{4}|\d{2})\b",
+    // Version strings (1.2.3, v2.10, 1.0.0-beta.2)
+    r"\bv?\d+(?:\.\d+){2,}(?:[-+][0-9A-Za-z.]+)?|\bv\d+(?:\.\d+)*",
+];
+
+/// Phone numbers: an international or trunk prefix, then groups of digits
+const PHONE: &str = r"(?:\+\d{1,3}|\b0\d{1,5})(?:[ /.-]\d{2,}){1,6}";
+
+/// The dash between two numbers, after `apply_replacements` may have made
+/// a spaced hyphen an en dash
+const RANGE: &str = r"\d([ \t]*-[ \t]*|[ \t]+–[ \t]+)\d";
+
+/// Postcodes before a place name at the start of an address line
+/// ("…, 10115 Berlin") and US ZIP codes after the state ("NY 10001")
+const POSTCODES: &[&str] = &[
+    r"(?:^|,|\n)[ \t]*(\d{4,5})[ \t]+\p{Lu}",
+    r"\b[A-Z]{2} (\d{5})(?:-\d{4})?\b",
+];
+
+/// Format the numbers in `text` for one language
+///
+/// Integers of `format.group_from` or more digits are grouped with
+/// `space.in_number`, also in front of the decimal separator; numbers that
+/// are already grouped are left alone. Four-digit integers are taken to be
+/// years unless a unit or currency follows. Dates, times, versions, postcodes,
+/// numbers with a leading zero, numbers attached to letters (`A380`, `D-10115`)
+/// and `format.keep` matches are never touched; phone numbers only have their
+/// spaces bound. With `format.range_dash`, the dash of a range between two
+/// other numbers becomes an en dash. Units and currencies are then bound to
+/// their numbers, with currency symbols moved to `format.currency`.
+pub fn format(text: &str, space: &SpaceRules, format: &NumberFormat) -> String {
+    let mut kept: Vec<Range<usize>> = Vec::new();
+    let mut phones: Vec<Range<usize>> = Vec::new();
+
+    for pattern in KEPT.iter().copied().chain(format.keep.iter().map(String::as_str)) {
+        if let Ok(re) = Regex::new(pattern) {
+            for found in re.find_iter(text) {
+                claim(&mut kept, found.range());
+            }
+        }
+    }
+    for pattern in POSTCODES {
+        let re = Regex::new(pattern).unwrap();
+        for postcode in re.captures_iter(text).filter_map(|caps| caps.get(1)) {
+            claim(&mut kept, postcode.range());
+        }
+    }
+    let phone = Regex::new(PHONE).unwrap();
+    for found in phone.find_iter(text) {
+        if claim(&mut kept, found.range()) {
+            phones.push(found.range());
+        }
+    }
+
+    // Edits in the original text, applied back to front
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    if format.bind_phone_numbers {
+        for range in phones {
+            edits.push((range.clone(), text[range].replace(' ', NBSP)));
+        }
+    }
+
+    if format.range_dash {
+        let range = Regex::new(RANGE).unwrap();
+        let mut at = 0;
+        // Matches share digits (1-2-3), so each search starts at the last dash
+        while let Some(caps) = range.captures_at(text, at) {
+            let (whole, dash) = (caps.get(0).unwrap(), caps.get(1).unwrap());
+            let first = number_around(text, whole.start());
+            let second = number_around(text, whole.end() - 1);
+            let both = first.start..second.end;
+            if !attached(text, &both) && !overlaps(&kept, &both) {
+                edits.push((dash.range(), "–".to_string()));
+            }
+            at = dash.end();
+        }
+    }
+
+    let follows = Regex::new(&format!(r"^\s*(?:(?:{})\b|{}|{})", units_pattern(), SYMBOLS, CODES)).unwrap();
+    let number = Regex::new(r"\d+(?:[.,'\u{2019}]\d+)*").unwrap();
+    for found in number.find_iter(text) {
+        let range = found.range();
+        if overlaps(&kept, &range) || attached(text, &range) {
+            continue;
+        }
+        let unit_follows = follows.is_match(&text[range.end..]);
+        if let Some(formatted) = format_number(found.as_str(), unit_follows, space, format) {
+            edits.push((range, formatted));
+        }
+    }
+
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut result = text.to_string();
+    for (range, replacement) in edits.into_iter().rev() {
+        result.replace_range(range, &replacement);
+    }
+
+    bind_units_and_currencies(&result, space, format)
+}
+
+/// The digits around the one at `at`
+fn number_around(text: &str, at: usize) -> Range<usize> {
+    let start = text[..at].rfind(|c: char| !c.is_ascii_digit()).map_or(0, |i| i + 1);
+    let end = text[at..].find(|c: char| !c.is_ascii_digit()).map_or(text.len(), |i| at + i);
+    start..end
+}
+
+/// Add `range` to `kept` unless an earlier match overlaps it
+fn claim(kept: &mut Vec<Range<usize>>, range: Range<usize>) -> bool {
+    let free = !overlaps(kept, &range);
+    if free {
+        kept.push(range);
+    }
+    free
+}
+
+fn overlaps(kept: &[Range<usize>], range: &Range<usize>) -> bool {
+    kept.iter().any(|k| k.start < range.end && range.start < k.end)
+}
+
+/// Whether a number is part of a word or code (`A380`, `MP3`, `D-10115`, `ISBN 978-3-16`)
+fn attached(text: &str, range: &Range<usize>) -> bool {
+    let mut before = text[..range.start].chars().rev();
+    let joined_before = match before.next() {
+        Some(c) if c.is_alphanumeric() || c == '_' => true,
+        Some('-' | '/') => before.next().is_some_and(char::is_alphanumeric),
+        _ => false,
+    };
+
+    let mut after = text[range.end..].chars();
+    let joined_after = matches!(after.next(), Some('-' | '/')) && after.next().is_some_and(|c| c.is_ascii_digit());
+
+    joined_before || joined_after
+}
+
+/// Group one number, or None to leave it as written
+fn format_number(number: &str, unit_follows: bool, space: &SpaceRules, format: &NumberFormat) -> Option<String> {
+    let separators: Vec<(usize, char)> = number.char_indices().filter(|(_, c)| !c.is_ascii_digit()).collect();
+
+    let (integer, fraction) = match separators.as_slice() {
+        [] => (number, None),
+        [(at, separator)] => {
+            let (integer, rest) = (&number[..*at], &number[at + separator.len_utf8()..]);
+            if format.decimal.starts_with(*separator) {
+                (integer, Some(rest))
+            } else if format.normalize_decimal && rest.len() != 3 && matches!(separator, '.' | ',') {
+                // 29.99 written with the other decimal separator
+                (integer, Some(rest))
+            } else {
+                // Already grouped (12.500) or not a number of this language
+                return None;
+            }
+        }
+        _ => return None,
+    };
+
+    let year = fraction.is_none()
+        && !unit_follows
+        && integer.len() == 4
+        && integer.parse::<u32>().is_ok_and(|year| (1000..2200).contains(&year));
+    let grouped = if integer.len() < format.group_from || integer.starts_with('0') || year {
+        integer.to_string()
+    } else {
+        group(integer, &space.in_number)
+    };
+
+    let formatted = match fraction {
+        Some(fraction) => format!("{}{}{}", grouped, format.decimal, fraction),
+        None => grouped,
+    };
+    (formatted != number).then_some(formatted)
+}
+
+/// Insert `separator` every three digits from the right
+fn group(digits: &str, separator: &str) -> String {
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn units_pattern() -> String {
+    UNITS.iter().map(|unit| regex::escape(unit)).collect::<Vec<_>>().join("|")
+}
+
+/// Bind units and currencies to their numbers, moving currency symbols to
+/// the side the language writes them on
+fn bind_units_and_currencies(text: &str, space: &SpaceRules, format: &NumberFormat) -> String {
+    let mut result = text.to_string();
+
+    for unit in UNITS {
+        let pattern = format!(r"(\d+)\s+{}\b", regex::escape(unit));
+        if let Ok(re) = Regex::new(&pattern) {
+            result = re.replace_all(&result, format!("${{1}}{}{}", space.before_unit, unit).as_str()).to_string();
+        }
+    }
+
+    // An amount, including the group separators inserted above
+    let amount = r"\d(?:[\d.,'\u{2019}\u{00A0}\u{202F}]*\d)?";
+    match format.currency {
+        CurrencyPosition::Before => {
+            let re = Regex::new(&format!(r"({})\s*({})", amount, SYMBOLS)).unwrap();
+            result = re.replace_all(&result, "$2$1").to_string();
+        }
+        CurrencyPosition::After => {
+            let re = Regex::new(&format!(r"(^|[\s(])({})\s*({})", SYMBOLS, amount)).unwrap();
+            result = re.replace_all(&result, "$1$3$2").to_string();
+        }
+        CurrencyPosition::Keep => {}
+    }
+
+    // Symbols take the language's space (none in English: $5); codes always take one
+    let code_space = if space.before_currency.is_empty() { NBSP } else { &space.before_currency };
+    for (currency, space) in [(SYMBOLS, space.before_currency.as_str()), (CODES, code_space)] {
+        let after = Regex::new(&format!(r"(\d)\s*({})", currency)).unwrap();
+        result = after.replace_all(&result, format!("${{1}}{}${{2}}", space).as_str()).to_string();
+        let before = Regex::new(&format!(r"(^|[\s(])({})\s*(\d)", currency)).unwrap();
+        result = before.replace_all(&result, format!("${{1}}${{2}}{}${{3}}", space).as_str()).to_string();
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::rules::NNBSP;
+
+    fn german() -> (SpaceRules, NumberFormat) {
+        let space = SpaceRules {
+            before_unit: NNBSP.to_string(),
+            in_number: NNBSP.to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        };
+        let format = NumberFormat { decimal: ",".to_string(), currency: CurrencyPosition::After, ..NumberFormat::default() };
+        (space, format)
+    }
+
+    #[test]
+    fn test_space_rules() {
+        let rules = SpaceRules {
+            before_unit: NNBSP.to_string(),
+            in_number: "'".to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        };
+        let format = NumberFormat::default();
+        assert_eq!(super::format("12500 m", &rules, &format), format!("12'500{}m", NNBSP));
+        assert_eq!(super::format("9.90 CHF", &rules, &format), format!("9.90{}CHF", NBSP));
+        assert_eq!(super::format("CHF 1250000", &rules, &format), format!("CHF{}1'250'000", NBSP));
+        assert_eq!(super::format("3.14159 kr", &rules, &format), format!("3.14159{}kr", NBSP));
+    }
+
+    #[test]
+    fn test_grouping() {
+        let (space, format) = german();
+
+        assert_eq!(super::format("12500 m und 1234567,891", &space, &format), format!("12{0}500{0}m und 1{0}234{0}567,891", NNBSP));
+        assert_eq!(super::format("€ 1500,00 und 12.500", &space, &format), format!("1{}500,00{}€ und 12.500", NNBSP, NBSP));
+        assert_eq!(super::format("Seit 2024 kostet es 2024 €", &space, &format), format!("Seit 2024 kostet es 2{}024{}€", NNBSP, NBSP));
+    }
+
+    #[test]
+    fn test_kept_numbers() {
+        let (space, format) = german();
+        let kept = "Am 2024-10-18 und 18.10.2024 kam v1.20.3 für den A3800 (Art. D-10115, ISBN 978-3-16-148410-0)";
+
+        assert_eq!(super::format(kept, &space, &format), kept);
+        assert_eq!(super::format("Hauptstraße 1, 10115 Berlin", &space, &format), "Hauptstraße 1, 10115 Berlin");
+        assert_eq!(super::format("Tel. +41 44 123 45 67", &space, &format), "Tel. +41\u{A0}44\u{A0}123\u{A0}45\u{A0}67");
+        assert_eq!(
+            super::format("PLZ 12345 und 99999", &space, &NumberFormat { keep: vec![r"PLZ \d{5}".to_string()], ..format.clone() }),
+            format!("PLZ 12345 und 99{}999", NNBSP)
+        );
+    }
+
+    #[test]
+    fn test_currency_before() {
+        let space = SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: ",".to_string(),
+            before_currency: String::new(),
+            after_abbr: NBSP.to_string(),
+        };
+        let format = NumberFormat { currency: CurrencyPosition::Before, ..NumberFormat::default() };
+
+        assert_eq!(super::format("It costs 1250.50 $ or 900 USD", &space, &format), "It costs $1,250.50 or 900\u{A0}USD");
+    }
+}
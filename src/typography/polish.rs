@@ -0,0 +1,89 @@
+use super::rules::{TypographyRules, ELLIPSIS, EMDASH, NBSP, NNBSP};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
+
+// Unicode constants for Polish typography
+const LDQUO_PL: &str = "\u{201E}";    // Polish opening double quote „
+const RDQUO_PL: &str = "\u{201D}";    // Polish closing double quote ”
+const LAQUO: &str = "\u{00AB}";       // Left guillemet «
+const RAQUO: &str = "\u{00BB}";       // Right guillemet »
+
+/// Polish typography rules
+///
+/// Quotations nest as „…«…»…”; sites that nest as „…»…«…” declare `pl`
+/// with reversed `single_quotes` in `reedstyle.typography.yaml`. Single-letter words are bound to the
+/// following word so they never end a line.
+pub struct PolishRules;
+
+impl TypographyRules for PolishRules {
+    fn quotes(&self) -> (&str, &str) {
+        (LDQUO_PL, RDQUO_PL)
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        (LAQUO, RAQUO)
+    }
+
+    fn replacements(&self) -> Vec<(&str, &str)> {
+        vec![
+            // Single-letter conjunctions and prepositions stay with the next word
+            (r"\b([aiouwzAIOUWZ]) ", "$1\u{00A0}"),
+
+            // Three dots to ellipsis
+            ("...", ELLIPSIS),
+
+            // Double hyphen to em dash
+            ("--", EMDASH),
+
+            // Single hyphen with spaces to en dash
+            (" - ", " \u{2013} "),
+        ]
+    }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        vec!["np. ", "tj. ", "tzn. ", "ul. ", "prof. ", "dr "]
+    }
+
+    fn space_rules(&self) -> Option<SpaceRules> {
+        Some(SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: NNBSP.to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        })
+    }
+
+    /// Decimal comma, 29,99 zł and no grouping below five digits
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            decimal: ",".to_string(),
+            currency: CurrencyPosition::After,
+            group_from: 5,
+            ..NumberFormat::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typography::{LanguageRegistry, TypographyConfig};
+
+    #[test]
+    fn test_polish() {
+        assert_eq!(
+            PolishRules.apply_all("\"Kot i pies\" w domu kosztują 12000 zł"),
+            format!("{}Kot i{}pies{} w{}domu kosztują 12{}000{}zł", LDQUO_PL, NBSP, RDQUO_PL, NBSP, NNBSP, NBSP)
+        );
+    }
+
+    #[test]
+    fn test_reversed_guillemets() {
+        let sample = "\"Mówił 'tak' i poszedł\"";
+        assert_eq!(PolishRules.apply_quotes(sample), format!("{}Mówił {}tak{} i poszedł{}", LDQUO_PL, LAQUO, RAQUO, RDQUO_PL));
+
+        let config: TypographyConfig = serde_yaml::from_str("languages:\n  pl:\n    quotes: [\"\u{201E}\", \"\u{201D}\"]\n    single_quotes: [\"\u{00BB}\", \"\u{00AB}\"]\n").unwrap();
+        let registry = LanguageRegistry::from_config(&config);
+        assert_eq!(registry.get("pl-PL").apply_quotes(sample), format!("{}Mówił {}tak{} i poszedł{}", LDQUO_PL, RAQUO, LAQUO, RDQUO_PL));
+        assert_eq!(registry.get("pl").format_numbers("12000 zł"), PolishRules.format_numbers("12000 zł"));
+    }
+}
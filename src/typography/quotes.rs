@@ -0,0 +1,205 @@
+/// Typographic apostrophe, the same in every supported language
+pub const APOSTROPHE: char = '\u{2019}';
+/// Feet / minutes
+pub const PRIME: char = '\u{2032}';
+/// Inches / seconds
+pub const DOUBLE_PRIME: char = '\u{2033}';
+
+/// Quotation marks of one language, and which ASCII quote is primary
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteMarks<'a> {
+    pub primary: (&'a str, &'a str),
+    pub secondary: (&'a str, &'a str),
+    /// `"` in most languages, `'` in British English
+    pub primary_char: char,
+}
+
+/// An open quotation: the ASCII character that opened it and whether it
+/// uses the primary marks
+struct Open {
+    quote: char,
+    primary: bool,
+}
+
+/// Replace ASCII quotes with typographic ones, reading each quote in context
+///
+/// A quote after a space or opening punctuation and before a word opens; one
+/// after a word closes the innermost quotation opened with the same
+/// character. Top-level quotations use the marks their character stands for,
+/// nested ones alternate between primary and secondary. A `'` between
+/// letters or before an abbreviated year (`'90s`) is an apostrophe, and
+/// quotes right after a digit with nothing open are feet and inches (`6' 2"`).
+pub fn apply(text: &str, marks: &QuoteMarks) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut open: Vec<Open> = Vec::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c != '"' && c != '\'' {
+            result.push(c);
+            continue;
+        }
+
+        let prev = neighbour(&chars, i, -1);
+        let next = neighbour(&chars, i, 1);
+        let is_open = |quote: char| open.iter().any(|o| o.quote == quote);
+
+        // Apostrophes: geht's, isn't, rock 'n' roll's tail, '90s
+        if c == '\'' {
+            if prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphabetic) {
+                result.push(APOSTROPHE);
+                continue;
+            }
+            if !prev.is_some_and(char::is_alphanumeric) && is_year_abbreviation(&chars, i) {
+                result.push(APOSTROPHE);
+                continue;
+            }
+        }
+
+        // Measurements: 6' 2", 12"
+        if prev.is_some_and(|p| p.is_ascii_digit()) && !is_open(c) {
+            result.push(if c == '"' { DOUBLE_PRIME } else { PRIME });
+            continue;
+        }
+
+        let after_space = prev.is_none_or(|p| p.is_whitespace() || is_opening_punctuation(p));
+        let before_space = next.is_none_or(char::is_whitespace);
+
+        if after_space && !before_space {
+            let primary = match open.last() {
+                Some(outer) => !outer.primary,
+                None => c == marks.primary_char,
+            };
+            result.push_str(if primary { marks.primary.0 } else { marks.secondary.0 });
+            open.push(Open { quote: c, primary });
+        } else if !after_space {
+            if let Some(index) = open.iter().rposition(|o| o.quote == c) {
+                let primary = open[index].primary;
+                open.truncate(index);
+                result.push_str(if primary { marks.primary.1 } else { marks.secondary.1 });
+            } else if c == '\'' {
+                // Trailing apostrophe (the students' books)
+                result.push(APOSTROPHE);
+            } else {
+                let primary = c == marks.primary_char;
+                result.push_str(if primary { marks.primary.1 } else { marks.secondary.1 });
+            }
+        } else {
+            // Free-standing quote between spaces
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Pair ASCII quotes by order, for languages written without spaces
+///
+/// A quote closes the innermost quotation opened with the same character,
+/// otherwise it opens one (nested ones alternate between primary and
+/// secondary marks). `'` between Latin letters stays an apostrophe.
+pub fn pair(text: &str, marks: &QuoteMarks) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut open: Vec<(char, bool)> = Vec::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c != '"' && c != '\'' {
+            result.push(c);
+            continue;
+        }
+
+        let latin = |c: Option<&char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+        if c == '\'' && latin(i.checked_sub(1).and_then(|p| chars.get(p))) && latin(chars.get(i + 1)) {
+            result.push(APOSTROPHE);
+            continue;
+        }
+
+        if let Some(index) = open.iter().rposition(|(quote, _)| *quote == c) {
+            let is_primary = open[index].1;
+            open.truncate(index);
+            result.push_str(if is_primary { marks.primary.1 } else { marks.secondary.1 });
+        } else {
+            let is_primary = open.last().map_or(c == marks.primary_char, |(_, outer)| !outer);
+            result.push_str(if is_primary { marks.primary.0 } else { marks.secondary.0 });
+            open.push((c, is_primary));
+        }
+    }
+
+    result
+}
+
+/// The nearest character before or after `index`, skipping the private use
+/// markers the HTML typesetter places between text nodes
+fn neighbour(chars: &[char], index: usize, step: isize) -> Option<char> {
+    let mut i = index as isize + step;
+    while i >= 0 && (i as usize) < chars.len() {
+        let c = chars[i as usize];
+        if !('\u{E000}'..='\u{F8FF}').contains(&c) {
+            return Some(c);
+        }
+        i += step;
+    }
+    None
+}
+
+fn is_opening_punctuation(c: char) -> bool {
+    matches!(c, '(' | '[' | '{' | '-' | '\u{2013}' | '\u{2014}' | '/' | '\u{00A0}' | '\u{202F}')
+        || matches!(c, '\u{201E}' | '\u{201A}' | '\u{201C}' | '\u{2018}' | '\u{00AB}' | '\u{2039}')
+}
+
+/// `'90s`, `'24`: an apostrophe followed by exactly two digits
+fn is_year_abbreviation(chars: &[char], index: usize) -> bool {
+    let digits = chars[index + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+    digits == 2 && !chars.get(index + 3).is_some_and(|&c| c == '\'' || c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN_US: QuoteMarks = QuoteMarks {
+        primary: ("\u{201C}", "\u{201D}"),
+        secondary: ("\u{2018}", "\u{2019}"),
+        primary_char: '"',
+    };
+
+    const DE: QuoteMarks = QuoteMarks {
+        primary: ("\u{201E}", "\u{201C}"),
+        secondary: ("\u{201A}", "\u{2018}"),
+        primary_char: '"',
+    };
+
+    #[test]
+    fn test_apostrophes_next_to_quotes() {
+        assert_eq!(apply("'Hello' isn't it", &EN_US), "\u{2018}Hello\u{2019} isn\u{2019}t it");
+        assert_eq!(apply("Wie geht's?", &DE), "Wie geht\u{2019}s?");
+        assert_eq!(apply("the '90s and the students' books", &EN_US), "the \u{2019}90s and the students\u{2019} books");
+    }
+
+    #[test]
+    fn test_nesting() {
+        assert_eq!(
+            apply("\"Er sagte 'Hallo' und \"ging\".\"", &DE),
+            "\u{201E}Er sagte \u{201A}Hallo\u{2018} und \u{201A}ging\u{2018}.\u{201C}"
+        );
+    }
+
+    #[test]
+    fn test_pairing_without_spaces() {
+        let ja = QuoteMarks {
+            primary: ("\u{300C}", "\u{300D}"),
+            secondary: ("\u{300E}", "\u{300F}"),
+            primary_char: '"',
+        };
+        assert_eq!(pair("彼は\"こんにちは'世界'\"と言った", &ja), "彼は「こんにちは『世界』」と言った");
+        assert_eq!(pair("\"don't\"", &EN_US), "\u{201C}don\u{2019}t\u{201D}");
+    }
+
+    #[test]
+    fn test_measurements_and_punctuation() {
+        assert_eq!(apply("He is 6' 2\" tall", &EN_US), "He is 6\u{2032} 2\u{2033} tall");
+        assert_eq!(apply("(\"Route 66\")", &EN_US), "(\u{201C}Route 66\u{201D})");
+        assert_eq!(apply("She said, \"Wait!\"", &EN_US), "She said, \u{201C}Wait!\u{201D}");
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use super::cjk::CjkRules;
+use super::czech::CzechRules;
+use super::dutch::DutchRules;
+use super::english::EnglishRules;
+use super::french::FrenchRules;
+use super::german::GermanRules;
+use super::italian::ItalianRules;
+use super::polish::PolishRules;
+use super::spanish::SpanishRules;
+use super::swedish::SwedishRules;
+use super::numbers;
+use super::rules::{self, DefaultRules, TypographyRules};
+use super::{LanguageRules, NumberFormat, SpaceRules, TypographyConfig};
+
+/// Typography rules keyed by BCP 47 language tag
+///
+/// Lookups fall back subtag by subtag (`de-CH-1996` → `de-CH` → `de`) and
+/// end at the default rules, so every tag gets some rules.
+pub struct LanguageRegistry {
+    /// Lowercased tag -> (tag as registered, rules)
+    languages: HashMap<String, (String, Arc<dyn TypographyRules>)>,
+    default: Arc<dyn TypographyRules>,
+}
+
+impl Default for LanguageRegistry {
+    fn default() -> Self {
+        let mut registry = LanguageRegistry {
+            languages: HashMap::new(),
+            default: Arc::new(DefaultRules),
+        };
+
+        registry.register("de", Arc::new(GermanRules::DE));
+        // Austria follows DIN 5008 like Germany
+        registry.register("de-AT", Arc::new(GermanRules::DE));
+        registry.register("de-CH", Arc::new(GermanRules::CH));
+        registry.register("en", Arc::new(EnglishRules::US));
+        registry.register("en-GB", Arc::new(EnglishRules::GB));
+        registry.register("fr", Arc::new(FrenchRules));
+        registry.register("it", Arc::new(ItalianRules));
+        registry.register("es", Arc::new(SpanishRules));
+        registry.register("nl", Arc::new(DutchRules));
+        registry.register("pl", Arc::new(PolishRules));
+        registry.register("cs", Arc::new(CzechRules));
+        registry.register("sv", Arc::new(SwedishRules));
+        for (tag, rules) in CjkRules::languages(false) {
+            registry.register(tag, Arc::new(rules));
+        }
+
+        registry
+    }
+}
+
+impl LanguageRegistry {
+    /// Built-in languages plus those declared in `reedstyle.typography.yaml`
+    pub fn from_config(config: &TypographyConfig) -> Self {
+        let mut registry = LanguageRegistry::default();
+
+        if config.cjk.spacing {
+            for (tag, rules) in CjkRules::languages(true) {
+                registry.register(tag, Arc::new(rules));
+            }
+        }
+
+        // Shorter tags first, so `de-CH` builds on a configured `de`
+        let mut languages: Vec<(&String, &LanguageRules)> = config.languages.iter().collect();
+        languages.sort_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(b.0)));
+
+        for (tag, rules) in languages {
+            registry.register_config(tag, rules.clone());
+        }
+
+        registry
+    }
+
+    pub fn register(&mut self, tag: &str, rules: Arc<dyn TypographyRules>) {
+        self.languages.insert(tag.to_ascii_lowercase(), (tag.to_string(), rules));
+    }
+
+    /// Register rules from configuration; anything the entry leaves out
+    /// comes from the rules the tag resolved to before
+    pub fn register_config(&mut self, tag: &str, rules: LanguageRules) {
+        let base = self.get_arc(tag);
+        self.register(tag, Arc::new(ConfiguredRules { rules, base }));
+    }
+
+    /// Rules for a language tag, with fallback
+    pub fn get(&self, tag: &str) -> &dyn TypographyRules {
+        self.resolve(tag).unwrap_or(&self.default).as_ref()
+    }
+
+    /// Rules for languages without a registered tag
+    pub fn default_rules(&self) -> &dyn TypographyRules {
+        self.default.as_ref()
+    }
+
+    /// Registered tags and their rules, sorted by tag
+    pub fn languages(&self) -> Vec<(&str, &dyn TypographyRules)> {
+        let mut languages: Vec<(&str, &dyn TypographyRules)> = self.languages.values()
+            .map(|(tag, rules)| (tag.as_str(), rules.as_ref()))
+            .collect();
+        languages.sort_by_key(|(tag, _)| tag.to_ascii_lowercase());
+        languages
+    }
+
+    fn get_arc(&self, tag: &str) -> Arc<dyn TypographyRules> {
+        self.resolve(tag).unwrap_or(&self.default).clone()
+    }
+
+    fn resolve(&self, tag: &str) -> Option<&Arc<dyn TypographyRules>> {
+        let mut tag = tag.to_ascii_lowercase();
+
+        loop {
+            if let Some((_, rules)) = self.languages.get(&tag) {
+                return Some(rules);
+            }
+            tag.truncate(tag.rfind('-')?);
+        }
+    }
+}
+
+/// A language declared in YAML, filling gaps from the rules it falls back to
+struct ConfiguredRules {
+    rules: LanguageRules,
+    base: Arc<dyn TypographyRules>,
+}
+
+impl TypographyRules for ConfiguredRules {
+    fn quotes(&self) -> (&str, &str) {
+        (&self.rules.quotes.0, &self.rules.quotes.1)
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        (&self.rules.single_quotes.0, &self.rules.single_quotes.1)
+    }
+
+    fn primary_quote(&self) -> char {
+        self.base.primary_quote()
+    }
+
+    fn word_spaces(&self) -> bool {
+        self.base.word_spaces()
+    }
+
+    fn replacements(&self) -> Vec<(&str, &str)> {
+        match &self.rules.replacements {
+            Some(replacements) => replacements.iter().map(|(p, r)| (p.as_str(), r.as_str())).collect(),
+            None => self.base.replacements(),
+        }
+    }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        match &self.rules.abbreviations {
+            Some(abbreviations) => abbreviations.iter().map(String::as_str).collect(),
+            None => self.base.abbreviations(),
+        }
+    }
+
+    fn space_rules(&self) -> Option<SpaceRules> {
+        self.rules.space_rules.clone().or_else(|| self.base.space_rules())
+    }
+
+    fn number_format(&self) -> NumberFormat {
+        self.rules.numbers.clone().unwrap_or_else(|| self.base.number_format())
+    }
+
+    fn apply_replacements(&self, text: &str) -> String {
+        match &self.rules.replacements {
+            Some(_) => rules::replace_all(text, &self.replacements()),
+            None => self.base.apply_replacements(text),
+        }
+    }
+
+    fn format_abbreviations(&self, text: &str) -> String {
+        if self.rules.abbreviations.is_none() && self.rules.space_rules.is_none() {
+            return self.base.format_abbreviations(text);
+        }
+        let space = self.space_rules().map(|rules| rules.after_abbr).unwrap_or_else(|| rules::NBSP.to_string());
+        rules::join_abbreviations(text, &self.abbreviations(), &space)
+    }
+
+    fn format_numbers(&self, text: &str) -> String {
+        if self.rules.space_rules.is_none() && self.rules.numbers.is_none() {
+            return self.base.format_numbers(text);
+        }
+        match self.space_rules() {
+            Some(space_rules) => numbers::format(text, &space_rules, &self.number_format()),
+            None => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback() {
+        let registry = LanguageRegistry::default();
+
+        assert_eq!(registry.get("de-DE-1996").quotes(), ("\u{201E}", "\u{201C}"));
+        assert_eq!(registry.get("de-CH").quotes(), ("\u{00AB}", "\u{00BB}"));
+        assert_eq!(registry.get("EN-gb").quotes(), ("\u{2018}", "\u{2019}"));
+        assert_eq!(registry.get("en-US").quotes(), ("\u{201C}", "\u{201D}"));
+        assert_eq!(registry.get("tlh").quotes(), registry.default_rules().quotes());
+    }
+
+    #[test]
+    fn test_yaml_language_extends_fallback() {
+        let config: TypographyConfig = serde_yaml::from_str(
+            "languages:\n  de-LU:\n    quotes: [\"\u{00AB}\", \"\u{00BB}\"]\n    single_quotes: [\"\u{2039}\", \"\u{203A}\"]\n"
+        ).unwrap();
+        let registry = LanguageRegistry::from_config(&config);
+        let luxembourg = registry.get("de-LU");
+
+        assert_eq!(luxembourg.apply_quotes("\"Moien\""), "\u{00AB}Moien\u{00BB}");
+        // Abbreviations and number spacing still come from German
+        assert_eq!(luxembourg.apply_all("z. B. 10000"), "z.\u{00A0}B. 10\u{202F}000");
+        assert_eq!(registry.get("de").quotes(), ("\u{201E}", "\u{201C}"));
+    }
+
+    #[test]
+    fn test_yaml_number_format() {
+        let config: TypographyConfig = serde_yaml::from_str(
+            "languages:\n  de-AT:\n    quotes: [\"\u{201E}\", \"\u{201C}\"]\n    single_quotes: [\"\u{201A}\", \"\u{2018}\"]\n\
+             \x20   numbers:\n      decimal: \",\"\n      group_from: 5\n      currency: before\n"
+        ).unwrap();
+        let registry = LanguageRegistry::from_config(&config);
+
+        assert_eq!(registry.get("de-AT").format_numbers("A-1010 Wien: 1500,00 €"), "A-1010 Wien: €\u{00A0}1500,00");
+    }
+}
@@ -0,0 +1,150 @@
+use super::numbers;
+use super::quotes::{self, QuoteMarks};
+use super::{NumberFormat, SpaceRules};
+
+pub(crate) const NBSP: &str = "\u{00A0}";     // Non-breaking space
+pub(crate) const NNBSP: &str = "\u{202F}";    // Narrow no-break space
+pub(crate) const ELLIPSIS: &str = "\u{2026}"; // Ellipsis …
+pub(crate) const EMDASH: &str = "\u{2014}";   // Em dash —
+
+/// Units that take a (narrow) no-break space after the number
+pub(crate) const UNITS: &[&str] = &[
+    "kg", "g", "mg", "t",           // Weight
+    "km", "m", "cm", "mm",          // Length
+    "l", "ml", "cl", "dl",          // Volume
+    "h", "min", "s", "ms",          // Time
+    "°C", "°F", "K",                // Temperature
+    "kW", "MW", "W",                // Power
+    "V", "A", "Ω",                  // Electrical
+    "bar", "Pa", "hPa",             // Pressure
+];
+
+/// Typography rules for one language
+///
+/// Only the quotation marks are required; every other step defaults to
+/// the shared implementation driven by `replacements`, `abbreviations`
+/// and `space_rules`. Languages with rules that do not fit those tables
+/// override the step itself.
+pub trait TypographyRules: Send + Sync {
+    /// Primary quotation marks
+    fn quotes(&self) -> (&str, &str);
+
+    /// Secondary quotation marks (used inside primary ones)
+    fn single_quotes(&self) -> (&str, &str);
+
+    /// The ASCII quote that stands for primary quotation marks at the top level
+    fn primary_quote(&self) -> char {
+        '"'
+    }
+
+    /// Whether words are separated by spaces; quotes in languages without
+    /// (Chinese, Japanese) are paired by order instead of by context
+    fn word_spaces(&self) -> bool {
+        true
+    }
+
+    /// Text replacements; patterns containing `(` are regular expressions
+    fn replacements(&self) -> Vec<(&str, &str)> {
+        vec![("...", ELLIPSIS), ("--", EMDASH), (" - ", " \u{2013} ")]
+    }
+
+    /// Abbreviations whose spaces become `space_rules.after_abbr`
+    /// ("Dr. " also binds the following word)
+    fn abbreviations(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Spaces used between numbers, units, currencies and after abbreviations
+    fn space_rules(&self) -> Option<SpaceRules> {
+        None
+    }
+
+    /// Decimal separator, grouping and currency placement
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat::default()
+    }
+
+    fn apply_replacements(&self, text: &str) -> String {
+        replace_all(text, &self.replacements())
+    }
+
+    fn format_abbreviations(&self, text: &str) -> String {
+        let space = self.space_rules().map(|rules| rules.after_abbr).unwrap_or_else(|| NBSP.to_string());
+        join_abbreviations(text, &self.abbreviations(), &space)
+    }
+
+    fn format_numbers(&self, text: &str) -> String {
+        match self.space_rules() {
+            Some(rules) => numbers::format(text, &rules, &self.number_format()),
+            None => text.to_string(),
+        }
+    }
+
+    fn apply_quotes(&self, text: &str) -> String {
+        let marks = QuoteMarks {
+            primary: self.quotes(),
+            secondary: self.single_quotes(),
+            primary_char: self.primary_quote(),
+        };
+        if self.word_spaces() { quotes::apply(text, &marks) } else { quotes::pair(text, &marks) }
+    }
+
+    /// Run every step: replacements, abbreviations, numbers, quotes
+    fn apply_all(&self, text: &str) -> String {
+        let result = self.apply_replacements(text);
+        let result = self.format_abbreviations(&result);
+        let result = self.format_numbers(&result);
+        self.apply_quotes(&result)
+    }
+}
+
+/// Rules for languages without an entry of their own
+pub struct DefaultRules;
+
+impl TypographyRules for DefaultRules {
+    fn quotes(&self) -> (&str, &str) {
+        ("\u{201C}", "\u{201D}")
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        ("\u{2018}", "\u{2019}")
+    }
+}
+
+/// Apply replacements in order; patterns containing `(` are regular expressions
+pub fn replace_all(text: &str, replacements: &[(&str, &str)]) -> String {
+    let mut result = text.to_string();
+
+    for (pattern, replacement) in replacements {
+        if pattern.contains('(') {
+            if let Ok(re) = regex::Regex::new(pattern) {
+                result = re.replace_all(&result, *replacement).to_string();
+            }
+        } else {
+            result = result.replace(pattern, replacement);
+        }
+    }
+
+    result
+}
+
+/// Replace the spaces inside each abbreviation with `space`
+pub fn join_abbreviations(text: &str, abbreviations: &[&str], space: &str) -> String {
+    let mut result = text.to_string();
+
+    for abbreviation in abbreviations {
+        result = result.replace(abbreviation, &abbreviation.replace(' ', space));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_steps() {
+        assert_eq!(DefaultRules.apply_all("\"Wait...\" -- 'now'"), "\u{201C}Wait\u{2026}\u{201D} \u{2014} \u{2018}now\u{2019}");
+    }
+}
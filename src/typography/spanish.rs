@@ -0,0 +1,86 @@
+use super::rules::{TypographyRules, ELLIPSIS, EMDASH, NBSP, NNBSP};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
+
+// Unicode constants for Spanish typography
+const LAQUO: &str = "\u{00AB}";    // Left guillemet «
+const RAQUO: &str = "\u{00BB}";    // Right guillemet »
+const LDQUO: &str = "\u{201C}";    // Left double quote "
+const RDQUO: &str = "\u{201D}";    // Right double quote "
+
+/// Spanish typography rules following the RAE (angular quotes, em dash
+/// dialogue, inverted question and exclamation marks)
+pub struct SpanishRules;
+
+impl TypographyRules for SpanishRules {
+    fn quotes(&self) -> (&str, &str) {
+        (LAQUO, RAQUO)
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        (LDQUO, RDQUO)
+    }
+
+    fn replacements(&self) -> Vec<(&str, &str)> {
+        vec![
+            // Dialogue: a dash opening a line becomes an em dash set close to the speech
+            ("(^|\n)[ \t]*(?:--|-|\u{2014})[ \t]*", "$1\u{2014}"),
+
+            // ¿ and ¡ are set close to the sentence they open
+            (r"([¿¡])\s+", "$1"),
+
+            // Three dots to ellipsis
+            ("...", ELLIPSIS),
+
+            // Double hyphen to em dash
+            ("--", EMDASH),
+
+            // Single hyphen with spaces to en dash
+            (" - ", " \u{2013} "),
+        ]
+    }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        vec!["p. ej.", "Sr. ", "Sra. ", "Srta. ", "Dr. ", "Dra. ", "D. ", "D.ª "]
+    }
+
+    /// The RAE groups thousands with a thin space, never with a point
+    fn space_rules(&self) -> Option<SpaceRules> {
+        Some(SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: NNBSP.to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        })
+    }
+
+    /// Decimal comma, 29,99 € and no grouping below five digits (RAE)
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            decimal: ",".to_string(),
+            currency: CurrencyPosition::After,
+            group_from: 5,
+            ..NumberFormat::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialogue() {
+        assert_eq!(
+            SpanishRules.apply_all("-- ¿ Vienes?\n- ¡Claro!"),
+            "\u{2014}¿Vienes?\n\u{2014}¡Claro!"
+        );
+    }
+
+    #[test]
+    fn test_quotes_and_numbers() {
+        assert_eq!(
+            SpanishRules.apply_all("Dijo \"cuesta 25000 €\""),
+            format!("Dijo {}cuesta 25{}000{}€{}", LAQUO, NNBSP, NBSP, RAQUO)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use super::rules::{TypographyRules, ELLIPSIS, NBSP, NNBSP};
+use super::{CurrencyPosition, NumberFormat, SpaceRules};
+
+// Unicode constants for Swedish typography
+const RDQUO: &str = "\u{201D}";    // Right double quote ”, opens and closes
+const RSQUO: &str = "\u{2019}";    // Right single quote ’, opens and closes
+
+/// Swedish typography rules (”…” quotes, thin space thousands grouping)
+pub struct SwedishRules;
+
+impl TypographyRules for SwedishRules {
+    fn quotes(&self) -> (&str, &str) {
+        (RDQUO, RDQUO)
+    }
+
+    fn single_quotes(&self) -> (&str, &str) {
+        (RSQUO, RSQUO)
+    }
+
+    fn replacements(&self) -> Vec<(&str, &str)> {
+        vec![
+            // Three dots to ellipsis
+            ("...", ELLIPSIS),
+
+            // Swedish uses a spaced en dash for dashes
+            ("--", " \u{2013} "),
+            (" - ", " \u{2013} "),
+        ]
+    }
+
+    fn abbreviations(&self) -> Vec<&str> {
+        vec!["t. ex.", "bl. a.", "m. m.", "s. k."]
+    }
+
+    fn space_rules(&self) -> Option<SpaceRules> {
+        Some(SpaceRules {
+            before_unit: NBSP.to_string(),
+            in_number: NNBSP.to_string(),
+            before_currency: NBSP.to_string(),
+            after_abbr: NBSP.to_string(),
+        })
+    }
+
+    /// Decimal comma, 29,99 kr and no grouping below five digits
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat {
+            decimal: ",".to_string(),
+            currency: CurrencyPosition::After,
+            group_from: 5,
+            ..NumberFormat::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swedish() {
+        assert_eq!(
+            SwedishRules.apply_all("\"Det kostar 250000 kr\""),
+            format!("{}Det kostar 250{}000{}kr{}", RDQUO, NNBSP, NBSP, RDQUO)
+        );
+    }
+}
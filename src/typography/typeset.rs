@@ -0,0 +1,539 @@
+use anyhow::{anyhow, Result};
+use lol_html::html_content::{ContentType, Element, TextType};
+use lol_html::{doc_text, element, end_tag, rewrite_str, RewriteStrSettings};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use super::hyphenate::SHY;
+use super::widows::{self, BlockKind};
+use super::{Hyphenator, LanguageRegistry, WidowSettings};
+
+/// Elements whose content is never typeset (code-like or raw text)
+const SKIP_ELEMENTS: &[&str] = &["script", "style", "textarea", "code", "pre", "kbd", "samp", "var"];
+
+/// Elements that continue the surrounding text run instead of starting their own
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "cite", "data", "del", "dfn", "em", "i", "ins",
+    "mark", "q", "s", "small", "span", "strong", "sub", "sup", "time", "u", "wbr",
+];
+
+/// Elements the HTML parser closes implicitly when a sibling of the same name opens
+const IMPLICIT_CLOSE: &[&str] = &["p", "li", "dt", "dd", "option", "tr", "td", "th"];
+
+/// Attribute set on processed `r-s` elements so the runtime engine leaves them alone
+pub const TYPESET_ATTRIBUTE: &str = "data-typeset";
+
+/// Optional steps after the language rules
+#[derive(Default)]
+pub struct TypesetOptions<'a> {
+    /// Insert soft hyphens into long words, unless an enclosing `r-s` sets
+    /// `text="hyphenate:false"`
+    pub hyphenator: Option<&'a Hyphenator>,
+    /// Join the last words of headings and short paragraphs
+    pub widows: Option<&'a WidowSettings>,
+}
+
+/// Separates the text nodes of a run while the rules see it as one string
+const NODE_BREAK: char = '\u{E000}';
+
+/// Stands in for skipped content (e.g. `<code>`) inside a run
+const OPAQUE: char = '\u{FFFC}';
+
+/// Private use characters standing in for entities the rules must not touch
+const PLACEHOLDER_BASE: u32 = 0xF0000;
+
+/// Inherited state of one open element
+#[derive(Debug, Clone)]
+struct Frame {
+    id: usize,
+    tag: String,
+    lang: Option<String>,
+    filter: Option<String>,
+    /// `text="hyphenate:false"` turns off soft hyphens
+    hyphenate: bool,
+    skip: bool,
+    /// Starts its own text run (block elements and language or filter changes)
+    block: bool,
+    kind: BlockKind,
+    /// The run text directly inside this block currently belongs to
+    run: Option<usize>,
+}
+
+impl Frame {
+    fn typeset(&self) -> bool {
+        !self.skip && matches!(self.filter.as_deref(), Some("smart" | "professional"))
+    }
+}
+
+/// Text that is typeset as one string, across inline elements
+#[derive(Debug)]
+struct Run {
+    lang: String,
+    hyphenate: bool,
+    kind: BlockKind,
+    items: Vec<RunItem>,
+}
+
+#[derive(Debug)]
+enum RunItem {
+    /// Index of a text node in document order
+    Text(usize),
+    /// Skipped element between text nodes
+    Opaque,
+}
+
+/// What the first pass learns about the document
+#[derive(Default)]
+struct Scan {
+    stack: Vec<Frame>,
+    next_id: usize,
+    /// Elements seen so far, in document order
+    elements: usize,
+    /// Element indices that get `data-typeset`
+    marked: HashSet<usize>,
+    /// Raw text of every text node in document order (None if not typeset)
+    nodes: Vec<Option<String>>,
+    runs: Vec<Run>,
+    /// Text of the current text node, collected across chunks
+    buffer: String,
+}
+
+impl Scan {
+    fn current(&self) -> Option<&Frame> {
+        self.stack.last()
+    }
+
+    /// The run the next text (or skipped element) in the current block belongs to
+    fn current_run(&mut self) -> usize {
+        let Some(block) = self.stack.iter().rposition(|frame| frame.block) else {
+            // Text outside any element (fragments) runs together
+            if self.runs.is_empty() {
+                self.runs.push(Run { lang: String::new(), hyphenate: true, kind: BlockKind::Other, items: Vec::new() });
+            }
+            return 0;
+        };
+        if let Some(run) = self.stack[block].run {
+            return run;
+        }
+
+        let lang = self.stack[block].lang.clone().unwrap_or_default();
+        let hyphenate = self.stack[block].hyphenate;
+        let kind = self.stack[block].kind;
+        self.runs.push(Run { lang, hyphenate, kind, items: Vec::new() });
+        let run = self.runs.len() - 1;
+        self.stack[block].run = Some(run);
+        run
+    }
+
+    /// Close frames from `index` up; text after a closed block starts a new run
+    fn close(&mut self, index: usize) {
+        let closed_block = self.stack[index..].iter().any(|frame| frame.block);
+        self.stack.truncate(index);
+        if closed_block && let Some(parent) = self.stack.iter_mut().rev().find(|frame| frame.block) {
+            parent.run = None;
+        }
+    }
+}
+
+/// Apply the language rules to the text of every `r-s` element with
+/// `text="filter:smart"` or `text="filter:professional"` (and its descendants)
+///
+/// The rules come from the registry entry for the nearest `lang` attribute
+/// and see the text of a block as a whole, so a quote opened in `<em>` and
+/// closed after it pairs up. Markup, attributes and comments are left as is,
+/// as is everything in code-like elements and under `translate="no"` or
+/// `text="filter:none"`.
+///
+/// See `TypesetOptions` for soft hyphens and widow prevention.
+pub fn typeset_html(html: &str, languages: &LanguageRegistry, options: &TypesetOptions) -> Result<String> {
+    let scan = scan(html)?;
+    let output = typeset_runs(&scan, languages, options);
+    rewrite(html, &scan.marked, output)
+}
+
+/// First pass: collect text nodes into runs
+fn scan(html: &str) -> Result<Scan> {
+    let scan = Rc::new(RefCell::new(Scan::default()));
+    let element_scan = scan.clone();
+    let text_scan = scan.clone();
+
+    let settings = RewriteStrSettings::new()
+        .append_element_content_handler(element!("*", move |el| {
+            open_element(el, &element_scan)
+        }))
+        .append_document_content_handler(doc_text!(move |chunk| {
+            if chunk.text_type() != TextType::Data {
+                return Ok(());
+            }
+
+            let mut scan = text_scan.borrow_mut();
+            scan.buffer.push_str(chunk.as_str());
+            if !chunk.last_in_text_node() {
+                return Ok(());
+            }
+
+            let text = std::mem::take(&mut scan.buffer);
+            let index = scan.nodes.len();
+            if scan.current().is_some_and(Frame::typeset) {
+                let run = scan.current_run();
+                scan.runs[run].items.push(RunItem::Text(index));
+                scan.nodes.push(Some(text));
+            } else {
+                scan.nodes.push(None);
+            }
+            Ok(())
+        }));
+
+    rewrite_str(html, settings).map_err(|e| anyhow!("Failed to typeset HTML: {}", e))?;
+
+    let scan = scan.take();
+    Ok(scan)
+}
+
+fn open_element(el: &mut Element, scan: &Rc<RefCell<Scan>>) -> lol_html::HandlerResult {
+    let tag = el.tag_name();
+    let mut scan_ref = scan.borrow_mut();
+    let element = scan_ref.elements;
+    scan_ref.elements += 1;
+
+    if IMPLICIT_CLOSE.contains(&tag.as_str())
+        && scan_ref.current().is_some_and(|frame| frame.tag == tag)
+    {
+        let index = scan_ref.stack.len() - 1;
+        scan_ref.close(index);
+    }
+
+    let parent = scan_ref.current().cloned();
+    let own_lang = el.get_attribute("lang").filter(|lang| !lang.is_empty());
+    let text = if tag == "r-s" { el.get_attribute("text") } else { None };
+    let own_filter = text.as_deref().and_then(|text| token_value(text, "filter"));
+    let own_hyphenate = text.as_deref().and_then(|text| token_value(text, "hyphenate"));
+    let opt_out = el.get_attribute("translate").is_some_and(|value| value == "no")
+        || own_filter.as_deref() == Some("none");
+
+    let lang = own_lang.clone().or_else(|| parent.as_ref().and_then(|p| p.lang.clone()));
+    let filter = own_filter.clone().or_else(|| parent.as_ref().and_then(|p| p.filter.clone()));
+    let hyphenate = match own_hyphenate.as_deref() {
+        Some(value) => value != "false",
+        None => parent.as_ref().is_none_or(|p| p.hyphenate),
+    };
+    let skip = SKIP_ELEMENTS.contains(&tag.as_str()) || opt_out || parent.as_ref().is_some_and(|p| p.skip);
+    let block = !is_inline(el, &tag) || own_lang.is_some() || own_filter.is_some() || own_hyphenate.is_some();
+    let kind = match tag.as_str() {
+        "r-s" => el.get_attribute("as").map_or(BlockKind::Other, |as_tag| BlockKind::of(&as_tag)),
+        tag => BlockKind::of(tag),
+    };
+
+    // Skipped content inside a run still separates the words around it
+    if skip && !block && parent.as_ref().is_some_and(Frame::typeset) {
+        let run = scan_ref.current_run();
+        scan_ref.runs[run].items.push(RunItem::Opaque);
+    }
+
+    if matches!(own_filter.as_deref(), Some("smart" | "professional")) && !skip {
+        scan_ref.marked.insert(element);
+    }
+
+    if !el.can_have_content() {
+        return Ok(());
+    }
+
+    let id = scan_ref.next_id;
+    scan_ref.next_id += 1;
+    scan_ref.stack.push(Frame { id, tag, lang, filter, hyphenate, skip, block, kind, run: None });
+    drop(scan_ref);
+
+    let scan = scan.clone();
+    el.on_end_tag(end_tag!(move |_| {
+        // Also drops children whose end tags were implied
+        let mut scan = scan.borrow_mut();
+        if let Some(index) = scan.stack.iter().rposition(|frame| frame.id == id) {
+            scan.close(index);
+        }
+        Ok(())
+    }))
+}
+
+fn is_inline(el: &Element, tag: &str) -> bool {
+    if tag == "r-s" {
+        return el.get_attribute("as").is_some_and(|as_tag| INLINE_ELEMENTS.contains(&as_tag.as_str()));
+    }
+    INLINE_ELEMENTS.contains(&tag) || (SKIP_ELEMENTS.contains(&tag) && tag != "pre")
+}
+
+/// Typeset every run and return the new HTML for each text node
+fn typeset_runs(scan: &Scan, languages: &LanguageRegistry, options: &TypesetOptions) -> Vec<Option<String>> {
+    let mut output: Vec<Option<String>> = vec![None; scan.nodes.len()];
+
+    for run in &scan.runs {
+        let nodes: Vec<usize> = run.items.iter()
+            .filter_map(|item| match item {
+                RunItem::Text(index) => Some(*index),
+                RunItem::Opaque => None,
+            })
+            .collect();
+        if nodes.is_empty() {
+            continue;
+        }
+
+        let mut kept = Vec::new();
+        let mut text = String::new();
+        let mut first = true;
+        for item in &run.items {
+            match item {
+                RunItem::Text(index) => {
+                    if !first {
+                        text.push(NODE_BREAK);
+                    }
+                    first = false;
+                    let raw = scan.nodes[*index].as_deref().unwrap_or_default();
+                    text.push_str(&decode_entities(raw, &mut kept));
+                }
+                RunItem::Opaque => text.push(OPAQUE),
+            }
+        }
+
+        let rules = languages.get(&run.lang);
+        let apply = |text: &str| {
+            let mut typeset = rules.apply_all(text);
+            if let Some(hyphenator) = options.hyphenator && run.hyphenate {
+                typeset = hyphenator.hyphenate(&typeset, &run.lang);
+            }
+            match options.widows {
+                Some(settings) => widows::join_last_words(&typeset, run.kind, settings),
+                None => typeset,
+            }
+        };
+        let typeset = apply(&text);
+        let segments: Vec<&str> = typeset.split(NODE_BREAK).collect();
+
+        if segments.len() == nodes.len() {
+            for (index, segment) in nodes.iter().zip(segments) {
+                output[*index] = Some(encode(segment, &kept));
+            }
+        } else {
+            // A rule swallowed a node boundary; fall back to node by node
+            for index in nodes {
+                let mut kept = Vec::new();
+                let raw = scan.nodes[index].as_deref().unwrap_or_default();
+                let text = decode_entities(raw, &mut kept);
+                output[index] = Some(encode(&apply(&text), &kept));
+            }
+        }
+    }
+
+    output
+}
+
+/// Second pass: write the typeset text nodes and mark processed elements
+fn rewrite(html: &str, marked: &HashSet<usize>, output: Vec<Option<String>>) -> Result<String> {
+    let mut elements = 0;
+    let mut node = 0;
+
+    let settings = RewriteStrSettings::new()
+        .append_element_content_handler(element!("*", move |el| {
+            if marked.contains(&elements) {
+                el.set_attribute(TYPESET_ATTRIBUTE, "")?;
+            }
+            elements += 1;
+            Ok(())
+        }))
+        .append_document_content_handler(doc_text!(move |chunk| {
+            if chunk.text_type() != TextType::Data {
+                return Ok(());
+            }
+
+            let Some(Some(text)) = output.get(node) else {
+                if chunk.last_in_text_node() {
+                    node += 1;
+                }
+                return Ok(());
+            };
+
+            if chunk.last_in_text_node() {
+                chunk.replace(text, ContentType::Html);
+                node += 1;
+            } else {
+                chunk.remove();
+            }
+            Ok(())
+        }));
+
+    rewrite_str(html, settings).map_err(|e| anyhow!("Failed to typeset HTML: {}", e))
+}
+
+/// The value of a `text` attribute token (`filter` in "filter:smart, leading:tight" -> "smart")
+fn token_value(text: &str, key: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, ',' | '[' | ']'))
+        .find_map(|token| token.strip_prefix(key)?.strip_prefix(':'))
+        .map(|value| value.to_string())
+}
+
+/// Escape typeset text for HTML, restoring kept entities
+fn encode(text: &str, kept: &[String]) -> String {
+    let mut html = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            SHY => html.push_str("&shy;"),
+            OPAQUE => {}
+            c if is_placeholder(c) => html.push_str(&kept[(c as u32 - PLACEHOLDER_BASE) as usize]),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+fn is_placeholder(c: char) -> bool {
+    (PLACEHOLDER_BASE..PLACEHOLDER_BASE + 0xFFFE).contains(&(c as u32))
+}
+
+/// Decode the entities that matter for typesetting (quotes, spaces, markup
+/// characters and numeric references); other named entities are replaced
+/// by placeholders and added to `kept` for restoring
+fn decode_entities(raw: &str, kept: &mut Vec<String>) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(start) = rest.find('&') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = rest[1..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+            .map(|i| i + 1)
+            .filter(|&i| rest[i..].starts_with(';'));
+        let Some(end) = end else {
+            text.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let entity = &rest[..=end];
+        let decoded = match &entity[1..end] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{00A0}'),
+            "shy" => Some(SHY),
+            name => name.strip_prefix('#').and_then(|number| {
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }),
+        };
+
+        match decoded {
+            // Our own marker characters never come from the document
+            Some(NODE_BREAK | OPAQUE) => text.push_str(entity),
+            Some(c) if !is_placeholder(c) => text.push(c),
+            _ if kept.len() < 0xFFFE => {
+                text.push(char::from_u32(PLACEHOLDER_BASE + kept.len() as u32).unwrap_or('\u{FFFD}'));
+                kept.push(entity.to_string());
+            }
+            _ => text.push_str(entity),
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typeset(html: &str) -> String {
+        typeset_html(html, &LanguageRegistry::default(), &TypesetOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_lang_and_filter_inheritance() {
+        let html = typeset(
+            "<html lang=\"de\"><body><r-s text=\"filter:smart\"><p>Er sagte \"Hallo\" &amp; ging.</p>\
+             <p lang=\"en-GB\">She said 'hi'.</p></r-s><p>\"Unberührt\"</p></body></html>"
+        );
+
+        assert!(html.contains("<r-s text=\"filter:smart\" data-typeset=\"\">"));
+        assert!(html.contains("Er sagte \u{201E}Hallo\u{201C} &amp; ging."));
+        assert!(html.contains("She said \u{2018}hi\u{2019}."));
+        assert!(html.contains("<p>\"Unberührt\"</p>"));
+    }
+
+    #[test]
+    fn test_markup_and_code_untouched() {
+        let html = typeset(
+            "<r-s text=\"filter:professional\" lang=\"en\"><a title=\"a 'b'\" href=\"x\">\"Link\"</a>\
+             <code>\"raw\" -- ...</code><!-- \"comment\" -- ... --><span>&copy; \"x\"</span></r-s>"
+        );
+
+        assert!(html.contains("<a title=\"a 'b'\" href=\"x\">\u{201C}Link\u{201D}</a>"));
+        assert!(html.contains("<code>\"raw\" -- ...</code>"));
+        assert!(html.contains("<!-- \"comment\" -- ... -->"));
+        assert!(html.contains("&copy; \u{201C}x\u{201D}"));
+    }
+
+    #[test]
+    fn test_quotes_across_inline_elements() {
+        let html = typeset(
+            "<r-s text=\"filter:smart\" lang=\"de\"><p><em>\"Achtung</em>, sagte sie\" und <code>x</code> \"ging\".</p></r-s>"
+        );
+
+        assert!(html.contains("<p><em>\u{201E}Achtung</em>, sagte sie\u{201C} und <code>x</code> \u{201E}ging\u{201C}.</p>"));
+    }
+
+    #[test]
+    fn test_opt_out() {
+        let html = typeset(
+            "<r-s text=\"filter:smart\" lang=\"en\"><p translate=\"no\">\"Brand\"</p>\
+             <r-s text=\"filter:none\">\"as is\"</r-s><p>\"typeset\"</p></r-s>"
+        );
+
+        assert!(html.contains("<p translate=\"no\">\"Brand\"</p>"));
+        assert!(html.contains("<r-s text=\"filter:none\">\"as is\"</r-s>"));
+        assert!(html.contains("<p>\u{201C}typeset\u{201D}</p>"));
+    }
+
+    #[test]
+    fn test_other_filters_are_left_to_css() {
+        let source = "<r-s text=\"filter:minimal\" lang=\"de\"><p>\"x\"</p></r-s>";
+        assert_eq!(typeset(source), source);
+    }
+
+    #[test]
+    fn test_soft_hyphens() {
+        let hyphenator = Hyphenator::new(&crate::typography::HyphenateSettings::default());
+        let html = typeset_html(
+            "<r-s text=\"filter:smart\" lang=\"de\"><p>Silbentrennung</p>\
+             <r-s as=\"p\" text=\"hyphenate:false\">Silbentrennung</r-s><p>Sil&shy;bentrennung</p></r-s>",
+            &LanguageRegistry::default(),
+            &TypesetOptions { hyphenator: Some(&hyphenator), ..TypesetOptions::default() },
+        ).unwrap();
+
+        assert!(html.contains("<p>Sil&shy;ben&shy;tren&shy;nung</p>"));
+        assert!(html.contains("text=\"hyphenate:false\">Silbentrennung</r-s>"));
+        assert!(html.contains("<p>Sil&shy;bentrennung</p>"));
+    }
+
+    #[test]
+    fn test_widows() {
+        let settings = WidowSettings::default();
+        let html = typeset_html(
+            "<r-s text=\"filter:smart\" lang=\"en\"><h2>Typography for <em>the web</em></h2>\
+             <r-s as=\"p\">A short paragraph.</r-s><div>Not a paragraph here</div></r-s>",
+            &LanguageRegistry::default(),
+            &TypesetOptions { widows: Some(&settings), ..TypesetOptions::default() },
+        ).unwrap();
+
+        assert!(html.contains("<h2>Typography for <em>the\u{00A0}web</em></h2>"));
+        assert!(html.contains("<r-s as=\"p\">A short\u{00A0}paragraph.</r-s>"));
+        assert!(html.contains("<div>Not a paragraph here</div>"));
+    }
+}
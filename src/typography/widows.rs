@@ -0,0 +1,114 @@
+use super::rules::NBSP;
+use super::{WidowSettings, NARROW_MEASURE};
+
+/// Block elements whose last words are kept together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Heading,
+    Paragraph,
+    Other,
+}
+
+impl BlockKind {
+    /// Kind of an element (`r-s` elements count as the element in their `as`)
+    pub fn of(tag: &str) -> Self {
+        match tag {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => BlockKind::Heading,
+            "p" => BlockKind::Paragraph,
+            _ => BlockKind::Other,
+        }
+    }
+}
+
+/// Selector for the blocks the runtime engine protects (mirrors `BlockKind::of`)
+pub const WIDOW_BLOCKS: &str = "h1, h2, h3, h4, h5, h6, p, r-s[as=\"h1\"], r-s[as=\"h2\"], r-s[as=\"h3\"], r-s[as=\"h4\"], r-s[as=\"h5\"], r-s[as=\"h6\"], r-s[as=\"p\"]";
+
+/// Join the last `settings.words` words of a heading or short paragraph
+/// with no-break spaces, so the last line never holds a single word
+///
+/// A word is a run of non-space characters with at least one letter or
+/// digit; anything else between two joined words (dashes, the typesetter's
+/// markers) stays where it is. Text with too few words, paragraphs longer
+/// than `settings.short_paragraph` characters and tails that would not fit
+/// on a `measure:narrow` line are returned unchanged.
+pub fn join_last_words(text: &str, kind: BlockKind, settings: &WidowSettings) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if settings.words < 2 || kind == BlockKind::Other {
+        return text.to_string();
+    }
+    if kind == BlockKind::Paragraph && chars.len() > settings.short_paragraph {
+        return text.to_string();
+    }
+
+    let is_space = |c: char| matches!(c, ' ' | '\t' | '\n' | '\r');
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        while i < chars.len() && !is_space(chars[i]) {
+            i += 1;
+        }
+        if chars[start..i].iter().any(|c| c.is_alphanumeric()) {
+            words.push((start, i));
+        }
+        i += 1;
+    }
+
+    if words.len() <= settings.words {
+        return text.to_string();
+    }
+    let (tail_start, _) = words[words.len() - settings.words];
+    let (last_start, tail_end) = words[words.len() - 1];
+    if tail_end - tail_start > NARROW_MEASURE {
+        return text.to_string();
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut joined = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if is_space(c) && i > tail_start && i < last_start {
+            // One no-break space per run of spaces
+            if !joined {
+                result.push_str(NBSP);
+            }
+            joined = true;
+        } else {
+            result.push(c);
+            joined = false;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_last_words() {
+        let settings = WidowSettings::default();
+
+        assert_eq!(
+            join_last_words("Typography for the web\n", BlockKind::Heading, &settings),
+            "Typography for the\u{00A0}web\n"
+        );
+        assert_eq!(
+            join_last_words("A short paragraph ends here.", BlockKind::Paragraph, &WidowSettings { words: 3, ..settings.clone() }),
+            "A short paragraph\u{00A0}ends\u{00A0}here."
+        );
+        // Nothing to protect, or not part of a heading or paragraph
+        assert_eq!(join_last_words("Two words", BlockKind::Heading, &settings), "Two words");
+        assert_eq!(join_last_words("Typography for the web", BlockKind::Other, &settings), "Typography for the web");
+    }
+
+    #[test]
+    fn test_skips_long_tails_and_paragraphs() {
+        let settings = WidowSettings::default();
+        let long = "See https://example.com/a/very/long/path/that/fills/a/line/by/itself";
+
+        assert_eq!(join_last_words(&format!("Intro {}", long), BlockKind::Heading, &settings), format!("Intro {}", long));
+        let paragraph = "word ".repeat(100) + "end";
+        assert_eq!(join_last_words(&paragraph, BlockKind::Paragraph, &settings), paragraph);
+    }
+}
@@ -0,0 +1,538 @@
+/**
+ * The parts of the DOM the runtime uses, for running it without a browser
+ *
+ * Selectors are compound only (`r-s[text*="filter:"]`, `h1, p`); anything
+ * else throws, so an engine that starts using more fails its tests instead
+ * of matching nothing.
+ */
+
+const ELEMENT_NODE = 1;
+const TEXT_NODE = 3;
+const VOID_ELEMENTS = ['br', 'hr', 'img', 'input', 'link', 'meta', 'source', 'wbr'];
+
+globalThis.window = globalThis;
+globalThis.addEventListener = () => {};
+globalThis.removeEventListener = () => {};
+globalThis.logs = [];
+globalThis.console = {
+  log: (...args) => logs.push(args.join(' ')),
+  warn: (...args) => logs.push(['warn:', ...args].join(' ')),
+  error: (...args) => logs.push(['error:', ...args].join(' ')),
+};
+
+/** `[attr op "value"]` */
+function parseAttribute(source) {
+  const match = source.match(/^\[\s*([\w-]+)\s*(?:([*^$]?=)\s*(?:"([^"]*)"|'([^']*)'|([\w-]+))\s*)?\]$/);
+  if (!match) throw new Error(`Unsupported selector part ${source}`);
+  const [, name, op, double, single, bare] = match;
+  return { name, op, value: double ?? single ?? bare };
+}
+
+/** A compound selector: tag, `#id`, `.class` and attribute tests */
+function parseCompound(source) {
+  const compound = { tag: null, attributes: [] };
+  const parts = source.match(/^[\w-]+|\*|#[\w-]+|\.[\w-]+|\[(?:[^\]"']|"[^"]*"|'[^']*')*\]/g) ?? [];
+  if (parts.join('') !== source) throw new Error(`Unsupported selector ${source}`);
+  for (const part of parts) {
+    if (part === '*') continue;
+    if (part[0] === '#') compound.attributes.push({ name: 'id', op: '=', value: part.slice(1) });
+    else if (part[0] === '.') compound.attributes.push({ name: 'class', op: '~=', value: part.slice(1) });
+    else if (part[0] === '[') compound.attributes.push(parseAttribute(part));
+    else compound.tag = part.toUpperCase();
+  }
+  return compound;
+}
+
+function parseSelector(selector) {
+  const list = [];
+  let current = '';
+  let quote = null;
+  for (const c of selector) {
+    if (quote) {
+      if (c === quote) quote = null;
+    } else if (c === '"' || c === "'") {
+      quote = c;
+    } else if (c === ',') {
+      list.push(current.trim());
+      current = '';
+      continue;
+    }
+    current += c;
+  }
+  list.push(current.trim());
+  return list.map(parseCompound);
+}
+
+function matchesCompound(element, { tag, attributes }) {
+  if (tag && element.tagName !== tag) return false;
+  return attributes.every(({ name, op, value }) => {
+    const actual = element.getAttribute(name);
+    if (actual === null) return false;
+    switch (op) {
+      case undefined: return true;
+      case '=': return actual === value;
+      case '*=': return actual.includes(value);
+      case '^=': return actual.startsWith(value);
+      case '$=': return actual.endsWith(value);
+      case '~=': return actual.split(/\s+/).includes(value);
+    }
+  });
+}
+
+class Node {
+  constructor(nodeType) {
+    this.nodeType = nodeType;
+    this.parentNode = null;
+    this.childNodes = [];
+  }
+
+  get parentElement() {
+    return this.parentNode?.nodeType === ELEMENT_NODE ? this.parentNode : null;
+  }
+
+  appendChild(node) {
+    node.remove();
+    node.parentNode = this;
+    this.childNodes.push(node);
+    return node;
+  }
+
+  append(...nodes) {
+    nodes.forEach(node => this.appendChild(typeof node === 'string' ? new Text(node) : node));
+  }
+
+  after(node) {
+    const siblings = this.parentNode.childNodes;
+    node.remove();
+    node.parentNode = this.parentNode;
+    siblings.splice(siblings.indexOf(this) + 1, 0, node);
+  }
+
+  remove() {
+    if (!this.parentNode) return;
+    const siblings = this.parentNode.childNodes;
+    siblings.splice(siblings.indexOf(this), 1);
+    this.parentNode = null;
+  }
+
+  /** Descendants in document order */
+  descendants() {
+    return this.childNodes.flatMap(child => [child, ...child.descendants()]);
+  }
+}
+
+class Text extends Node {
+  constructor(data) {
+    super(TEXT_NODE);
+    this.data = data;
+  }
+
+  get textContent() {
+    return this.data;
+  }
+
+  set textContent(data) {
+    this.data = data;
+  }
+}
+
+class Element extends Node {
+  constructor(tag) {
+    super(ELEMENT_NODE);
+    this.tagName = tag.toUpperCase();
+    this.attributes = {};
+    this.style = {};
+    this.listeners = {};
+  }
+
+  getAttribute(name) {
+    return this.attributes[name] ?? null;
+  }
+
+  setAttribute(name, value) {
+    this.attributes[name] = String(value);
+  }
+
+  hasAttribute(name) {
+    return name in this.attributes;
+  }
+
+  removeAttribute(name) {
+    delete this.attributes[name];
+  }
+
+  get id() {
+    return this.getAttribute('id') ?? '';
+  }
+
+  set id(value) {
+    this.setAttribute('id', value);
+  }
+
+  get lang() {
+    return this.getAttribute('lang') ?? '';
+  }
+
+  get children() {
+    return this.childNodes.filter(node => node.nodeType === ELEMENT_NODE);
+  }
+
+  get textContent() {
+    return this.descendants().filter(node => node.nodeType === TEXT_NODE).map(node => node.data).join('');
+  }
+
+  set textContent(text) {
+    this.childNodes.forEach(node => { node.parentNode = null; });
+    this.childNodes = [];
+    if (text) this.appendChild(new Text(text));
+  }
+
+  set innerHTML(html) {
+    this.textContent = '';
+    parseHtml(html, this);
+  }
+
+  matches(selector) {
+    return parseSelector(selector).some(compound => matchesCompound(this, compound));
+  }
+
+  closest(selector) {
+    const list = parseSelector(selector);
+    for (let element = this; element; element = element.parentElement) {
+      if (list.some(compound => matchesCompound(element, compound))) return element;
+    }
+    return null;
+  }
+
+  querySelectorAll(selector) {
+    const list = parseSelector(selector);
+    return this.descendants()
+      .filter(node => node.nodeType === ELEMENT_NODE && list.some(compound => matchesCompound(node, compound)));
+  }
+
+  querySelector(selector) {
+    return this.querySelectorAll(selector)[0] ?? null;
+  }
+
+  addEventListener(type, listener, options) {
+    (this.listeners[type] ??= []).push({ listener, capture: isCapture(options) });
+  }
+
+  removeEventListener(type, listener, options) {
+    const capture = isCapture(options);
+    this.listeners[type] = (this.listeners[type] ?? [])
+      .filter(added => added.listener !== listener || added.capture !== capture);
+  }
+
+  /** Capture from the root down, the target, then bubbling up when the event does */
+  dispatchEvent(event) {
+    event.target = this;
+    const ancestors = [];
+    for (let node = this.parentNode; node; node = node.parentNode) ancestors.push(node);
+
+    const invoke = (node, phase) => {
+      for (const { listener, capture } of [...(node.listeners?.[event.type] ?? [])]) {
+        if (event.immediatePropagationStopped) return;
+        if ((phase === 'capture' && !capture) || (phase === 'bubble' && capture)) continue;
+        event.currentTarget = node;
+        listener.call(node, event);
+      }
+    };
+    for (const node of [...ancestors].reverse()) {
+      if (event.propagationStopped) break;
+      invoke(node, 'capture');
+    }
+    if (!event.propagationStopped) invoke(this, 'target');
+    for (const node of event.bubbles ? ancestors : []) {
+      if (event.propagationStopped) break;
+      invoke(node, 'bubble');
+    }
+    return !event.defaultPrevented;
+  }
+
+  focus() {
+    document.activeElement = this;
+  }
+}
+
+function isCapture(options) {
+  return typeof options === 'boolean' ? options : Boolean(options?.capture);
+}
+
+/** Submissions that went ahead without JS, by form id */
+globalThis.submitted = [];
+
+/** `<form>`: fields, reset, and submission with constraint validation */
+class Form extends Element {
+  get action() {
+    return this.getAttribute('action') ?? '';
+  }
+
+  get fields() {
+    return this.querySelectorAll('input, select, textarea');
+  }
+
+  get elements() {
+    const fields = this.fields;
+    return Object.assign(fields, { namedItem: name => fields.find(field => field.name === name) ?? null });
+  }
+
+  reset() {
+    this.fields.forEach(field => { delete field.currentValue; });
+  }
+
+  /** `invalid` fires on each invalid field */
+  checkValidity() {
+    return this.fields.map(field => field.checkValidity()).every(Boolean);
+  }
+
+  reportValidity() {
+    return this.checkValidity();
+  }
+
+  requestSubmit() {
+    if (!this.hasAttribute('novalidate') && !this.checkValidity()) return;
+    if (this.dispatchEvent(new Event('submit', { bubbles: true, cancelable: true }))) {
+      submitted.push(this.id);
+    }
+  }
+}
+
+/** `<input>`, `<select>` and `<textarea>`; validation knows `required` and `type="email"` */
+class Field extends Element {
+  get name() {
+    return this.getAttribute('name') ?? '';
+  }
+
+  get type() {
+    return this.getAttribute('type')?.toLowerCase() ?? (this.tagName === 'INPUT' ? 'text' : this.tagName.toLowerCase());
+  }
+
+  get value() {
+    return this.currentValue ?? (this.tagName === 'TEXTAREA' ? this.textContent : this.getAttribute('value') ?? '');
+  }
+
+  set value(value) {
+    this.currentValue = String(value);
+  }
+
+  get form() {
+    return this.closest('form');
+  }
+
+  setCustomValidity(message) {
+    this.customMessage = message;
+  }
+
+  /** With Chrome's wording */
+  get validationMessage() {
+    if (this.customMessage) return this.customMessage;
+    if (this.hasAttribute('required') && !this.value) return 'Please fill out this field.';
+    if (this.type === 'email' && this.value && !/^[^@\s]+@[^@\s]+$/.test(this.value)) {
+      return `Please include an '@' in the email address. '${this.value}' is missing an '@'.`;
+    }
+    return '';
+  }
+
+  get validity() {
+    return { valid: !this.validationMessage, customError: Boolean(this.customMessage) };
+  }
+
+  checkValidity() {
+    if (this.validity.valid) return true;
+    this.dispatchEvent(new Event('invalid', { cancelable: true }));
+    return false;
+  }
+}
+
+function createElement(tag) {
+  switch (tag.toLowerCase()) {
+    case 'form': return new Form(tag);
+    case 'input': case 'select': case 'textarea': return new Field(tag);
+    default: return new Element(tag);
+  }
+}
+
+globalThis.Event = class Event {
+  constructor(type, { bubbles = false, cancelable = false } = {}) {
+    this.type = type;
+    this.bubbles = bubbles;
+    this.cancelable = cancelable;
+    this.defaultPrevented = false;
+    this.target = null;
+  }
+
+  preventDefault() {
+    if (this.cancelable) this.defaultPrevented = true;
+  }
+
+  stopPropagation() {
+    this.propagationStopped = true;
+  }
+
+  stopImmediatePropagation() {
+    this.propagationStopped = this.immediatePropagationStopped = true;
+  }
+};
+
+globalThis.CustomEvent = class CustomEvent extends Event {
+  constructor(type, options = {}) {
+    super(type, options);
+    this.detail = options.detail ?? null;
+  }
+};
+
+/** Named, enabled fields other than buttons and files */
+globalThis.FormData = class FormData {
+  constructor(form) {
+    this.entries = (form?.fields ?? [])
+      .filter(field => field.name && !field.hasAttribute('disabled') && !['submit', 'button', 'file'].includes(field.type))
+      .map(field => [field.name, field.value]);
+  }
+
+  append(name, value) {
+    this.entries.push([name, value]);
+  }
+
+  get(name) {
+    return this.entries.find(([added]) => added === name)?.[1] ?? null;
+  }
+
+  forEach(callback) {
+    this.entries.forEach(([name, value]) => callback(value, name, this));
+  }
+};
+
+globalThis.URLSearchParams = class URLSearchParams {
+  constructor() {
+    this.entries = [];
+  }
+
+  append(name, value) {
+    this.entries.push([name, value]);
+  }
+
+  toString() {
+    return this.entries.map(pair => pair.map(encodeURIComponent).join('=')).join('&');
+  }
+};
+
+/** `signal.aborted` and its `abort` listeners; fetch does not watch it */
+globalThis.AbortController = class AbortController {
+  constructor() {
+    const listeners = [];
+    this.signal = {
+      aborted: false,
+      addEventListener: (type, listener) => listeners.push(listener),
+      removeEventListener: (type, listener) => listeners.includes(listener) && listeners.splice(listeners.indexOf(listener), 1),
+    };
+    this.listeners = listeners;
+  }
+
+  abort() {
+    this.signal.aborted = true;
+    this.listeners.forEach(listener => listener(new Event('abort')));
+  }
+};
+
+/** Tags, quoted attributes and text; enough for test markup */
+function parseHtml(html, root) {
+  let parent = root;
+  const tokens = html.match(/<\/?[\w-]+(?:\s+[\w-]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+))?)*\s*\/?>|[^<]+/g) ?? [];
+  for (const token of tokens) {
+    if (token.startsWith('</')) {
+      parent = parent.parentNode;
+    } else if (token.startsWith('<')) {
+      const tag = token.match(/^<([\w-]+)/)[1];
+      const element = createElement(tag);
+      // Quoted values are consumed whole, so their contents are never names
+      const attributes = token.slice(tag.length + 1).matchAll(/([\w-]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>"']+)))?/g);
+      for (const [, name, double, single, bare] of attributes) {
+        element.setAttribute(name, double ?? single ?? bare ?? '');
+      }
+      parent.appendChild(element);
+      if (!token.endsWith('/>') && !VOID_ELEMENTS.includes(element.tagName.toLowerCase())) {
+        parent = element;
+      }
+    } else {
+      parent.appendChild(new Text(token));
+    }
+  }
+}
+
+globalThis.NodeFilter = { SHOW_ELEMENT: 1, SHOW_TEXT: 4, FILTER_ACCEPT: 1, FILTER_REJECT: 2, FILTER_SKIP: 3 };
+
+const html = new Element('html');
+const head = html.appendChild(new Element('head'));
+const body = html.appendChild(new Element('body'));
+
+globalThis.document = {
+  readyState: 'complete',
+  currentScript: null,
+  documentElement: html,
+  head,
+  body,
+  activeElement: null,
+  createElement,
+  createTextNode: data => new Text(data),
+  getElementById: id => html.querySelectorAll('[id]').find(element => element.id === id) ?? null,
+  querySelectorAll: selector => html.querySelectorAll(selector),
+  querySelector: selector => html.querySelector(selector),
+  addEventListener: (type, listener, options) => html.addEventListener(type, listener, options),
+  removeEventListener: (type, listener, options) => html.removeEventListener(type, listener, options),
+
+  /** Text nodes only, which is all the runtime walks */
+  createTreeWalker(root, whatToShow, filter) {
+    const nodes = root.descendants().filter(node =>
+      node.nodeType === TEXT_NODE && (filter?.acceptNode(node) ?? NodeFilter.FILTER_ACCEPT) === NodeFilter.FILTER_ACCEPT);
+    let index = 0;
+    return { nextNode: () => nodes[index++] ?? null };
+  },
+};
+
+globalThis.MutationObserver = class {
+  observe() {}
+  disconnect() {}
+};
+globalThis.IntersectionObserver = class {
+  observe() {}
+  unobserve() {}
+  disconnect() {}
+};
+globalThis.matchMedia = query => ({ media: query, matches: false, addEventListener() {}, removeEventListener() {} });
+
+const storage = {};
+globalThis.localStorage = {
+  getItem: key => storage[key] ?? null,
+  setItem: (key, value) => { storage[key] = String(value); },
+  removeItem: key => { delete storage[key]; },
+};
+
+// Timers never fire: nothing the tests check waits for one
+globalThis.setTimeout = () => 0;
+globalThis.clearTimeout = () => {};
+globalThis.requestAnimationFrame = () => 0;
+globalThis.queueMicrotask ??= callback => Promise.resolve().then(callback);
+
+// Offline, except for what a test serves: `served[url] = { status, statusText, body }`,
+// or a function of the request returning one; requests are kept in `fetched`
+globalThis.served = {};
+globalThis.fetched = [];
+globalThis.fetch = async (url, init = {}) => {
+  fetched.push({ url: String(url), method: init.method ?? 'GET', body: init.body ?? null });
+  let response = served[url];
+  if (typeof response === 'function') response = await response(init);
+  if (!response) throw new Error(`fetch ${url}: offline`);
+
+  const { status = 200, statusText = '', body = '' } = response;
+  const json = typeof body !== 'string';
+  const text = json ? JSON.stringify(body) : body;
+  return {
+    ok: status >= 200 && status < 300,
+    status,
+    statusText,
+    headers: { get: name => name.toLowerCase() === 'content-type' ? (json ? 'application/json' : 'text/plain') : null },
+    text: async () => text,
+    json: async () => JSON.parse(text),
+  };
+};
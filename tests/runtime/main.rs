@@ -0,0 +1,407 @@
+//! Runs the generated runtime in an embedded JS engine, on the minimal DOM
+//! of dom.js, and checks what it does against the Rust side of the build
+
+use boa_engine::{Context, Source};
+use serde_json::{json, Value};
+use reedstyle::builder::VERSION;
+use reedstyle::config::{ApiConfig, BridgeConfig, ColorsConfig, ComponentsConfig, FontsConfig, ThemesConfig};
+use reedstyle::js::engines::Engine;
+use reedstyle::js::{self, RuntimeData};
+use reedstyle::tokens::DesignTokens;
+use reedstyle::typography::{LanguageRegistry, TypographyConfig, WidowSettings};
+
+const DOM: &str = include_str!("dom.js");
+
+/// The configuration compiled into a test runtime
+struct Project {
+    components: ComponentsConfig,
+    bridge: BridgeConfig,
+    tokens: DesignTokens,
+    languages: LanguageRegistry,
+    widows: WidowSettings,
+    themes: ThemesConfig,
+    api: ApiConfig,
+}
+
+impl Project {
+    fn new(components: &str, bridge: &str) -> Self {
+        Self {
+            components: serde_yaml::from_str(components).unwrap(),
+            bridge: serde_yaml::from_str(bridge).unwrap(),
+            tokens: DesignTokens::resolve(&ColorsConfig::default(), &FontsConfig::default()).unwrap(),
+            languages: LanguageRegistry::from_config(&TypographyConfig::default()),
+            widows: WidowSettings::default(),
+            themes: ThemesConfig::default(),
+            api: ApiConfig::default(),
+        }
+    }
+
+    fn data(&self) -> RuntimeData<'_> {
+        RuntimeData {
+            components: &self.components,
+            bridge: &self.bridge,
+            tokens: &self.tokens,
+            languages: &self.languages,
+            widows: &self.widows,
+            themes: &self.themes,
+            api: &self.api,
+        }
+    }
+}
+
+/// A page with `body` that loads reedstyle.js as a plain `<script>`
+struct Page {
+    context: Context,
+}
+
+impl Page {
+    fn load(engines: &[Engine], project: &Project, body: &str) -> Self {
+        let script = js::generate(engines, &project.data()).unwrap().script;
+        let mut page = Page { context: Context::default() };
+        page.run(DOM);
+        page.run(&format!("document.body.innerHTML = {};", serde_json::to_string(body).unwrap()));
+        page.run(&script);
+        assert!(page.logs().contains(&"ReedSTYLE initialized".into()), "not initialized: {:?}", page.logs());
+        page
+    }
+
+    /// Run a script, then the promise jobs it queued
+    fn run(&mut self, source: &str) {
+        if let Err(error) = self.context.eval(Source::from_bytes(source)) {
+            panic!("{}\nin\n{}", error, &source[..source.len().min(500)]);
+        }
+        self.context.run_jobs();
+    }
+
+    /// The value of an expression, through JSON
+    fn eval(&mut self, expression: &str) -> Value {
+        let source = format!("JSON.stringify({}) ?? 'null'", expression);
+        let value = match self.context.eval(Source::from_bytes(&source)) {
+            Ok(value) => value,
+            Err(error) => panic!("{} in {}", error, expression),
+        };
+        let json = value.to_string(&mut self.context).unwrap().to_std_string_escaped();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    /// `console` output so far
+    fn logs(&mut self) -> Vec<Value> {
+        self.eval("logs").as_array().unwrap().clone()
+    }
+}
+
+#[test]
+fn test_components_are_applied() {
+    let project = Project::new(r#"
+components:
+  card:
+    element: article
+    box: "[padding:4]"
+    face: "[bg:base-0, radius:lg]"
+  card-featured:
+    extends: card
+    face: "[bg:brand-a]"
+  quote:
+    text: "[font:serif] </script> \"${alert(1)}\" '"
+"#, "bridge: {}");
+    let mut page = Page::load(&[Engine::Components], &project, concat!(
+        r#"<r-s as="card" id="card"></r-s>"#,
+        r#"<r-s as="card-featured" id="featured"></r-s>"#,
+        r#"<r-s as="card-featured" id="own" box="[padding:8]"></r-s>"#,
+        r#"<r-s as="quote" id="quote"></r-s>"#,
+        r#"<r-s as="unknown" id="unknown"></r-s>"#,
+    ));
+    let attributes = |page: &mut Page, id: &str| page.eval(&format!("document.getElementById('{}').attributes", id));
+
+    assert_eq!(attributes(&mut page, "card"), json!({
+        "as": "card", "id": "card", "box": "[padding:4]", "face": "[bg:base-0, radius:lg]",
+    }));
+    // The base fills in what the component leaves out
+    assert_eq!(attributes(&mut page, "featured"), json!({
+        "as": "card-featured", "id": "featured", "box": "[padding:4]", "face": "[bg:brand-a]",
+    }));
+    // Attributes on the element win
+    assert_eq!(attributes(&mut page, "own")["box"], "[padding:8]");
+    assert_eq!(attributes(&mut page, "quote")["text"], project.components.components["quote"].text.as_deref().unwrap());
+    assert_eq!(attributes(&mut page, "unknown"), json!({ "as": "unknown", "id": "unknown" }));
+
+    assert_eq!(page.eval("ReedStyle.components.resolve('card-featured')"), json!({
+        "element": "article", "extends": "card", "box": "[padding:4]", "face": "[bg:brand-a]",
+    }));
+
+    // Registered at runtime, applied to elements already on the page
+    page.run("ReedStyle.components.register('unknown', { extends: 'card', fx: '[hover:lift]' });");
+    assert_eq!(attributes(&mut page, "unknown")["box"], "[padding:4]");
+    assert_eq!(attributes(&mut page, "unknown")["fx"], "[hover:lift]");
+}
+
+#[test]
+fn test_components_runtime_api() {
+    let project = Project::new("components:\n  card:\n    box: \"[padding:4]\"\n    face: \"[bg:base-0]\"\n", "bridge: {}");
+    let mut page = Page::load(&[Engine::Components], &project, "");
+
+    page.run(r#"
+        globalThis.clicks = 0;
+        globalThis.created = ReedStyle.createElement('card', {
+            id: 'created', face: '[bg:brand-a]', 'data-count': 1, hidden: undefined, onClick: () => clicks++,
+            children: ['Intro ', { as: 'heading', text: 'Title' }, { as: 'badge', text: '[size:small]', content: 'New' }],
+        });
+        created.dispatchEvent({ type: 'click' });
+    "#);
+    // Props win over the component; `text` without brackets is the content
+    assert_eq!(page.eval("created.attributes"), json!({
+        "as": "card", "id": "created", "face": "[bg:brand-a]", "data-count": "1", "box": "[padding:4]",
+    }));
+    assert_eq!(page.eval("created.childNodes.map(node => node.nodeType === 3 ? node.data : [node.attributes, node.textContent])"), json!([
+        "Intro ",
+        [{ "as": "heading" }, "Title"],
+        [{ "as": "badge", "text": "[size:small]" }, "New"],
+    ]));
+    assert_eq!(page.eval("clicks"), 1);
+
+    page.run(r#"
+        globalThis.store = ReedStyle.state.create({ count: 0, name: 'a' });
+        globalThis.seen = [];
+        const unsubscribe = store.subscribe((state, previous) => seen.push([state.count, previous.count]));
+        store.set({ count: 1 });
+        store.set({ count: 2 });
+        unsubscribe();
+        store.set({ count: 3 });
+        ReedStyle.state.set({ theme: 'dark' });
+    "#);
+    assert_eq!(page.eval("seen"), json!([[1, 0], [2, 1]]));
+    assert_eq!(page.eval("store.get()"), json!({ "count": 3, "name": "a" }));
+    assert_eq!(page.eval("ReedStyle.state.get()"), json!({ "theme": "dark" }));
+
+    page.run(r#"
+        served['/components.yaml'] = { body: [
+            '# Loaded at runtime',
+            'components:',
+            '  # Cards',
+            '  card:',
+            '    box: "[padding:8]"   # a comment after a quoted value',
+            "    face: '[bg:brand-a] # inside quotes'",
+            '    text: [font:serif]  # a comment after a plain value',
+            '    layout: a#b',
+            '    unknown: ignored',
+            '  badge:',
+            '    extends: card',
+            'other:',
+            '  card:',
+            '    box: elsewhere',
+        ].join('\n') };
+        ReedStyle.components.load('/components.yaml').then(loaded => { globalThis.loaded = loaded; });
+    "#);
+    assert_eq!(page.eval("loaded"), json!({
+        "card": { "box": "[padding:8]", "face": "[bg:brand-a] # inside quotes", "text": "[font:serif]", "layout": "a#b" },
+        "badge": { "extends": "card" },
+    }));
+    assert_eq!(page.eval("ReedStyle.components.resolve('badge').box"), "[padding:8]");
+
+    page.run(r#"
+        served['/gone.yaml'] = { status: 404, statusText: 'Not Found' };
+        ReedStyle.init({ components: '/gone.yaml' });
+        ReedStyle.init({ components: '/offline.yaml' });
+    "#);
+    let logs = page.logs();
+    assert!(logs.contains(&"warn: Components not loaded: Cannot load /gone.yaml: 404 Not Found".into()), "{:?}", logs);
+    assert!(logs.contains(&"warn: Components not loaded: fetch /offline.yaml: offline".into()), "{:?}", logs);
+}
+
+#[test]
+fn test_typography_matches_rust_rules() {
+    let samples = [
+        r#"Er sagte "Hallo Welt" und ging."#,
+        r#"Sie fragte: "Was meint 'man' damit?""#,
+        r#""Er sagte 'Hallo' und "ging".""#,
+        "Warte... gleich -- oder nicht.",
+        "Die Seiten 10-20 und 30 - 40.",
+        r#"Il a dit "bonjour" et il est parti."#,
+        "the '90s and the students' books",
+        r#"'Hello' isn't it, she said, "Wait!""#,
+        r#"("Route 66") and [ "spaced" ]"#,
+        "Wie geht's? Dr. Meier, z. B. um 9 Uhr",
+        r#"He is 6' 2" tall, the screen 12" wide"#,
+        r#"彼は"こんにちは'世界'"と言った。"don't""#,
+        "他说,我们走吧!现在...",
+        r#"Mówił "w domu" i "dość"; "Ahoj", řekl"#,
+        r#"¿Dónde? "Aquí", dijo -- y se fue"#,
+        r#"Er sagte "<em>Hallo</em>" und <b>'ging'</b>."#,
+    ];
+
+    let project = Project::new("components: {}", "bridge: {}");
+    let mut languages = project.languages.languages();
+    languages.push(("xx", project.languages.default_rules()));
+
+    let mut body = String::new();
+    for (lang, _) in &languages {
+        for (index, sample) in samples.iter().enumerate() {
+            body.push_str(&format!(r#"<r-s lang="{}" text="[filter:smart]" id="{}-{}">{}</r-s>"#, lang, lang, index, sample));
+        }
+    }
+    let mut page = Page::load(&[Engine::Typography], &project, &body);
+
+    for (lang, rules) in &languages {
+        for (index, sample) in samples.iter().enumerate() {
+            let typeset = page.eval(&format!("document.getElementById('{}-{}').textContent", lang, index));
+            let text = ["<em>", "</em>", "<b>", "</b>"].iter().fold(sample.to_string(), |text, tag| text.replace(tag, ""));
+            // `apply_all` without `format_numbers`: number and punctuation
+            // spacing is left to `reedstyle typeset`
+            let expected = rules.apply_quotes(&rules.format_abbreviations(&rules.apply_replacements(&text)));
+            assert_eq!(typeset, expected, "{} for {:?}", lang, sample);
+        }
+    }
+
+    // The edge cases of `quotes::apply`
+    assert_eq!(page.eval("document.getElementById('en-6').textContent"), "the \u{2019}90s and the students\u{2019} books");
+    assert_eq!(page.eval("document.getElementById('en-8').textContent"), "(\u{201C}Route 66\u{201D}) and [ \u{201C}spaced\u{201D} ]");
+    assert_eq!(page.eval("document.getElementById('de-9').textContent"), "Wie geht\u{2019}s? Dr.\u{a0}Meier, z.\u{a0}B. um 9 Uhr");
+    assert_eq!(page.eval("document.getElementById('ja-11').textContent"), "彼は「こんにちは『世界』」と言った。「don\u{2019}t」");
+}
+
+#[test]
+fn test_typography_patterns_are_unicode_aware() {
+    // Rust's \b knows that "ż" and "ě" are letters, so "a" and "k" are not words here
+    let samples = [
+        ("pl", "duża dom i w nim kot"),
+        ("cs", "člověk je v domě a k nám"),
+    ];
+
+    let project = Project::new("components: {}", "bridge: {}");
+    let body: String = samples.iter().enumerate()
+        .map(|(index, (lang, sample))| format!(r#"<r-s lang="{}" text="[filter:smart]" id="sample-{}">{}</r-s>"#, lang, index, sample))
+        .collect();
+    let mut page = Page::load(&[Engine::Typography], &project, &body);
+
+    for (index, (lang, sample)) in samples.iter().enumerate() {
+        let typeset = page.eval(&format!("document.getElementById('sample-{}').textContent", index));
+        assert_eq!(typeset, project.languages.get(lang).apply_replacements(sample), "{} for {:?}", lang, sample);
+    }
+    assert_eq!(page.eval("document.getElementById('sample-0').textContent"), "duża dom i\u{a0}w\u{a0}nim kot");
+}
+
+#[test]
+fn test_api_forms() {
+    let project = Project::new("components: {}", "bridge: {}");
+    let mut page = Page::load(&[Engine::Forms], &project, concat!(
+        r#"<r-s api="contact.send" id="host"><form id="form" action="/contact" method="post">"#,
+        r#"<label>Email <input name="email" type="email" required></label>"#,
+        r#"<input name="topic" value="hello"><input name="topic" value="world">"#,
+        r#"<button type="submit">Send</button>"#,
+        r#"</form></r-s>"#,
+        r#"<form id="fallback" api="missing.endpoint" action="/fallback"><input name="q" value="x"></form>"#,
+    ));
+    page.run(r#"
+        globalThis.form = document.getElementById('form');
+        globalThis.host = document.getElementById('host');
+        globalThis.email = form.elements.namedItem('email');
+        globalThis.sent = [];
+        globalThis.events = [];
+        // Settled by the test
+        ReedStyle.api = { contact: { send: body => {
+            sent.push(body);
+            return new Promise((resolve, reject) => { globalThis.settle = { resolve, reject }; });
+        } } };
+        document.addEventListener('reedstyle:success', event => events.push(['success', event.detail]));
+        document.addEventListener('reedstyle:error', event => events.push(['error', event.detail.message]));
+        globalThis.type = value => {
+            email.value = value;
+            email.dispatchEvent(new Event('input', { bubbles: true }));
+        };
+        globalThis.message = () => document.querySelector('r-s[as="form-error"]');
+    "#);
+
+    // Invalid: a message after the label instead of a bubble, and no request
+    page.run("form.requestSubmit();");
+    assert_eq!(page.eval("[email.attributes.state, email.attributes['aria-invalid'], document.activeElement === email]"), json!(["invalid", "true", true]));
+    assert_eq!(page.eval("message().textContent"), "Please fill out this field.");
+    assert_eq!(page.eval("message().id === email.getAttribute('aria-describedby')"), true);
+    assert_eq!(page.eval("form.querySelector('label').parentNode.childNodes.indexOf(message())"), 1);
+    assert_eq!(page.eval("[sent.length, submitted]"), json!([0, []]));
+
+    // Rechecked while typing, cleared once valid
+    page.run("type('reed');");
+    assert_eq!(page.eval("message().textContent"), "Please include an '@' in the email address. 'reed' is missing an '@'.");
+    page.run("type('reed@example.com');");
+    assert_eq!(page.eval("[email.attributes.state ?? null, message().textContent]"), json!([null, ""]));
+
+    // Sent to the endpoint; a second submit while loading is blocked
+    page.run("form.requestSubmit(); form.requestSubmit();");
+    assert_eq!(page.eval("sent"), json!([{ "email": "reed@example.com", "topic": ["hello", "world"] }]));
+    assert_eq!(page.eval("[host.attributes.state, host.attributes['aria-busy'], submitted]"), json!(["loading", "true", []]));
+
+    page.run("settle.resolve({ id: 7 });");
+    assert_eq!(page.eval("[host.attributes.state, host.attributes['aria-busy'], email.value]"), json!(["success", "false", ""]));
+    assert_eq!(page.eval("events"), json!([["success", { "id": 7 }]]));
+
+    page.run("type('reed@example.com'); form.requestSubmit(); settle.reject(new Error('502 Bad Gateway'));");
+    assert_eq!(page.eval("[host.attributes.state, sent.length, events[1]]"), json!(["error", 2, ["error", "502 Bad Gateway"]]));
+
+    // Without the endpoint, the form submits to its action
+    page.run("document.getElementById('fallback').requestSubmit();");
+    assert_eq!(page.eval("submitted"), json!(["fallback"]));
+    assert!(page.logs().contains(&"warn: ReedSTYLE: no endpoint 'missing.endpoint', the form submits normally".into()));
+}
+
+#[test]
+fn test_api_retries() {
+    let mut project = Project::new("components: {}", "bridge: {}");
+    project.api = serde_yaml::from_str(concat!(
+        "endpoints:\n  forms:\n    contact: /contact\n    import: { path: /import, method: POST, retry: true }\n",
+        "  items:\n    list: { path: /items, method: GET }\n",
+        "config:\n  auth: { type: bearer, tokenKey: token }\n  errors: { retry: 2 }\n",
+    )).unwrap();
+    let mut page = Page::load(&[Engine::Api], &project, "");
+    page.run(r#"
+        // Backoff without waiting
+        globalThis.setTimeout = callback => { callback(); return 0; };
+        globalThis.failures = [];
+        for (const url of ['/contact', '/import', '/items']) served[url] = { status: 503, statusText: 'Service Unavailable' };
+        for (const call of [() => ReedStyle.api.forms.contact({ name: 'Reed' }), () => ReedStyle.api.forms.import([]), () => ReedStyle.api.items.list()]) {
+            call().catch(error => failures.push(error.status));
+        }
+    "#);
+
+    // A POST that may have been handled is sent once, unless its endpoint opts in
+    assert_eq!(page.eval("failures"), json!([503, 503, 503]));
+    assert_eq!(page.eval("fetched.map(request => request.url)"), json!(["/contact", "/import", "/items", "/import", "/items", "/import", "/items"]));
+
+    // Storage can be disabled
+    page.run("localStorage.setItem = () => { throw new Error('SecurityError'); }; ReedStyle.api.setToken('secret');");
+    assert_eq!(page.eval("ReedStyle.api.getToken()"), Value::Null);
+}
+
+#[test]
+fn test_embedded_data() {
+    let project = Project::new(
+        "components:\n  card:\n    box: \"[padding:4]\"\n",
+        "bridge:\n  bootstrap:\n    enabled: true\n    version: '5.3'\n    mappings: { btn: 'as=\"button\"' }\n",
+    );
+    let mut page = Page::load(&Engine::ALL, &project, "");
+
+    assert_eq!(page.eval("ReedStyle.version"), VERSION);
+    let engines: Vec<_> = Engine::ALL.iter().map(|engine| engine.name()).collect();
+    assert_eq!(page.eval("ReedStyle.engines.slice().sort()"), json!(engines.iter().copied().collect::<std::collections::BTreeSet<_>>()));
+    assert_eq!(page.eval("ReedStyle.palette.colors"), serde_json::to_value(&project.tokens.colors).unwrap());
+    assert_eq!(page.eval("ReedStyle.palette.semantic"), serde_json::to_value(&project.tokens.semantic).unwrap());
+    assert_eq!(page.eval("ReedStyle.bridge"), serde_json::to_value(&project.bridge.bridge).unwrap());
+    assert_eq!(page.eval("ReedStyle.components.definitions"), serde_json::to_value(&project.components.components).unwrap());
+
+    // The runtime converts data-root colors like the build converts reedstyle.colors.yaml
+    for color in ["#FF6B6B", "#4ECDC4", "#000000", "rgb(78, 205, 196)", "hsl(210, 50%, 40%)"] {
+        let runtime = page.eval(&format!("ReedStyle.toOklch('{}')", color));
+        let build = reedstyle::color::to_oklch(color).unwrap();
+        let build: Vec<f64> = build.trim_start_matches("oklch(").trim_end_matches(')')
+            .split(' ')
+            .map(|part| part.trim_end_matches('%').parse().unwrap())
+            .collect();
+
+        let close = |a: f64, b: f64, tolerance: f64| (a - b).abs() <= tolerance;
+        let l = runtime["l"].as_f64().unwrap() * 100.0;
+        let c = runtime["c"].as_f64().unwrap();
+        let h = runtime["h"].as_f64().unwrap();
+        assert!(close(l, build[0], 0.02) && close(c, build[1], 0.002), "{}: {} vs {:?}", color, runtime, build);
+        // Hue is meaningless without chroma
+        assert!(build[1] < 0.001 || close(h, build[2], 0.2), "{}: {} vs {:?}", color, runtime, build);
+    }
+}